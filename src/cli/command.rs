@@ -1,8 +1,36 @@
-use chrono::NaiveDate;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
-
-use crate::{models::item::ItemPriority, utils::{dates::parse_ymd, list_scope::ListScope, render::{RenderOpts, RenderOutput}}};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::{Args, Parser, Subcommand};
+use nanoid::nanoid;
+use std::{io, path::PathBuf};
+
+use crate::{
+    export::ExportFormat,
+    import::ImportFormat,
+    models::{
+        item::ItemPriority, journal::OperationKind, recurrence::Schedule, settings::Settings,
+        vault_config::StoreBackend,
+    },
+    store::{
+        fs::{
+            lock::VaultLock, settings_store::FsSettingsStore, shared::vault_name,
+            vault_registry::FsVaultRegistry,
+        },
+        read_only::ReadOnlyStore,
+        settings_store::SettingsStore,
+        vault_registry::VaultRegistry,
+        vault_store::{DiskStore, VaultStore},
+    },
+    utils::{
+        dates::{parse_due, parse_duration, parse_minutes, parse_month, parse_ymd},
+        day_ref::{OnDay, parse_day_pick, parse_day_task_ref},
+        list_scope::ListScope,
+        render::{RenderOpts, RenderOutput},
+        selection::{Pick, Selection, parse_id_pick, parse_pick},
+        stats_range::{StatsRange, parse_stats_range},
+        task_ref::{TaskRef, parse_task_ref},
+        tusk_error::TuskError,
+    },
+};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -17,9 +45,13 @@ pub struct Cli {
     #[arg(long, value_name = "DIR")]
     pub data_dir: Option<PathBuf>,
 
-    /// Specify the terminal output as Terminal, JSON or markdown.
-    #[arg(short, long, value_enum, default_value_t = RenderOutput::Terminal)]
-    pub output: RenderOutput,
+    /// Use this config file instead of `config.toml` in the platform config dir.
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Specify the terminal output as Terminal, JSON or markdown (default: terminal).
+    #[arg(short, long, value_enum)]
+    pub output: Option<RenderOutput>,
 
     /// Disable coloured output (useful in scripts or non-TTY environments).
     #[arg(short, long)]
@@ -33,6 +65,10 @@ pub struct Cli {
     #[arg(short, long)]
     pub vault: Option<String>,
 
+    /// Open the vault without changing anything, e.g. to browse an archive.
+    #[arg(long)]
+    pub read_only: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -69,24 +105,60 @@ pub enum Commands {
         /// Add a note to this item, opens in an external editor
         #[arg(short = 'n', long = "notes")]
         attach_notes: bool,
+
+        /// When the item is due, e.g. `tomorrow`, `fri 17:00`, `+3d` or `2025-10-20`.
+        #[arg(long = "due", value_parser = parse_due, value_name = "WHEN")]
+        due: Option<DateTime<Utc>>,
+
+        /// How long the item should take, e.g. `45m`, `2h` or `1h30m`.
+        #[arg(long = "estimate", value_parser = parse_minutes, value_name = "DURATION")]
+        estimate: Option<u32>,
+
+        /// Add this as a subtask of another item, by its index or id.
+        #[arg(long = "under", value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        under: Option<TaskRef>,
     },
 
-    #[command(name = "done", about = "Mark an item done by its index")]
+    #[command(name = "done", about = "Mark an item done by its index or id")]
     Done {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`, or
+        /// a day from `tusk week` or `tusk month` in front, e.g. `mon:3`.
+        #[arg(
+            value_parser = parse_day_pick,
+            value_delimiter = ',',
+            value_name = "[DAY:]INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<OnDay<Pick>>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
     },
 
-    #[command(name = "undone", about = "Mark an item undone by its index")]
+    #[command(name = "undone", about = "Mark an item undone by its index or id")]
     Undone {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`, or
+        /// a day from `tusk week` or `tusk month` in front, e.g. `mon:3`.
+        #[arg(
+            value_parser = parse_day_pick,
+            value_delimiter = ',',
+            value_name = "[DAY:]INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<OnDay<Pick>>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
     },
 
     #[command(name = "rm", about = "Remove an item from your list.")]
@@ -95,7 +167,19 @@ pub enum Commands {
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`, or
+        /// a day from `tusk week` or `tusk month` in front, e.g. `mon:3`.
+        #[arg(
+            value_parser = parse_day_pick,
+            value_delimiter = ',',
+            value_name = "[DAY:]INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<OnDay<Pick>>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
     },
 
     #[command(name = "edit", about = "Edit an item from your list.")]
@@ -104,25 +188,80 @@ pub enum Commands {
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
-        text: Option<String>,
-        /// Add a note to this item, opens in an external editor
-        #[arg(short = 'n', long = "notes")]
-        attach_notes: bool,
-        /// The priority of the item being edited.
-        #[arg(short = 'p', long = "priority")]
-        priority: Option<ItemPriority>,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `3` or `1 3 5-8`, with a day from `tusk week` or
+        /// `tusk month` in front to pick from it, e.g. `mon:1,3`. The new
+        /// text can follow a single item, e.g. `4 "Count sheep"`.
+        #[arg(
+            value_name = "[DAY:]INDEX|@ID|RANGE [TEXT]",
+            required_unless_present_any = ["tags", "all_open", "with_priority"]
+        )]
+        items: Vec<String>,
+
+        #[command(flatten)]
+        filters: EditFilterArgs,
+
+        #[command(flatten)]
+        changes: EditArgs,
     },
 
-    #[command(name = "show", about = "Show an item by its index.")]
+    #[command(
+        name = "promote",
+        about = "Move items from the day's list to your focus tasks."
+    )]
+    Promote {
+        /// The day to move the items from. Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`, or with a day from `tusk week` or
+        /// `tusk month` in front, e.g. `mon:3`.
+        #[arg(
+            value_parser = parse_day_pick,
+            value_delimiter = ',',
+            value_name = "[DAY:]INDEX|@ID|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<OnDay<Pick>>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+
+    #[command(name = "show", about = "Show an item by its index or id.")]
     Show {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The item's index, or `@` followed by its id (or a unique id prefix),
+        /// with a day from `tusk week` or `tusk month` in front, e.g. `mon:3`.
+        #[arg(value_parser = parse_day_task_ref, value_name = "[DAY:]INDEX|@ID")]
+        reference: OnDay<TaskRef>,
     },
 
+    #[command(
+        name = "start",
+        about = "Start a timer on an item, stopping any that's running."
+    )]
+    Start {
+        /// Target date (YYYY-MM-DD). Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+
+        /// The item's index, or `@` followed by its id (or a unique id prefix),
+        /// with a day from `tusk week` or `tusk month` in front, e.g. `mon:3`.
+        #[arg(value_parser = parse_day_task_ref, value_name = "[DAY:]INDEX|@ID")]
+        reference: OnDay<TaskRef>,
+    },
+
+    #[command(
+        name = "stop",
+        about = "Stop the running timer and log the time on its item."
+    )]
+    Stop,
+
     #[command(
         name = "migrate",
         about = "Migrate undone items from one date to another."
@@ -134,6 +273,31 @@ pub enum Commands {
         #[arg(name = "to", short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         to_date: Option<NaiveDate>,
 
+        /// The items to migrate by index, range or id, e.g. `1,3,5-7`. Defaults to every open item.
+        /// A day from `tusk week` or `tusk month` in front, e.g. `mon:3`, migrates from that day.
+        #[arg(value_name = "ITEMS", value_delimiter = ',', value_parser = parse_day_pick)]
+        items: Vec<OnDay<Pick>>,
+
+        /// Only migrate items with all of these tags.
+        #[arg(long = "tag", num_args = 1..)]
+        tags: Vec<String>,
+
+        /// Only migrate items with this priority.
+        #[arg(short = 'p', long = "priority")]
+        priority: Option<ItemPriority>,
+
+        /// Migrate the item with this id, can be repeated.
+        #[arg(long = "id", value_name = "ID", value_parser = parse_id_pick)]
+        ids: Vec<Pick>,
+
+        /// Ask what to do with each item: move, keep, drop or send to focus.
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Copy the items instead of moving them, leaving the originals in place.
+        #[arg(long)]
+        copy: bool,
+
         /// Perform a dry run to show you what changes will be made.
         #[arg(long = "dry-run")]
         dry_run: bool,
@@ -148,9 +312,338 @@ pub enum Commands {
         days: Option<u64>,
     },
 
+    #[command(
+        name = "week",
+        about = "Show every day of a week side by side, with your focus tasks."
+    )]
+    Week {
+        /// Any day in the week (YYYY-MM-DD). Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+    },
+
+    #[command(
+        name = "month",
+        about = "Show every day of a month, with your focus tasks."
+    )]
+    Month {
+        /// Any day in the month (YYYY-MM-DD). Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+    },
+
+    #[command(
+        name = "cal",
+        about = "Show a month or year of days coloured by how much got done."
+    )]
+    Cal {
+        /// The month to show (YYYY-MM). Defaults to this month.
+        #[arg(long, value_parser = parse_month, value_name = "YYYY-MM", conflicts_with = "year")]
+        month: Option<NaiveDate>,
+
+        /// Show a whole year as a heatmap instead.
+        #[arg(long, value_name = "YYYY")]
+        year: Option<i32>,
+    },
+
+    #[command(
+        name = "stats",
+        about = "Show completion rates, streaks and trends over a range of days."
+    )]
+    Stats {
+        /// How far back to look: days, weeks, months or years, e.g. `30d`, `12w`, `6m` or `1y`.
+        #[arg(long = "range", value_parser = parse_stats_range, value_name = "RANGE", default_value = "30d")]
+        range: StatsRange,
+    },
+
+    #[command(
+        name = "due",
+        about = "List overdue items and items due soon across all days and focus."
+    )]
+    Due {
+        /// How many days ahead count as due soon.
+        #[arg(long = "soon", value_name = "DAYS")]
+        soon: Option<i64>,
+    },
+
+    #[command(
+        name = "stale",
+        about = "List open items that keep getting migrated, and drop, focus or reprioritise them."
+    )]
+    Stale {
+        /// How many migrations make an item stale.
+        #[arg(long = "min-migrations", value_name = "N")]
+        min_migrations: Option<usize>,
+
+        /// Remove the stale items.
+        #[arg(long, conflicts_with_all = ["focus", "priority"])]
+        drop: bool,
+
+        /// Move the stale items to your focus tasks.
+        #[arg(long, conflicts_with = "priority")]
+        focus: bool,
+
+        /// Give the stale items this priority.
+        #[arg(short = 'p', long = "priority")]
+        priority: Option<ItemPriority>,
+    },
+
+    #[command(name = "undo", about = "Undo the most recent change.")]
+    Undo {
+        /// Undo even if the files have changed since, discarding those changes.
+        #[arg(long)]
+        force: bool,
+    },
+
+    #[command(name = "redo", about = "Redo the most recently undone change.")]
+    Redo {
+        /// Redo even if the files have changed since, discarding those changes.
+        #[arg(long)]
+        force: bool,
+    },
+
+    #[command(name = "log", about = "List recent changes that can be undone.")]
+    Log {
+        /// How many operations to show, most recent first.
+        #[arg(short = 'n', long = "limit", default_value_t = 10)]
+        limit: usize,
+    },
+
+    #[command(
+        name = "search",
+        about = "Search every day and your focus tasks.",
+        long_about = "Search every day and your focus tasks. Words match the text and notes, \
+                      and can be combined with #tag, priority:high, status:open, migrated:true, \
+                      before:YYYY-MM-DD and after:YYYY-MM-DD. Terms are ANDed together, use \
+                      OR, NOT or a leading '-' and parentheses for anything else."
+    )]
+    Search {
+        /// The search query, e.g. `#work status:open (review OR "1:1")`.
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        query: Vec<String>,
+
+        /// Limit the search to daily tasks or focus tasks.
+        #[arg(short = 's', long = "scope")]
+        scope: Option<ListScope>,
+    },
+
+    #[command(
+        name = "export",
+        about = "Write a range of days to a Markdown, CSV, iCalendar, HTML or JSON file."
+    )]
+    Export {
+        /// The first day to export (YYYY-MM-DD), defaults to today.
+        #[arg(name = "from", short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        from_date: Option<NaiveDate>,
+
+        /// The last day to export (YYYY-MM-DD), defaults to today.
+        #[arg(name = "to", short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        to_date: Option<NaiveDate>,
+
+        /// The file format to write.
+        #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+        format: ExportFormat,
+
+        /// Include your focus tasks.
+        #[arg(long)]
+        focus: bool,
+
+        /// A file to write to, or a directory for one file per day. Prints to stdout if omitted.
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+
+    #[command(
+        name = "import",
+        about = "Add tasks from a todo.txt, Taskwarrior, Markdown or tusk JSON file.",
+        long_about = "Add tasks from a todo.txt, Taskwarrior, Markdown or tusk JSON file. \
+                      Each task goes under the day it was created, or else the day it's due. \
+                      Tasks already on that day with the same text are skipped."
+    )]
+    Import {
+        /// The file to read, or `-` for stdin.
+        file: PathBuf,
+
+        /// The format of the file.
+        #[arg(long, value_enum)]
+        format: ImportFormat,
+
+        /// Show what would be imported without writing anything.
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+
     /// Manage persistent focus tasks
     #[clap(subcommand)]
     Focus(FocusCommands),
+
+    /// Manage recurring tasks
+    #[clap(subcommand)]
+    Recur(RecurCommands),
+
+    /// Manage how the vault is stored
+    #[clap(subcommand)]
+    Store(StoreCommands),
+
+    /// Read and change your defaults
+    #[clap(subcommand)]
+    Config(ConfigCommands),
+
+    /// List, create and switch between vaults
+    #[clap(subcommand)]
+    Vault(VaultCommands),
+
+    #[command(
+        name = "ui",
+        about = "Work through your focus and daily tasks full screen"
+    )]
+    Ui,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VaultCommands {
+    #[command(
+        name = "ls",
+        about = "List every vault with its task counts and last activity"
+    )]
+    Ls,
+
+    #[command(name = "create", about = "Create an empty vault")]
+    Create {
+        /// The vault's name: letters, numbers, `-` and `_`.
+        name: String,
+    },
+
+    #[command(name = "rename", about = "Rename a vault")]
+    Rename {
+        /// The vault to rename.
+        from: String,
+        /// Its new name.
+        to: String,
+    },
+
+    #[command(name = "rm", about = "Delete a vault and everything in it")]
+    Rm {
+        /// The vault to delete.
+        name: String,
+        /// Delete it even if it still has tasks.
+        #[arg(long)]
+        force: bool,
+    },
+
+    #[command(name = "copy", about = "Copy a vault, history included, to a new name")]
+    Copy {
+        /// The vault to copy.
+        from: String,
+        /// The new vault's name.
+        to: String,
+    },
+
+    #[command(
+        name = "current",
+        about = "Show the vault commands use without `--vault`, or switch to another"
+    )]
+    Current {
+        /// The vault to switch to.
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    #[command(name = "get", about = "Show the value in use for a config key")]
+    Get {
+        /// The key, e.g. `default-priority` or `alias.today`.
+        key: String,
+    },
+
+    #[command(name = "set", about = "Set a config key")]
+    Set {
+        /// The key, e.g. `default-priority` or `alias.today`.
+        key: String,
+        /// The new value.
+        value: String,
+        /// Only set it for the current vault.
+        #[arg(long)]
+        local: bool,
+    },
+
+    #[command(
+        name = "unset",
+        about = "Clear a config key, going back to the default"
+    )]
+    Unset {
+        /// The key, e.g. `default-priority` or `alias.today`.
+        key: String,
+        /// Only clear it for the current vault.
+        #[arg(long)]
+        local: bool,
+    },
+
+    #[command(
+        name = "ls",
+        about = "List every config key and where its value comes from"
+    )]
+    Ls,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StoreCommands {
+    #[command(
+        name = "migrate",
+        about = "Copy the vault to another storage backend and switch to it."
+    )]
+    Migrate {
+        /// The backend to move the vault to.
+        #[arg(long = "to", value_enum)]
+        to: StoreBackend,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RecurCommands {
+    #[command(name = "ls", about = "List recurring rules.")]
+    Ls,
+
+    #[command(name = "add", about = "Add a rule that adds an item on a schedule.")]
+    Add {
+        /// The description of the items the rule adds.
+        text: String,
+
+        /// When the rule adds an item: daily, weekdays, 3d, mon,thu, monthly:15 or
+        /// cron:'<day-of-month> <month> <day-of-week>'.
+        #[arg(short = 'e', long = "every", value_name = "SCHEDULE")]
+        schedule: Schedule,
+
+        /// The priority of the items the rule adds.
+        #[arg(short = 'p', long = "priority")]
+        priority: Option<ItemPriority>,
+
+        /// The first date the rule applies to. Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        start: Option<NaiveDate>,
+    },
+
+    #[command(name = "rm", about = "Remove a recurring rule.")]
+    Rm {
+        /// The rule's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
+    },
+
+    #[command(name = "pause", about = "Stop a rule adding items until it's resumed.")]
+    Pause {
+        /// The rule's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
+    },
+
+    #[command(name = "resume", about = "Resume a paused rule.")]
+    Resume {
+        /// The rule's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -162,24 +655,64 @@ pub enum FocusCommands {
     Add {
         /// The description of the item being added.
         text: String,
+
+        /// When the item is due, e.g. `tomorrow`, `fri 17:00`, `+3d` or `2025-10-20`.
+        #[arg(long = "due", value_parser = parse_due, value_name = "WHEN")]
+        due: Option<DateTime<Utc>>,
+
+        /// How long the item should take, e.g. `45m`, `2h` or `1h30m`.
+        #[arg(long = "estimate", value_parser = parse_minutes, value_name = "DURATION")]
+        estimate: Option<u32>,
+
+        /// Add this as a subtask of another item, by its index or id.
+        #[arg(long = "under", value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        under: Option<TaskRef>,
     },
 
-    #[command(name = "done", about = "Mark a long running item done by its index")]
+    #[command(
+        name = "done",
+        about = "Mark a long running item done by its index or id"
+    )]
     Done {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`.
+        #[arg(
+            value_parser = parse_pick,
+            value_delimiter = ',',
+            value_name = "INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<Pick>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
     },
 
-    #[command(name = "undone", about = "Mark a long running item undone by its index")]
+    #[command(
+        name = "undone",
+        about = "Mark a long running item undone by its index or id"
+    )]
     Undone {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`.
+        #[arg(
+            value_parser = parse_pick,
+            value_delimiter = ',',
+            value_name = "INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<Pick>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
     },
 
     #[command(name = "rm", about = "Remove a long running item from your list.")]
@@ -187,32 +720,290 @@ pub enum FocusCommands {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
-        /// The index of the item to be removed.
-        index: usize,
+
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`. Add a dot for a subtask, e.g. `3.2`.
+        #[arg(
+            value_parser = parse_pick,
+            value_delimiter = ',',
+            value_name = "INDEX|@ID[.SUB]|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<Pick>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+
+    #[command(name = "edit", about = "Edit a long running item.")]
+    Edit {
+        /// Target date (YYYY-MM-DD). Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `3` or `1 3 5-8`. The new text can follow a single
+        /// item, e.g. `4 "Count sheep"`.
+        #[arg(
+            value_name = "INDEX|@ID|RANGE [TEXT]",
+            required_unless_present_any = ["tags", "all_open", "with_priority"]
+        )]
+        items: Vec<String>,
+
+        #[command(flatten)]
+        filters: EditFilterArgs,
+
+        #[command(flatten)]
+        changes: EditArgs,
     },
 
-    #[command(name = "show", about = "Show a long running item by its index.")]
+    #[command(name = "demote", about = "Move long running items to a day's list.")]
+    Demote {
+        /// The day to move the items to. Defaults to today if omitted.
+        #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
+        date: Option<NaiveDate>,
+
+        /// The items by index, range or `@` followed by an id (or a unique id
+        /// prefix), e.g. `1 3 5-8`.
+        #[arg(
+            value_parser = parse_pick,
+            value_delimiter = ',',
+            value_name = "INDEX|@ID|RANGE",
+            required_unless_present_any = ["tags", "all_open", "priority"]
+        )]
+        items: Vec<Pick>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+
+    #[command(name = "show", about = "Show a long running item by its index or id.")]
     Show {
         /// Target date (YYYY-MM-DD). Defaults to today if omitted.
         #[arg(short, long, value_parser = parse_ymd, value_name = "YYYY-MM-DD")]
         date: Option<NaiveDate>,
 
-        index: usize,
+        /// The item's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
+    },
+
+    #[command(name = "start", about = "Start a timer on a long running item.")]
+    Start {
+        /// The item's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
     },
+
+    #[command(
+        name = "pomodoro",
+        about = "Work on a long running item in timed rounds."
+    )]
+    Pomodoro {
+        /// The item's index, or `@` followed by its id (or a unique id prefix).
+        #[arg(value_parser = parse_task_ref, value_name = "INDEX|@ID")]
+        reference: TaskRef,
+
+        /// How long each round of work lasts, e.g. `25m` or `50m`.
+        #[arg(long = "work", value_parser = parse_duration, value_name = "DURATION", default_value = "25m")]
+        work: Duration,
+
+        /// How long the break between rounds lasts.
+        #[arg(long = "break", value_parser = parse_duration, value_name = "DURATION", default_value = "5m")]
+        rest: Duration,
+
+        /// How many rounds of work to do.
+        #[arg(long = "rounds", value_name = "N", default_value_t = 4)]
+        rounds: u32,
+    },
+}
+
+/// Filters for commands that work on several items at once.
+#[derive(Args, Debug)]
+pub struct FilterArgs {
+    /// Only the items with all of these tags.
+    #[arg(long = "tag", num_args = 1..)]
+    pub tags: Vec<String>,
+    /// Only the items that are still open.
+    #[arg(long = "all-open")]
+    pub all_open: bool,
+    /// Only the items with this priority.
+    #[arg(short = 'p', long = "priority")]
+    pub priority: Option<ItemPriority>,
+}
+
+impl FilterArgs {
+    pub fn select(self, picks: Vec<Pick>) -> Selection {
+        Selection {
+            picks,
+            tags: self.tags,
+            priority: self.priority,
+            open_only: self.all_open,
+        }
+    }
+}
+
+/// [`FilterArgs`] for `edit`, where `-p` sets the priority, so the priority
+/// filter is `--with-priority` instead.
+#[derive(Args, Debug)]
+pub struct EditFilterArgs {
+    /// Only the items with all of these tags.
+    #[arg(long = "tag", num_args = 1..)]
+    pub tags: Vec<String>,
+    /// Only the items that are still open.
+    #[arg(long = "all-open")]
+    pub all_open: bool,
+    /// Only the items with this priority.
+    #[arg(long = "with-priority", value_name = "PRIORITY")]
+    pub with_priority: Option<ItemPriority>,
+}
+
+impl EditFilterArgs {
+    pub fn select(self, picks: Vec<Pick>) -> Selection {
+        Selection {
+            picks,
+            tags: self.tags,
+            priority: self.with_priority,
+            open_only: self.all_open,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct EditArgs {
+    /// The new description of the item, for text that looks like an item
+    /// reference.
+    #[arg(short = 't', long = "text")]
+    pub text: Option<String>,
+    /// Add a note to this item, opens in an external editor
+    #[arg(short = 'n', long = "notes")]
+    pub attach_notes: bool,
+    /// The priority of the item being edited.
+    #[arg(short = 'p', long = "priority")]
+    pub priority: Option<ItemPriority>,
+    /// When the item is due, e.g. `tomorrow`, `fri 17:00`, `+3d` or `2025-10-20`.
+    #[arg(long = "due", value_parser = parse_due, value_name = "WHEN", conflicts_with = "clear_due")]
+    pub due: Option<DateTime<Utc>>,
+    /// Remove the item's due date.
+    #[arg(long = "no-due")]
+    pub clear_due: bool,
+    /// How long the item should take, e.g. `45m`, `2h` or `1h30m`.
+    #[arg(long = "estimate", value_parser = parse_minutes, value_name = "DURATION", conflicts_with = "clear_estimate")]
+    pub estimate: Option<u32>,
+    /// Remove the item's estimate.
+    #[arg(long = "no-estimate")]
+    pub clear_estimate: bool,
+    /// Add a tag to the item, can be repeated.
+    #[arg(long = "add-tag", value_name = "TAG")]
+    pub add_tags: Vec<String>,
+    /// Remove a tag from the item, and from its text, can be repeated.
+    #[arg(long = "rm-tag", value_name = "TAG")]
+    pub rm_tags: Vec<String>,
+    /// Remove all of the item's tags, and the ones written in its text.
+    #[arg(long = "clear-tags")]
+    pub clear_tags: bool,
+}
+
+impl EditArgs {
+    /// Adds the text given after the item, which can't be used with `--text`.
+    pub fn with_text(mut self, text: Option<String>) -> Result<Self, TuskError> {
+        if text.is_some() && self.text.is_some() {
+            return Err(TuskError::InvalidInput {
+                message: "Give the new text once, either after the item or with --text."
+                    .to_string(),
+            });
+        }
+
+        self.text = self.text.or(text);
+        Ok(self)
+    }
 }
 
 pub struct CommandContext {
-    pub data_dir: Option<PathBuf>,
-    pub vault: Option<String>,
+    /// The global config file, `None` for the platform default.
+    pub config_path: Option<PathBuf>,
+    /// The global config with the vault's own settings laid over it.
+    pub settings: Settings,
     pub render_opts: RenderOpts,
+    /// The vault every command reads from and writes to, opened once at startup.
+    pub store: Box<dyn VaultStore>,
+    /// Groups every file this invocation writes into one journal operation.
+    pub operation: OperationContext,
 }
 
-impl From<&Cli> for CommandContext {
-    fn from(cli: &Cli) -> Self {
+pub struct OperationContext {
+    pub id: String,
+    pub kind: OperationKind,
+    pub command: &'static str,
+}
+
+impl OperationContext {
+    pub fn new(command: &'static str) -> Self {
         Self {
-            data_dir: cli.data_dir.clone(),
-            vault: cli.vault.clone(),
-            render_opts: RenderOpts::from(cli),
+            id: nanoid!(8),
+            kind: OperationKind::Do,
+            command,
         }
     }
-}
\ No newline at end of file
+}
+
+impl CommandContext {
+    /// Takes the vault lock, released when dropped. A read-only vault never
+    /// writes, so it isn't locked.
+    pub fn lock(&self) -> Result<Option<VaultLock>, TuskError> {
+        Ok(self.store.lock()?)
+    }
+}
+
+impl TryFrom<&Cli> for CommandContext {
+    type Error = TuskError;
+
+    fn try_from(cli: &Cli) -> Result<Self, Self::Error> {
+        let vault = resolve_vault(cli.data_dir.clone(), cli.vault.as_deref())?;
+        let disk = DiskStore::open(cli.data_dir.clone(), vault.as_deref())?;
+
+        let global = load_settings(cli.config.clone())?;
+        let settings = match disk.config().load() {
+            Ok(config) => config.settings.or(&global),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => global,
+            Err(e) => return Err(e.into()),
+        };
+
+        let store: Box<dyn VaultStore> = if cli.read_only {
+            Box::new(ReadOnlyStore::new(Box::new(disk)))
+        } else {
+            Box::new(disk)
+        };
+
+        Ok(Self {
+            config_path: cli.config.clone(),
+            render_opts: RenderOpts::new(cli, &settings, vault),
+            settings,
+            store,
+            operation: OperationContext::new(""),
+        })
+    }
+}
+
+/// The vault to use: `--vault` if given, otherwise the one picked with
+/// `tusk vault current`. `None` is the default vault.
+pub fn resolve_vault(
+    data_dir: Option<PathBuf>,
+    flag: Option<&str>,
+) -> Result<Option<String>, TuskError> {
+    match flag {
+        Some(name) => Ok(Some(
+            vault_name(name).map_err(|message| TuskError::InvalidInput { message })?,
+        )),
+        None => Ok(FsVaultRegistry::new(data_dir)?.current()?),
+    }
+}
+
+/// The global config, empty if there isn't a config file yet.
+pub fn load_settings(path: Option<PathBuf>) -> io::Result<Settings> {
+    match FsSettingsStore::new(path)?.load() {
+        Ok(settings) => Ok(settings),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(e),
+    }
+}
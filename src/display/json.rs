@@ -6,22 +6,88 @@ use serde::Serialize;
 use crate::{
     display::{
         json::{
-            action_output::ActionOutput, agenda_output::AgendaOutput, dayfile_output::{DayFileOutput, DayOutput}, error_output::ErrorOutput, migrate_output::MigrateOutput, response::{ErrorResponse, Response}, review_output::ReviewOutput, show_output::{Reference, ReferenceKind, ShowOutput}
+            action_output::{ActionOutput, BatchOutput},
+            agenda_output::AgendaOutput,
+            calendar_output::CalendarOutput,
+            config_output::ConfigOutput,
+            dayfile_output::{DayFileOutput, DayOutput},
+            due_output::DueOutput,
+            error_output::ErrorOutput,
+            export_output::ExportOutput,
+            history_output::{HistoryActionOutput, HistoryEntryOutput, HistoryOutput},
+            import_output::ImportOutput,
+            migrate_output::MigrateOutput,
+            plan_output::PlanOutput,
+            pomodoro_output::PomodoroOutput,
+            recurring_output::{RuleActionOutput, RuleOutput, RulesOutput},
+            response::{ErrorResponse, Response},
+            review_output::ReviewOutput,
+            rollover_output::RolloverOutput,
+            search_output::SearchOutput,
+            show_output::{Reference, ShowOutput},
+            stale_output::StaleOutput,
+            stats_output::StatsOutput,
+            store_output::StoreMigrationOutput,
+            timer_output::TimerOutput,
+            vault_output::{VaultActionOutput, VaultOutput, VaultsOutput},
         },
         renderer::Renderer,
     },
-    models::{dayfile::DayFile, item::Item},
-    utils::{helpers::item_count_meta, render::ActionKind, tusk_error::TuskError}, view::agenda::Agenda,
+    models::{dayfile::DayFile, item::Item, recurrence::RecurrenceRule},
+    utils::{
+        dates::todays_date,
+        helpers::item_count_meta,
+        render::{ActionKind, ConfigAction, HistoryAction, RuleAction, VaultAction},
+        task_ref::{TaskPath, TaskRef},
+        tusk_error::TuskError,
+    },
+    view::{
+        agenda::Agenda,
+        batch::Batch,
+        calendar::Calendar,
+        config::ConfigEntry,
+        due::DueReport,
+        export::ExportReport,
+        history::{History, HistoryEntry},
+        import::ImportReport,
+        migration::Migration,
+        plan::Plan,
+        pomodoro::PomodoroRun,
+        rollover::Rollover,
+        search::SearchResults,
+        stale::StaleReport,
+        stats::StatsReport,
+        store_migration::StoreMigration,
+        time_report::TimeReport,
+        timer::TimerChange,
+        vault::VaultSummary,
+    },
 };
 
 mod action_output;
+mod agenda_output;
+mod calendar_output;
+mod config_output;
 mod dayfile_output;
+mod due_output;
 mod error_output;
+mod export_output;
+mod history_output;
+mod import_output;
 mod migrate_output;
+mod plan_output;
+mod pomodoro_output;
+mod recurring_output;
 mod response;
 mod review_output;
+mod rollover_output;
+mod search_output;
 mod show_output;
-mod agenda_output;
+mod stale_output;
+mod stats_output;
+mod store_output;
+mod timer_output;
+mod vault_output;
 
 pub struct JsonRenderer;
 
@@ -39,20 +105,19 @@ impl Renderer for JsonRenderer {
         Self::to_json(&response)
     }
 
-    fn render_summary(&self, date: Option<NaiveDate>, index: usize, item: &Item) -> std::io::Result<()> {
-        let payload = ShowOutput::new(index, date, item);
+    fn render_summary(
+        &self,
+        date: Option<NaiveDate>,
+        reference: &TaskRef,
+        item: &Item,
+    ) -> std::io::Result<()> {
+        let payload = ShowOutput::new(reference, date, item);
         let response = Response::new("show", &payload);
         Self::to_json(&response)
     }
 
-    fn render_migrate(
-        &self,
-        to_date: NaiveDate,
-        from_df_original: &DayFile,
-        moved_items: &[Item],
-        dry_run: bool,
-    ) -> std::io::Result<()> {
-        let payload = MigrateOutput::new(dry_run, from_df_original, to_date, moved_items);
+    fn render_migrate(&self, migration: &Migration) -> std::io::Result<()> {
+        let payload = MigrateOutput::from(migration);
         let response = Response::new("migrate", &payload);
         Self::to_json(&response)
     }
@@ -63,26 +128,24 @@ impl Renderer for JsonRenderer {
         end: NaiveDate,
         days: u64,
         dayfiles: &[DayFile],
+        time: &TimeReport,
     ) -> std::io::Result<()> {
         let count = item_count_meta(dayfiles);
-        let payload = ReviewOutput::new(days, start, end, true, dayfiles, count);
+        let payload = ReviewOutput::new(days, start, end, true, dayfiles, count, time);
         let response = Response::new("review", &payload);
         Self::to_json(&response)
     }
 
     fn render_action(
         &self,
-        index: usize,
+        reference: &TaskPath,
         date: NaiveDate,
         action: ActionKind,
         item: Option<&Item>,
     ) -> std::io::Result<()> {
         let payload = ActionOutput::new(
             DayOutput { date: Some(date), path: None },
-            Reference {
-                kind: ReferenceKind::Index,
-                value: index,
-            },
+            Reference::from(reference),
             action.as_result(),
             item.map(Into::into),
         );
@@ -91,6 +154,164 @@ impl Renderer for JsonRenderer {
         Self::to_json(&response)
     }
 
+    fn render_batch(&self, batch: &Batch) -> std::io::Result<()> {
+        let payload = BatchOutput::from(batch);
+        let response = Response::new(batch.action.as_command(), &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_due(&self, report: &DueReport) -> std::io::Result<()> {
+        let payload = DueOutput::from(report);
+        let response = Response::new("due", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_search(&self, results: &SearchResults) -> std::io::Result<()> {
+        let payload = SearchOutput::from(results);
+        let response = Response::new("search", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_rules(&self, rules: &[RecurrenceRule], today: NaiveDate) -> std::io::Result<()> {
+        let payload = RulesOutput::new(rules, today);
+        let response = Response::new("recur ls", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_rule_action(
+        &self,
+        reference: &TaskRef,
+        action: RuleAction,
+        rule: &RecurrenceRule,
+    ) -> std::io::Result<()> {
+        let payload = RuleActionOutput {
+            reference: Reference::from(reference),
+            result: action.as_result(),
+            rule: RuleOutput::new(rule, todays_date()),
+        };
+
+        let response = Response::new(action.as_command(), payload);
+        Self::to_json(&response)
+    }
+
+    fn render_history(&self, history: &History) -> std::io::Result<()> {
+        let payload = HistoryOutput::from(history);
+        let response = Response::new("log", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_history_action(
+        &self,
+        action: HistoryAction,
+        entry: &HistoryEntry,
+    ) -> std::io::Result<()> {
+        let payload = HistoryActionOutput {
+            operation: HistoryEntryOutput::from(entry),
+        };
+
+        let response = Response::new(action.as_command(), payload);
+        Self::to_json(&response)
+    }
+
+    fn render_store_migrate(&self, migration: &StoreMigration) -> std::io::Result<()> {
+        let payload = StoreMigrationOutput::from(migration);
+        let response = Response::new("store migrate", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_config(&self, action: ConfigAction, entries: &[ConfigEntry]) -> std::io::Result<()> {
+        let payload = ConfigOutput::from(entries);
+        let response = Response::new(action.as_command(), &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_vaults(&self, vaults: &[VaultSummary]) -> std::io::Result<()> {
+        let payload = VaultsOutput {
+            vaults: vaults.iter().map(VaultOutput::from).collect(),
+        };
+
+        let response = Response::new("vault ls", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_vault_action(
+        &self,
+        action: VaultAction,
+        vault: &str,
+        from: Option<&str>,
+    ) -> std::io::Result<()> {
+        let payload = VaultActionOutput {
+            result: action.as_result(),
+            vault,
+            from,
+        };
+
+        let response = Response::new(action.as_command(), payload);
+        Self::to_json(&response)
+    }
+
+    fn render_export(&self, report: &ExportReport) -> std::io::Result<()> {
+        let payload = ExportOutput::from(report);
+        let response = Response::new("export", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_import(&self, report: &ImportReport) -> std::io::Result<()> {
+        let payload = ImportOutput::from(report);
+        let response = Response::new("import", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_rollover(&self, rollover: &Rollover) -> std::io::Result<()> {
+        let payload = RolloverOutput::from(rollover);
+        let response = Response::new("rollover", &payload);
+
+        let mut err = io::stderr().lock();
+        serde_json::to_writer(&mut err, &response)?;
+        writeln!(err)
+    }
+
+    fn render_stale(&self, report: &StaleReport) -> std::io::Result<()> {
+        let payload = StaleOutput::from(report);
+        let response = Response::new("stale", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_timer(&self, change: &TimerChange) -> std::io::Result<()> {
+        let command = if change.started.is_some() {
+            "start"
+        } else {
+            "stop"
+        };
+        let payload = TimerOutput::from(change);
+        let response = Response::new(command, &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_pomodoro(&self, run: &PomodoroRun) -> std::io::Result<()> {
+        let payload = PomodoroOutput::from(run);
+        let response = Response::new("focus pomodoro", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_stats(&self, report: &StatsReport) -> std::io::Result<()> {
+        let payload = StatsOutput::from(report);
+        let response = Response::new("stats", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_calendar(&self, calendar: &Calendar) -> std::io::Result<()> {
+        let payload = CalendarOutput::from(calendar);
+        let response = Response::new("cal", &payload);
+        Self::to_json(&response)
+    }
+
+    fn render_plan(&self, plan: &Plan) -> std::io::Result<()> {
+        let payload = PlanOutput::from(plan);
+        let response = Response::new(plan.span.as_command(), &payload);
+        Self::to_json(&response)
+    }
+
     fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()> {
         let payload = ErrorOutput {
             code: e.code(),
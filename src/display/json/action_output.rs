@@ -2,10 +2,14 @@ use serde::Serialize;
 
 use crate::{
     display::json::{
-        dayfile_output::{DayOutput, ItemOutput},
+        dayfile_output::{DayOutput, ItemOutput, SubtaskOutput},
         show_output::Reference,
     },
-    utils::render::ActionKind,
+    utils::{
+        render::{ActionKind, RuleAction},
+        task_target::TaskTarget,
+    },
+    view::batch::Batch,
 };
 
 #[derive(Debug, Serialize)]
@@ -33,18 +37,75 @@ impl ActionOutput {
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct BatchOutput {
+    day: DayOutput,
+    result: ActionResult,
+    count: usize,
+    items: Vec<BatchItemOutput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemOutput {
+    focus: bool,
+    reference: Reference,
+    item: ItemOutput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtask: Option<SubtaskOutput>,
+}
+
+impl From<&Batch> for BatchOutput {
+    fn from(value: &Batch) -> Self {
+        Self {
+            day: DayOutput {
+                date: Some(value.date),
+                path: None,
+            },
+            result: value.action.as_result(),
+            count: value.entries.len(),
+            items: value
+                .entries
+                .iter()
+                .map(|e| BatchItemOutput {
+                    focus: e.target == TaskTarget::Focus,
+                    reference: Reference::from(&e.reference),
+                    item: ItemOutput::from(&e.item),
+                    subtask: e
+                        .subtask
+                        .as_ref()
+                        .zip(e.reference.subtask)
+                        .map(|(s, index)| SubtaskOutput::new(index, s)),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ActionResult {
+    Created,
     Updated,
     Removed,
+    Moved,
 }
 
 impl ActionKind {
     pub fn as_result(&self) -> ActionResult {
         match self {
-            ActionKind::Done | ActionKind::Undone => ActionResult::Updated,
+            ActionKind::Done | ActionKind::Undone | ActionKind::Edited => ActionResult::Updated,
             ActionKind::Removed => ActionResult::Removed,
+            ActionKind::Promoted | ActionKind::Demoted => ActionResult::Moved,
+        }
+    }
+}
+
+impl RuleAction {
+    pub fn as_result(&self) -> ActionResult {
+        match self {
+            RuleAction::Added => ActionResult::Created,
+            RuleAction::Paused | RuleAction::Resumed => ActionResult::Updated,
+            RuleAction::Removed => ActionResult::Removed,
         }
     }
 }
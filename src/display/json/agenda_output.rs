@@ -17,22 +17,14 @@ pub struct AgendaOutput {
 impl From<&Agenda> for AgendaOutput {
     fn from(value: &Agenda) -> Self {
         Self {
-            focus: if let Some(ff) = &value.focusfile {
-                Some(FocusFileOutput::from(ff))
-            } else {
-                None
-            },
-            day: if let Some(df) = &value.dayfile {
-                Some(DayFileOutput::from(df))
-            } else {
-                None
-            },
+            focus: value.focusfile.as_ref().map(FocusFileOutput::from),
+            day: value.dayfile.as_ref().map(DayFileOutput::from),
         }
     }
 }
 
 #[derive(Serialize, Debug)]
-struct FocusFileOutput {
+pub struct FocusFileOutput {
     stats: DayStatsOutput,
     items: Vec<ItemOutput>,
 }
@@ -0,0 +1,63 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::view::calendar::{Calendar, CalendarDay, CalendarSpan};
+
+#[derive(Serialize, Debug)]
+pub struct CalendarOutput {
+    span: &'static str,
+    from: NaiveDate,
+    to: NaiveDate,
+    active_days: usize,
+    done: usize,
+    total: usize,
+    /// Monday to Sunday, `null` for days either side of the span.
+    weeks: Vec<[Option<CalendarDayOutput>; 7]>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CalendarDayOutput {
+    date: NaiveDate,
+    total: usize,
+    done: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate: Option<f64>,
+    open_migrated: usize,
+    due: usize,
+}
+
+impl From<&CalendarDay> for CalendarDayOutput {
+    fn from(value: &CalendarDay) -> Self {
+        Self {
+            date: value.date,
+            total: value.total,
+            done: value.done,
+            rate: value.rate(),
+            open_migrated: value.open_migrated,
+            due: value.due,
+        }
+    }
+}
+
+impl From<&Calendar> for CalendarOutput {
+    fn from(value: &Calendar) -> Self {
+        let (done, total) = value.totals();
+
+        Self {
+            span: match value.span {
+                CalendarSpan::Month(_) => "month",
+                CalendarSpan::Year(_) => "year",
+            },
+            from: value.start(),
+            to: value.end(),
+            active_days: value.active_days(),
+            done,
+            total,
+            weeks: value
+                .weeks()
+                .into_iter()
+                .map(|week| week.map(|day| day.map(Into::into)))
+                .collect(),
+        }
+    }
+}
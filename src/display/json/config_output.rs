@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+use crate::view::config::{ConfigEntry, ConfigSource};
+
+#[derive(Serialize, Debug)]
+pub struct ConfigOutput {
+    pub entries: Vec<ConfigEntryOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigEntryOutput {
+    key: String,
+    value: Option<String>,
+    source: ConfigSource,
+}
+
+impl From<&ConfigEntry> for ConfigEntryOutput {
+    fn from(value: &ConfigEntry) -> Self {
+        Self {
+            key: value.key.clone(),
+            value: value.value.clone(),
+            source: value.source,
+        }
+    }
+}
+
+impl From<&[ConfigEntry]> for ConfigOutput {
+    fn from(value: &[ConfigEntry]) -> Self {
+        Self {
+            entries: value.iter().map(ConfigEntryOutput::from).collect(),
+        }
+    }
+}
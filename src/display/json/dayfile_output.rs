@@ -1,9 +1,14 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::Serialize;
 
-use crate::models::{
-    dayfile::DayFile,
-    item::{Item, ItemPriority, ItemStatus},
+use crate::{
+    models::{
+        dayfile::DayFile,
+        item::{
+            DUE_SOON_DAYS, DueState, Item, ItemPriority, ItemStatus, ListMove, Subtask, WorkSession,
+        },
+    },
+    utils::dates::todays_date,
 };
 
 #[derive(Serialize, Debug)]
@@ -38,10 +43,52 @@ pub struct ItemOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     due: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    due_state: Option<DueState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     notes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     migrated_from_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    migration_history: Vec<NaiveDate>,
+    migrations: usize,
+    age_days: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recurrence_id: Option<String>,
     status: ItemStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    subtasks: Vec<SubtaskOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    list_moves: Vec<ListMove>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate_minutes: Option<u32>,
+    logged_minutes: i64,
+    pomodoros: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sessions: Vec<WorkSession>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SubtaskOutput {
+    index: usize,
+    id: String,
+    text: String,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    done_at: Option<DateTime<Utc>>,
+    status: ItemStatus,
+}
+
+impl SubtaskOutput {
+    pub fn new(index: usize, subtask: &Subtask) -> Self {
+        Self {
+            index,
+            id: subtask.id.clone(),
+            text: subtask.text.clone(),
+            created_at: subtask.created_at,
+            done_at: subtask.done_at,
+            status: subtask.status(),
+        }
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -68,9 +115,25 @@ impl From<&Item> for ItemOutput {
             priority: value.priority,
             tags: value.tags.clone(),
             due: value.due,
+            due_state: value.due_state(Utc::now(), DUE_SOON_DAYS),
             notes: value.notes.clone(),
             migrated_from_date: value.migrated_from,
+            migration_history: value.migration_history.clone(),
+            migrations: value.migrations(),
+            age_days: value.age_days(todays_date()),
+            recurrence_id: value.recurrence_id.clone(),
             status: value.status(),
+            subtasks: value
+                .subtasks
+                .iter()
+                .enumerate()
+                .map(|(idx, s)| SubtaskOutput::new(idx + 1, s))
+                .collect(),
+            list_moves: value.list_moves.clone(),
+            estimate_minutes: value.estimate_minutes,
+            logged_minutes: value.logged().num_minutes(),
+            pomodoros: value.pomodoros(),
+            sessions: value.sessions.clone(),
         }
     }
 }
@@ -0,0 +1,56 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{
+    display::json::dayfile_output::ItemOutput, models::item::DueState, view::due::DueReport,
+};
+
+#[derive(Serialize, Debug)]
+pub struct DueOutput {
+    now: DateTime<Utc>,
+    soon_days: i64,
+    stats: DueStatsOutput,
+    items: Vec<DueItemOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DueStatsOutput {
+    overdue: usize,
+    today: usize,
+    soon: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DueItemOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<NaiveDate>,
+    focus: bool,
+    index: usize,
+    state: DueState,
+    item: ItemOutput,
+}
+
+impl From<&DueReport> for DueOutput {
+    fn from(value: &DueReport) -> Self {
+        Self {
+            now: value.now,
+            soon_days: value.soon_days,
+            stats: DueStatsOutput {
+                overdue: value.count(DueState::Overdue),
+                today: value.count(DueState::Today),
+                soon: value.count(DueState::Soon),
+            },
+            items: value
+                .entries
+                .iter()
+                .map(|e| DueItemOutput {
+                    date: e.date,
+                    focus: e.date.is_none(),
+                    index: e.index,
+                    state: e.state,
+                    item: ItemOutput::from(&e.item),
+                })
+                .collect(),
+        }
+    }
+}
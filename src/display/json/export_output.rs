@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::{export::ExportFormat, view::export::ExportReport};
+
+#[derive(Serialize, Debug)]
+pub struct ExportOutput {
+    format: ExportFormat,
+    path: String,
+    files: usize,
+    days: usize,
+    items: usize,
+}
+
+impl From<&ExportReport> for ExportOutput {
+    fn from(value: &ExportReport) -> Self {
+        Self {
+            format: value.format,
+            path: value.path.display().to_string(),
+            files: value.files,
+            days: value.days,
+            items: value.items,
+        }
+    }
+}
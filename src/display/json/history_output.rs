@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    models::journal::{JournalFile, OperationKind},
+    view::history::{History, HistoryEntry},
+};
+
+#[derive(Serialize, Debug)]
+pub struct HistoryEntryOutput {
+    id: String,
+    command: String,
+    at: DateTime<Utc>,
+    kind: OperationKind,
+    files: Vec<JournalFile>,
+    undone: bool,
+}
+
+impl From<&HistoryEntry> for HistoryEntryOutput {
+    fn from(entry: &HistoryEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            command: entry.command.clone(),
+            at: entry.at,
+            kind: entry.kind.clone(),
+            files: entry.files.clone(),
+            undone: entry.undone,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct HistoryOutput {
+    total: usize,
+    operations: Vec<HistoryEntryOutput>,
+}
+
+impl From<&History> for HistoryOutput {
+    fn from(history: &History) -> Self {
+        Self {
+            total: history.total,
+            operations: history
+                .entries
+                .iter()
+                .map(HistoryEntryOutput::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct HistoryActionOutput {
+    pub operation: HistoryEntryOutput,
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+
+use crate::{
+    import::{ImportFormat, Target},
+    view::import::{ImportOutcome, ImportReport},
+};
+
+#[derive(Serialize, Debug)]
+pub struct ImportOutput {
+    format: ImportFormat,
+    path: String,
+    dry_run: bool,
+    imported: usize,
+    skipped: usize,
+    rejected: usize,
+    entries: Vec<ImportEntryOutput>,
+}
+
+#[derive(Serialize, Debug)]
+struct ImportEntryOutput {
+    entry: usize,
+    text: String,
+    /// The date the item goes under, or `focus`.
+    target: Option<String>,
+    imported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl From<&ImportReport> for ImportOutput {
+    fn from(value: &ImportReport) -> Self {
+        let entries = value
+            .entries
+            .iter()
+            .map(|e| ImportEntryOutput {
+                entry: e.entry,
+                text: e.text.clone(),
+                target: e.target.map(|t| match t {
+                    Target::Day(date) => date.to_string(),
+                    Target::Focus => "focus".to_string(),
+                }),
+                imported: matches!(e.outcome, ImportOutcome::Imported),
+                reason: match &e.outcome {
+                    ImportOutcome::Imported => None,
+                    ImportOutcome::Skipped(reason) | ImportOutcome::Rejected(reason) => {
+                        Some(reason.clone())
+                    }
+                },
+            })
+            .collect();
+
+        Self {
+            format: value.format,
+            path: value.path.display().to_string(),
+            dry_run: value.dry_run,
+            imported: value.imported(),
+            skipped: value.skipped().count(),
+            rejected: value.rejected().count(),
+            entries,
+        }
+    }
+}
@@ -1,49 +1,64 @@
-use chrono::NaiveDate;
 use serde::Serialize;
 
 use crate::{
     display::json::dayfile_output::{DayOutput, ItemOutput},
-    models::{dayfile::DayFile, item::Item},
+    view::migration::Migration,
 };
 
 #[derive(Serialize, Debug)]
 pub struct MigrateStatsOutput {
     pub considered: usize,
     pub migrated: usize,
+    pub focused: usize,
+    pub dropped: usize,
+    pub kept: usize,
     pub skipped_done: usize,
 }
 
 #[derive(Serialize, Debug)]
 pub struct MigrateOutput {
     dry_run: bool,
+    copy: bool,
     from: DayOutput,
     to: DayOutput,
     stats: MigrateStatsOutput,
     items: Vec<ItemOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    focused: Vec<ItemOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dropped: Vec<ItemOutput>,
+    /// Picked items left behind because they were already done.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    already_done: Vec<ItemOutput>,
 }
 
-impl MigrateOutput {
-    pub fn new(dry_run: bool, from_df: &DayFile, to_date: NaiveDate, items: &[Item]) -> Self {
-
-        let considered = from_df.items.len();
-        let migrated = items.len();
+impl From<&Migration> for MigrateOutput {
+    fn from(value: &Migration) -> Self {
+        let from_df = &value.from;
 
         Self {
-            dry_run,
+            dry_run: value.dry_run,
+            copy: value.copy,
             from: DayOutput {
                 date: Some(from_df.date),
                 path: None,
             },
             to: DayOutput {
-                date: Some(to_date),
+                date: Some(value.to),
                 path: None,
             },
             stats: MigrateStatsOutput {
-                considered,
-                migrated,
+                considered: from_df.items.len(),
+                migrated: value.moved.len(),
+                focused: value.focused.len(),
+                dropped: value.dropped.len(),
+                kept: value.kept,
                 skipped_done: from_df.items.iter().filter(|i| i.done_at.is_some()).count(),
             },
-            items: items.iter().map(Into::into).collect(),
+            items: value.moved.iter().map(Into::into).collect(),
+            focused: value.focused.iter().map(Into::into).collect(),
+            dropped: value.dropped.iter().map(Into::into).collect(),
+            already_done: value.already_done.iter().map(|(_, i)| i.into()).collect(),
         }
     }
 }
@@ -0,0 +1,77 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    display::json::{
+        agenda_output::FocusFileOutput,
+        dayfile_output::{DayStatsOutput, ItemOutput},
+    },
+    utils::helpers::SummaryStats,
+    view::plan::{Plan, PlanDay},
+};
+
+#[derive(Serialize, Debug)]
+pub struct PlanOutput {
+    span: &'static str,
+    from: NaiveDate,
+    to: NaiveDate,
+    stats: DayStatsOutput,
+    focus: FocusFileOutput,
+    days: Vec<PlanDayOutput>,
+}
+
+#[derive(Serialize, Debug)]
+struct PlanDayOutput {
+    date: NaiveDate,
+    /// The prefix for this day's items in references, e.g. `mon`.
+    day: String,
+    stats: DayStatsOutput,
+    items: Vec<PlanItemOutput>,
+}
+
+#[derive(Serialize, Debug)]
+struct PlanItemOutput {
+    reference: String,
+    item: ItemOutput,
+}
+
+fn stats_output(stats: SummaryStats) -> DayStatsOutput {
+    DayStatsOutput {
+        total: stats.total,
+        open: stats.open,
+        done: stats.completed,
+    }
+}
+
+impl From<&PlanDay> for PlanDayOutput {
+    fn from(value: &PlanDay) -> Self {
+        Self {
+            date: value.date(),
+            day: value.day.to_string(),
+            stats: stats_output(value.stats()),
+            items: value
+                .dayfile
+                .items
+                .iter()
+                .enumerate()
+                .map(|(idx, i)| PlanItemOutput {
+                    reference: value.reference(idx + 1),
+                    item: ItemOutput::from(i),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Plan> for PlanOutput {
+    fn from(value: &Plan) -> Self {
+        Self {
+            span: value.span.as_command(),
+            from: value.start(),
+            to: value.end(),
+            stats: stats_output(value.stats()),
+            focus: FocusFileOutput::from(&value.focus),
+            days: value.days.iter().map(PlanDayOutput::from).collect(),
+        }
+    }
+}
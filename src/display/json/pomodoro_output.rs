@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use crate::{
+    display::json::{dayfile_output::ItemOutput, show_output::Reference},
+    view::pomodoro::PomodoroRun,
+};
+
+#[derive(Serialize, Debug)]
+pub struct PomodoroOutput {
+    reference: Reference,
+    work_minutes: i64,
+    break_minutes: i64,
+    rounds: u32,
+    completed: u32,
+    marked_done: bool,
+    item: ItemOutput,
+}
+
+impl From<&PomodoroRun> for PomodoroOutput {
+    fn from(value: &PomodoroRun) -> Self {
+        Self {
+            reference: Reference::from(&value.reference),
+            work_minutes: value.plan.work.num_minutes(),
+            break_minutes: value.plan.rest.num_minutes(),
+            rounds: value.plan.rounds,
+            completed: value.completed,
+            marked_done: value.marked_done,
+            item: (&value.item).into(),
+        }
+    }
+}
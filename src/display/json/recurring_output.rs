@@ -0,0 +1,74 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{
+    display::json::{action_output::ActionResult, show_output::Reference},
+    models::{item::ItemPriority, recurrence::RecurrenceRule},
+};
+
+#[derive(Serialize, Debug)]
+pub struct RuleOutput {
+    id: String,
+    text: String,
+    priority: ItemPriority,
+    tags: Vec<String>,
+    schedule: String,
+    start: NaiveDate,
+    paused: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<NaiveDate>,
+    created_at: DateTime<Utc>,
+}
+
+impl RuleOutput {
+    pub fn new(rule: &RecurrenceRule, today: NaiveDate) -> Self {
+        Self {
+            id: rule.id.clone(),
+            text: rule.text.clone(),
+            priority: rule.priority,
+            tags: rule.tags.clone(),
+            schedule: rule.schedule.to_string(),
+            start: rule.start,
+            paused: rule.paused,
+            next: if rule.paused {
+                None
+            } else {
+                rule.next_on_or_after(today)
+            },
+            created_at: rule.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct IndexRuleOutput {
+    index: usize,
+    rule: RuleOutput,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RulesOutput {
+    rules: Vec<IndexRuleOutput>,
+}
+
+impl RulesOutput {
+    pub fn new(rules: &[RecurrenceRule], today: NaiveDate) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .enumerate()
+                .map(|(i, rule)| IndexRuleOutput {
+                    index: i + 1,
+                    rule: RuleOutput::new(rule, today),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct RuleActionOutput {
+    pub reference: Reference,
+    pub result: ActionResult,
+    pub rule: RuleOutput,
+}
@@ -3,8 +3,9 @@ use serde::Serialize;
 
 use crate::{
     display::json::dayfile_output::{DayOutput, DayStatsOutput, IndexItemOutput},
-    models::dayfile::DayFile,
+    models::{dayfile::DayFile, item::ItemPriority},
     utils::helpers::ItemCountResult,
+    view::time_report::TimeReport,
 };
 
 #[derive(Serialize, Debug)]
@@ -12,6 +13,7 @@ pub struct ReviewOutput {
     pub range: RangeOutput,
     pub stats: RangeStats,
     pub days: Vec<ReviewDayOutput>,
+    pub time: TimeOutput,
 }
 
 impl ReviewOutput {
@@ -22,13 +24,14 @@ impl ReviewOutput {
         exclude_today: bool,
         dayfiles: &[DayFile],
         count_meta: ItemCountResult,
+        time: &TimeReport,
     ) -> Self {
         Self {
             range: RangeOutput {
                 days,
                 from,
                 to,
-                exclude_today: exclude_today,
+                exclude_today,
             },
             stats: RangeStats {
                 total: count_meta.total,
@@ -37,6 +40,7 @@ impl ReviewOutput {
                 active_days: dayfiles.len(),
             },
             days: Self::make_days(dayfiles),
+            time: time.into(),
         }
     }
 
@@ -88,3 +92,61 @@ pub struct ReviewDayOutput {
     pub stats: DayStatsOutput,
     pub items: Vec<IndexItemOutput>,
 }
+
+#[derive(Serialize, Debug)]
+pub struct TimeOutput {
+    pub total_minutes: i64,
+    pub by_day: Vec<DayTimeOutput>,
+    pub by_tag: Vec<TagTimeOutput>,
+    pub by_priority: Vec<PriorityTimeOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DayTimeOutput {
+    pub date: NaiveDate,
+    pub minutes: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TagTimeOutput {
+    pub tag: String,
+    pub minutes: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PriorityTimeOutput {
+    pub priority: ItemPriority,
+    pub minutes: i64,
+}
+
+impl From<&TimeReport> for TimeOutput {
+    fn from(value: &TimeReport) -> Self {
+        Self {
+            total_minutes: value.total.num_minutes(),
+            by_day: value
+                .by_day
+                .iter()
+                .map(|(date, spent)| DayTimeOutput {
+                    date: *date,
+                    minutes: spent.num_minutes(),
+                })
+                .collect(),
+            by_tag: value
+                .tags()
+                .into_iter()
+                .map(|(tag, spent)| TagTimeOutput {
+                    tag: tag.to_string(),
+                    minutes: spent.num_minutes(),
+                })
+                .collect(),
+            by_priority: value
+                .by_priority
+                .iter()
+                .map(|(priority, spent)| PriorityTimeOutput {
+                    priority: *priority,
+                    minutes: spent.num_minutes(),
+                })
+                .collect(),
+        }
+    }
+}
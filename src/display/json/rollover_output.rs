@@ -0,0 +1,21 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{display::json::dayfile_output::ItemOutput, view::rollover::Rollover};
+
+#[derive(Serialize, Debug)]
+pub struct RolloverOutput {
+    from: NaiveDate,
+    to: NaiveDate,
+    items: Vec<ItemOutput>,
+}
+
+impl From<&Rollover> for RolloverOutput {
+    fn from(value: &Rollover) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            items: value.items.iter().map(ItemOutput::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{display::json::dayfile_output::ItemOutput, view::search::SearchResults};
+
+#[derive(Serialize, Debug)]
+pub struct SearchOutput {
+    query: String,
+    stats: SearchStatsOutput,
+    hits: Vec<SearchHitOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SearchStatsOutput {
+    matches: usize,
+    days_searched: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SearchHitOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<NaiveDate>,
+    focus: bool,
+    index: usize,
+    item: ItemOutput,
+}
+
+impl From<&SearchResults> for SearchOutput {
+    fn from(value: &SearchResults) -> Self {
+        Self {
+            query: value.query.clone(),
+            stats: SearchStatsOutput {
+                matches: value.hits.len(),
+                days_searched: value.days_searched,
+            },
+            hits: value
+                .hits
+                .iter()
+                .map(|h| SearchHitOutput {
+                    date: h.date,
+                    focus: h.date.is_none(),
+                    index: h.index,
+                    item: ItemOutput::from(&h.item),
+                })
+                .collect(),
+        }
+    }
+}
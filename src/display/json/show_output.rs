@@ -4,12 +4,42 @@ use serde::Serialize;
 use crate::{
     display::json::dayfile_output::{DayOutput, ItemOutput},
     models::item::Item,
+    utils::task_ref::{TaskPath, TaskRef},
 };
 
 #[derive(Debug, Serialize)]
 pub struct Reference {
     pub kind: ReferenceKind,
-    pub value: usize,
+    pub value: ReferenceValue,
+    /// The 1-based position of the subtask, for dotted references like `3.2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtask: Option<usize>,
+}
+
+impl From<&TaskRef> for Reference {
+    fn from(value: &TaskRef) -> Self {
+        match value {
+            TaskRef::Index(i) => Self {
+                kind: ReferenceKind::Index,
+                value: ReferenceValue::Index(*i),
+                subtask: None,
+            },
+            TaskRef::Id(id) => Self {
+                kind: ReferenceKind::Id,
+                value: ReferenceValue::Id(id.clone()),
+                subtask: None,
+            },
+        }
+    }
+}
+
+impl From<&TaskPath> for Reference {
+    fn from(value: &TaskPath) -> Self {
+        Self {
+            subtask: value.subtask,
+            ..Self::from(&value.task)
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -20,13 +50,10 @@ pub struct ShowOutput {
 }
 
 impl ShowOutput {
-    pub fn new(index: usize, date: Option<NaiveDate>, item: &Item) -> Self {
+    pub fn new(reference: &TaskRef, date: Option<NaiveDate>, item: &Item) -> Self {
         Self {
             day: DayOutput { date, path: None },
-            reference: Reference {
-                kind: ReferenceKind::Index,
-                value: index,
-            },
+            reference: Reference::from(reference),
             item: ItemOutput::from(item),
         }
     }
@@ -35,6 +62,13 @@ impl ShowOutput {
 #[derive(Debug, Clone, Copy, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ReferenceKind {
-    _Id,
+    Id,
     Index,
 }
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ReferenceValue {
+    Id(String),
+    Index(usize),
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    display::json::dayfile_output::ItemOutput,
+    models::item::ItemPriority,
+    view::stale::{StaleAction, StaleReport},
+};
+
+#[derive(Serialize, Debug)]
+pub struct StaleOutput {
+    min_migrations: usize,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<ItemPriority>,
+    count: usize,
+    items: Vec<StaleItemOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StaleItemOutput {
+    date: NaiveDate,
+    index: usize,
+    item: ItemOutput,
+}
+
+impl From<&StaleReport> for StaleOutput {
+    fn from(value: &StaleReport) -> Self {
+        let (action, priority) = match value.action {
+            StaleAction::List => ("list", None),
+            StaleAction::Drop => ("drop", None),
+            StaleAction::Focus => ("focus", None),
+            StaleAction::Reprioritise(p) => ("priority", Some(p)),
+        };
+
+        Self {
+            min_migrations: value.min_migrations,
+            action,
+            priority,
+            count: value.entries.len(),
+            items: value
+                .entries
+                .iter()
+                .map(|e| StaleItemOutput {
+                    date: e.date,
+                    index: e.index,
+                    item: ItemOutput::from(&e.item),
+                })
+                .collect(),
+        }
+    }
+}
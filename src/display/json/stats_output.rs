@@ -0,0 +1,150 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    models::item::ItemPriority,
+    view::stats::{Counts, StatsReport},
+};
+
+#[derive(Serialize, Debug)]
+pub struct StatsOutput {
+    range: StatsRangeOutput,
+    total: usize,
+    done: usize,
+    open: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    completion_rate: Option<f64>,
+    active_days: usize,
+    items_per_day: f64,
+    current_streak: usize,
+    longest_streak: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mean_minutes_to_complete: Option<i64>,
+    migrated: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    migration_rate: Option<f64>,
+    periods: Vec<PeriodOutput>,
+    by_priority: Vec<PriorityCountsOutput>,
+    by_tag: Vec<TagCountsOutput>,
+    weekdays: Vec<WeekdayCountsOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StatsRangeOutput {
+    label: String,
+    from: NaiveDate,
+    to: NaiveDate,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CountsOutput {
+    total: usize,
+    done: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PeriodOutput {
+    from: NaiveDate,
+    to: NaiveDate,
+    #[serde(flatten)]
+    counts: CountsOutput,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PriorityCountsOutput {
+    priority: ItemPriority,
+    #[serde(flatten)]
+    counts: CountsOutput,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TagCountsOutput {
+    tag: String,
+    #[serde(flatten)]
+    counts: CountsOutput,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WeekdayCountsOutput {
+    weekday: &'static str,
+    #[serde(flatten)]
+    counts: CountsOutput,
+}
+
+impl From<&Counts> for CountsOutput {
+    fn from(value: &Counts) -> Self {
+        Self {
+            total: value.total,
+            done: value.done,
+            rate: value.rate(),
+        }
+    }
+}
+
+impl From<&StatsReport> for StatsOutput {
+    fn from(value: &StatsReport) -> Self {
+        const WEEKDAYS: [&str; 7] = [
+            "monday",
+            "tuesday",
+            "wednesday",
+            "thursday",
+            "friday",
+            "saturday",
+            "sunday",
+        ];
+
+        Self {
+            range: StatsRangeOutput {
+                label: value.range.to_string(),
+                from: value.start,
+                to: value.end,
+            },
+            total: value.counts.total,
+            done: value.counts.done,
+            open: value.counts.total - value.counts.done,
+            completion_rate: value.counts.rate(),
+            active_days: value.active_days,
+            items_per_day: value.items_per_day(),
+            current_streak: value.current_streak,
+            longest_streak: value.longest_streak,
+            mean_minutes_to_complete: value.time_to_complete.map(|d| d.num_minutes()),
+            migrated: value.migrated,
+            migration_rate: value.migration_rate(),
+            periods: value
+                .periods
+                .iter()
+                .map(|p| PeriodOutput {
+                    from: p.start,
+                    to: p.end,
+                    counts: (&p.counts).into(),
+                })
+                .collect(),
+            by_priority: value
+                .by_priority
+                .iter()
+                .map(|(priority, c)| PriorityCountsOutput {
+                    priority: *priority,
+                    counts: c.into(),
+                })
+                .collect(),
+            by_tag: value
+                .tags()
+                .into_iter()
+                .map(|(tag, c)| TagCountsOutput {
+                    tag: tag.to_string(),
+                    counts: (&c).into(),
+                })
+                .collect(),
+            weekdays: WEEKDAYS
+                .iter()
+                .zip(&value.weekdays)
+                .map(|(weekday, c)| WeekdayCountsOutput {
+                    weekday,
+                    counts: c.into(),
+                })
+                .collect(),
+        }
+    }
+}
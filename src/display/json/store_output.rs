@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::{models::vault_config::StoreBackend, view::store_migration::StoreMigration};
+
+#[derive(Serialize, Debug)]
+pub struct StoreMigrationOutput {
+    from: StoreBackend,
+    to: StoreBackend,
+    days: usize,
+    items: usize,
+    focus_items: usize,
+}
+
+impl From<&StoreMigration> for StoreMigrationOutput {
+    fn from(value: &StoreMigration) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            days: value.days,
+            items: value.items,
+            focus_items: value.focus_items,
+        }
+    }
+}
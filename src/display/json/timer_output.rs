@@ -0,0 +1,50 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{
+    display::json::dayfile_output::ItemOutput,
+    utils::task_target::TaskTarget,
+    view::timer::{TimerChange, TimerEntry},
+};
+
+#[derive(Serialize, Debug)]
+pub struct TimerOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stopped: Option<TimerEntryOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started: Option<TimerEntryOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TimerEntryOutput {
+    list: TaskTarget,
+    date: NaiveDate,
+    started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stopped_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minutes: Option<i64>,
+    item: ItemOutput,
+}
+
+impl From<&TimerEntry> for TimerEntryOutput {
+    fn from(value: &TimerEntry) -> Self {
+        Self {
+            list: value.target,
+            date: value.date,
+            started_at: value.started_at,
+            stopped_at: value.stopped_at,
+            minutes: value.elapsed().map(|d| d.num_minutes()),
+            item: (&value.item).into(),
+        }
+    }
+}
+
+impl From<&TimerChange> for TimerOutput {
+    fn from(value: &TimerChange) -> Self {
+        Self {
+            stopped: value.stopped.as_ref().map(Into::into),
+            started: value.started.as_ref().map(Into::into),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::view::vault::VaultSummary;
+
+#[derive(Serialize, Debug)]
+pub struct VaultsOutput {
+    pub vaults: Vec<VaultOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct VaultOutput {
+    name: String,
+    current: bool,
+    days: usize,
+    open: usize,
+    done: usize,
+    focus: usize,
+    last_activity: Option<DateTime<Utc>>,
+}
+
+impl From<&VaultSummary> for VaultOutput {
+    fn from(value: &VaultSummary) -> Self {
+        Self {
+            name: value.name.clone(),
+            current: value.current,
+            days: value.days,
+            open: value.open,
+            done: value.done,
+            focus: value.focus,
+            last_activity: value.last_activity,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct VaultActionOutput<'a> {
+    pub result: &'static str,
+    pub vault: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<&'a str>,
+}
@@ -1,28 +1,56 @@
 use std::io::{self, Write};
 
-use chrono::{Days, NaiveDate};
+use chrono::{Datelike, Days, Local, NaiveDate, Utc};
 
 use crate::{
-    display::{
-        renderer::Renderer,
-        terminal::{DATE_FORMAT, DATE_WITH_TIME_FORMAT},
+    display::{renderer::Renderer, terminal::DateFormats},
+    import::Target,
+    models::{
+        dayfile::DayFile,
+        item::{DUE_SOON_DAYS, DueState, Item, ListMove},
+        journal::OperationKind,
+        recurrence::RecurrenceRule,
     },
-    models::{dayfile::DayFile, item::Item},
     utils::{
+        dates::{format_minutes, todays_date},
         helpers::{SummaryStats, item_count_meta},
-        render::ActionKind,
+        render::{ActionKind, ConfigAction, HistoryAction, RuleAction, VaultAction},
+        task_ref::{TaskPath, TaskRef},
+        task_target::TaskTarget,
         tusk_error::TuskError,
     },
-    view::agenda::Agenda,
+    view::{
+        agenda::Agenda,
+        batch::Batch,
+        calendar::{Calendar, CalendarDay, CalendarSpan},
+        config::{ConfigEntry, ConfigSource},
+        due::DueReport,
+        export::ExportReport,
+        history::{History, HistoryEntry},
+        import::{ImportOutcome, ImportReport},
+        migration::Migration,
+        plan::{Plan, PlanSpan},
+        pomodoro::PomodoroRun,
+        rollover::Rollover,
+        search::SearchResults,
+        stale::{StaleAction, StaleReport},
+        stats::{Counts, StatsReport, format_rate},
+        store_migration::StoreMigration,
+        time_report::TimeReport,
+        timer::{TimerChange, TimerEntry},
+        vault::VaultSummary,
+    },
 };
 
-pub struct MarkdownRenderer;
+pub struct MarkdownRenderer {
+    pub formats: DateFormats,
+}
 
 impl Renderer for MarkdownRenderer {
     fn render_agenda(&self, agenda: &Agenda) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
-        Self::render_header(&mut out, agenda.date)?;
+        self.render_header(&mut out, agenda.date)?;
 
         if let Some(ff) = &agenda.focusfile {
             writeln!(out, "### Focus Tasks")?;
@@ -50,14 +78,10 @@ impl Renderer for MarkdownRenderer {
     fn render_day(&self, df: &DayFile) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
-        Self::render_header(&mut out, df.date)?;
+        self.render_header(&mut out, df.date)?;
 
         if df.items.is_empty() {
-            writeln!(
-                out,
-                "🦣 No tasks for {}",
-                format!("No tasks for {}", df.date)
-            )?;
+            writeln!(out, "🦣 No tasks for {}", df.date)?;
 
             let hint = r#"tusk add "Drink more water 💦""#;
             writeln!(out, "_Add one with: {}_", hint)?;
@@ -73,28 +97,109 @@ impl Renderer for MarkdownRenderer {
     fn render_summary(
         &self,
         _date: Option<NaiveDate>,
-        index: usize,
+        reference: &TaskRef,
         item: &Item,
     ) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
-        let create_at = item.created_at.format(DATE_WITH_TIME_FORMAT);
+        let create_at = item.created_at.format(&self.formats.date_time);
 
-        writeln!(out, "# #{} - {}", index, item.text)?;
+        writeln!(out, "# {} - {}", reference, item.text)?;
         writeln!(out)?;
 
         writeln!(out, "**Status:** {}  ", item.status())?;
         writeln!(out, "**Priority:** {}  ", item.priority)?;
         writeln!(out, "**Created:** {}  ", create_at)?;
 
+        if let Some(due) = item.due {
+            match item.due_state(Utc::now(), DUE_SOON_DAYS) {
+                Some(s @ (DueState::Overdue | DueState::Today | DueState::Soon)) => {
+                    writeln!(out, "**Due:** {} ({})  ", self.formats.format_due(&due), s)?
+                }
+                _ => writeln!(out, "**Due:** {}  ", self.formats.format_due(&due))?,
+            }
+        }
+
         if let Some(done_at) = item.done_at {
             writeln!(
                 out,
                 "**Completed:** {}  ",
-                done_at.format(DATE_WITH_TIME_FORMAT)
+                done_at.format(&self.formats.date_time)
             )?;
         }
 
+        if item.migrations() > 0 {
+            let history = item
+                .migration_history
+                .iter()
+                .map(|d| d.format(&self.formats.date).to_string())
+                .collect::<Vec<_>>();
+
+            let history = if history.is_empty() {
+                item.migrated_from
+                    .map(|d| d.format(&self.formats.date).to_string())
+                    .unwrap_or_default()
+            } else {
+                history.join(" → ")
+            };
+
+            writeln!(
+                out,
+                "**Migrated:** {} time(s), from {}  ",
+                item.migrations(),
+                history
+            )?;
+        }
+
+        for m in &item.list_moves {
+            match m {
+                ListMove::Promoted { date, .. } => writeln!(
+                    out,
+                    "**Promoted:** to focus from {}  ",
+                    date.format(&self.formats.date)
+                )?,
+                ListMove::Demoted { date, .. } => writeln!(
+                    out,
+                    "**Demoted:** from focus to {}  ",
+                    date.format(&self.formats.date)
+                )?,
+            }
+        }
+
+        writeln!(out, "**Age:** {} day(s)  ", item.age_days(todays_date()))?;
+
+        if item.estimate_minutes.is_some() || !item.sessions.is_empty() {
+            let logged = format_minutes(item.logged().num_minutes());
+
+            match item.estimate_minutes {
+                Some(estimate) => writeln!(
+                    out,
+                    "**Time:** {logged} logged of {} estimated  ",
+                    format_minutes(estimate.into())
+                )?,
+                None => writeln!(out, "**Time:** {logged} logged  ")?,
+            }
+        }
+
+        if item.pomodoros() > 0 {
+            writeln!(out, "**Pomodoros:** {}  ", item.pomodoros())?;
+        }
+
+        if let Some((done, total)) = item.progress() {
+            writeln!(out)?;
+            writeln!(out, "## Subtasks ({done}/{total})")?;
+            writeln!(out)?;
+
+            for s in &item.subtasks {
+                let checkbox = if s.done_at.is_some() {
+                    "- [x]"
+                } else {
+                    "- [ ]"
+                };
+                writeln!(out, "{checkbox} {}", s.text)?;
+            }
+        }
+
         if let Some(n) = &item.notes {
             writeln!(out)?;
             writeln!(out, "---")?;
@@ -107,28 +212,49 @@ impl Renderer for MarkdownRenderer {
         Ok(())
     }
 
-    fn render_migrate(
-        &self,
-        to_date: NaiveDate,
-        from_df_original: &DayFile,
-        moved_items: &[Item],
-        dry_run: bool,
-    ) -> std::io::Result<()> {
+    fn render_migrate(&self, migration: &Migration) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
         writeln!(out, "# Migration")?;
-        let title = self.build_date_line(to_date, Some(from_df_original.date));
+        let title = self.build_date_line(migration.to, Some(migration.from.date));
         writeln!(out, "{}", title)?;
         writeln!(out)?;
 
-        if moved_items.is_empty() {
+        if !migration.already_done.is_empty() {
+            for (pos, item) in &migration.already_done {
+                writeln!(
+                    out,
+                    "> {}. {} — already done, not migrated",
+                    pos + 1,
+                    item.text
+                )?;
+            }
+            writeln!(out)?;
+        }
+
+        if migration.is_empty() {
             writeln!(out, "> 🦣 No tasks to migrate.")?;
 
             return Ok(());
         }
 
-        self.render_list(&mut out, moved_items)?;
-        self.render_migration_count(&mut out, moved_items, from_df_original.date, dry_run)?;
+        self.render_list(&mut out, &migration.moved)?;
+
+        if !migration.focused.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Sent to focus")?;
+            writeln!(out)?;
+            self.render_list(&mut out, &migration.focused)?;
+        }
+
+        if !migration.dropped.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Dropped")?;
+            writeln!(out)?;
+            self.render_list(&mut out, &migration.dropped)?;
+        }
+
+        self.render_migration_count(&mut out, migration)?;
 
         Ok(())
     }
@@ -139,6 +265,7 @@ impl Renderer for MarkdownRenderer {
         end: NaiveDate,
         days: u64,
         dayfiles: &[DayFile],
+        time: &TimeReport,
     ) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
@@ -146,28 +273,31 @@ impl Renderer for MarkdownRenderer {
             .checked_sub_days(Days::new(1))
             .expect("end should always be at least one day after start");
 
-        let start_s = start.format(DATE_FORMAT).to_string();
-        let end_s = display_end.format(DATE_FORMAT).to_string();
+        let start_s = start.format(&self.formats.date).to_string();
+        let end_s = display_end.format(&self.formats.date).to_string();
 
         writeln!(out, "# Review: {} → {}", start_s, end_s)?;
 
         writeln!(
             &mut out,
-            "> Last {} {} {}\n",
+            "> Last {} {} (excluding today)\n",
             days,
             if days == 1 { "day" } else { "days" },
-            "(excluding today)",
         )?;
 
         let count = item_count_meta(dayfiles);
 
         writeln!(&mut out, "## Summary")?;
-        writeln!(&mut out, "{} {}", "- **Total:**", count.total)?;
-        writeln!(&mut out, "{} {}", "- **Open:**", count.open)?;
-        writeln!(&mut out, "{} {}", "- **Completed:**", count.complete)?;
-        writeln!(&mut out, "{} {}", "- **Active days:**", dayfiles.len())?;
+        writeln!(&mut out, "- **Total:** {}", count.total)?;
+        writeln!(&mut out, "- **Open:** {}", count.open)?;
+        writeln!(&mut out, "- **Completed:** {}", count.complete)?;
+        writeln!(&mut out, "- **Active days:** {}", dayfiles.len())?;
         writeln!(&mut out)?;
 
+        if !time.is_empty() {
+            self.render_time_report(&mut out, time)?;
+        }
+
         for df in dayfiles {
             let total_item_count: usize = df.items.len();
 
@@ -175,7 +305,7 @@ impl Renderer for MarkdownRenderer {
                 df.items.iter().filter(|i| i.done_at.is_none()).count();
 
             writeln!(out)?;
-            let title = format!("## {}", df.date.format(DATE_FORMAT));
+            let title = format!("## {}", df.date.format(&self.formats.date));
             writeln!(out, "{}", title)?;
 
             writeln!(
@@ -191,15 +321,18 @@ impl Renderer for MarkdownRenderer {
                 let text = if is_done {
                     format!("~~{}~~", item.text)
                 } else {
-                    format!("{}", item.text)
+                    item.text.to_string()
                 };
 
-                writeln!(
+                write!(
                     &mut out,
                     "{} {text} {}",
                     if is_done { "- [x]" } else { "- [ ]" },
                     format_args!("*({})*", item.priority)
                 )?;
+
+                self.render_due_label(&mut out, item)?;
+                writeln!(&mut out)?;
             }
             writeln!(out)?;
         }
@@ -209,62 +342,269 @@ impl Renderer for MarkdownRenderer {
 
     fn render_action(
         &self,
-        _index: usize,
-        _date: NaiveDate,
-        _action: ActionKind,
-        _item: Option<&Item>,
+        reference: &TaskPath,
+        date: NaiveDate,
+        action: ActionKind,
+        item: Option<&Item>,
     ) -> std::io::Result<()> {
+        // Done, undone and rm stay quiet, an edit shows the item as it is now.
+        match (action, item) {
+            (ActionKind::Edited, Some(item)) => {
+                self.render_summary(Some(date), &reference.task, item)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn render_batch(&self, batch: &Batch) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        if batch.entries.is_empty() {
+            writeln!(out, "> 🦣 No matching items.")?;
+
+            return Ok(());
+        }
+
+        let destination = match batch.action {
+            ActionKind::Promoted => " to focus".to_string(),
+            ActionKind::Demoted => format!(" to {}", batch.date.format(&self.formats.date)),
+            _ => String::new(),
+        };
+
+        writeln!(
+            out,
+            "# {} {} item(s){}",
+            batch.action.as_past_tense(),
+            batch.entries.len(),
+            destination
+        )?;
+        writeln!(out)?;
+
+        for entry in &batch.entries {
+            let source = match entry.target {
+                TaskTarget::Day => entry.reference.to_string(),
+                TaskTarget::Focus => format!("focus {}", entry.reference),
+            };
+
+            let (text, is_done) = match &entry.subtask {
+                Some(s) => (&s.text, s.done_at.is_some()),
+                None => (&entry.item.text, entry.item.done_at.is_some()),
+            };
+
+            let checkbox = if is_done { "- [x]" } else { "- [ ]" };
+            writeln!(out, "{checkbox} **{source}** {text}")?;
+        }
+
         Ok(())
     }
 
-    fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()> {
-        let mut err = io::stderr().lock();
+    fn render_due(&self, report: &DueReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
+        writeln!(out, "# Due")?;
+        writeln!(out)?;
         writeln!(
-            err,
-            "{} {} {}",
-            "error:",
-            e,
-            format!("(command: {command})")
+            out,
+            "## {}",
+            report.now.with_timezone(&Local).format(&self.formats.date)
         )?;
+        writeln!(out)?;
 
-        if let Some(hint) = e.hint() {
-            writeln!(err, "hint: {}", hint)?;
+        if report.entries.is_empty() {
+            writeln!(
+                out,
+                "> 🦣 Nothing due in the next {} day(s).",
+                report.soon_days
+            )?;
+
+            return Ok(());
+        }
+
+        let sections = [
+            (DueState::Overdue, "Overdue".to_string()),
+            (DueState::Today, "Due today".to_string()),
+            (
+                DueState::Soon,
+                format!("Due in the next {} day(s)", report.soon_days),
+            ),
+        ];
+
+        for (state, heading) in sections {
+            if report.count(state) == 0 {
+                continue;
+            }
+
+            writeln!(out, "### {heading}")?;
+
+            for entry in report.with_state(state) {
+                let source = match entry.date {
+                    Some(d) => format!("{} #{}", d, entry.index),
+                    None => format!("focus #{}", entry.index),
+                };
+
+                write!(
+                    out,
+                    "- [ ] {} {} · {}",
+                    entry.item.text, entry.item.priority, source
+                )?;
+                self.render_due_label(&mut out, &entry.item)?;
+                writeln!(out)?;
+            }
+
+            writeln!(out)?;
         }
 
+        writeln!(
+            out,
+            "> **{} overdue**, {} due today, {} due soon",
+            report.count(DueState::Overdue),
+            report.count(DueState::Today),
+            report.count(DueState::Soon)
+        )?;
+
         Ok(())
     }
-}
 
-impl MarkdownRenderer {
+    fn render_search(&self, results: &SearchResults) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
-    fn render_header(out: &mut impl Write, date: NaiveDate) -> std::io::Result<()> {
-        writeln!(out, "# Tasks")?;
+        writeln!(out, "# Search: `{}`", results.query)?;
         writeln!(out)?;
-        writeln!(out, "## {}", date.format("%a %d %b %Y"))?;
-        writeln!(out)
+
+        if results.hits.is_empty() {
+            writeln!(out, "> 🦣 No matching tasks.")?;
+
+            return Ok(());
+        }
+
+        let mut current: Option<Option<NaiveDate>> = None;
+
+        for hit in &results.hits {
+            if current != Some(hit.date) {
+                if current.is_some() {
+                    writeln!(out)?;
+                }
+
+                match hit.date {
+                    Some(d) => writeln!(out, "## {} ({})", d.format(&self.formats.date), d)?,
+                    None => writeln!(out, "## Focus Tasks")?,
+                }
+                writeln!(out)?;
+                current = Some(hit.date);
+            }
+
+            let is_done = hit.item.done_at.is_some();
+            let checkbox = if is_done { "- [x]" } else { "- [ ]" };
+
+            write!(
+                out,
+                "{checkbox} **#{}** {} {}",
+                hit.index, hit.item.text, hit.item.priority
+            )?;
+            self.render_due_label(&mut out, &hit.item)?;
+            writeln!(out)?;
+        }
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "> **{} match(es)** across {} day(s)",
+            results.hits.len(),
+            results.days_searched
+        )?;
+
+        Ok(())
     }
 
-    fn build_date_line(&self, to_date: NaiveDate, migration_date: Option<NaiveDate>) -> String {
-        if let Some(migration_date) = migration_date {
-            let from_date_s = migration_date.format("%a %d %b %Y");
-            format!(
-                "From **{}** → **{}**",
-                from_date_s,
-                to_date.format("%a %d %b %Y")
-            )
-        } else {
-            format!("On **{}**", to_date.format("%a %d %b %Y"))
+    fn render_rules(&self, rules: &[RecurrenceRule], today: NaiveDate) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Recurring Tasks")?;
+        writeln!(out)?;
+
+        if rules.is_empty() {
+            writeln!(out, "> 🦣 No recurring tasks.")?;
+
+            return Ok(());
+        }
+
+        for rule in rules {
+            let next = if rule.paused {
+                "**paused**".to_string()
+            } else {
+                match rule.next_on_or_after(today) {
+                    Some(d) => format!("next {}", d.format(&self.formats.due)),
+                    None => "never".to_string(),
+                }
+            };
+
+            writeln!(
+                out,
+                "- {} {} · `{}` · {} · `@{}`",
+                rule.text, rule.priority, rule.schedule, next, rule.id
+            )?;
         }
+
+        Ok(())
     }
 
-    fn render_list(&self, out: &mut impl Write, items: &[Item]) -> std::io::Result<()> {
-        for item in items {
-            let is_done = item.done_at.is_some();
-            let checkbox = if is_done { "- [x]" } else { "- [ ]" };
+    fn render_rule_action(
+        &self,
+        _reference: &TaskRef,
+        action: RuleAction,
+        rule: &RecurrenceRule,
+    ) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
-            let priority = format!("{}", item.priority);
-            write!(out, "{checkbox} {} {}", item.text, priority)?;
+        writeln!(
+            out,
+            "> {} `@{}`: {} (`{}`)",
+            action.as_past_tense(),
+            rule.id,
+            rule.text,
+            rule.schedule
+        )?;
+
+        Ok(())
+    }
+
+    fn render_history(&self, history: &History) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Recent Changes")?;
+        writeln!(out)?;
+
+        if history.entries.is_empty() {
+            writeln!(out, "> 🦣 No changes recorded yet.")?;
+
+            return Ok(());
+        }
+
+        for entry in &history.entries {
+            let at = entry
+                .at
+                .with_timezone(&Local)
+                .format(&self.formats.date_time);
+
+            write!(
+                out,
+                "- `{}` {} · **{}** · {}",
+                entry.id,
+                at,
+                entry.command,
+                entry.files_label()
+            )?;
+
+            match &entry.kind {
+                OperationKind::Do => {}
+                OperationKind::Undo { target } | OperationKind::Redo { target } => {
+                    write!(out, " (of `{target}`)")?;
+                }
+            }
+
+            if entry.undone {
+                write!(out, " · _undone_")?;
+            }
 
             writeln!(out)?;
         }
@@ -272,47 +612,754 @@ impl MarkdownRenderer {
         Ok(())
     }
 
-    fn render_footer(&self, out: &mut impl Write, stats: SummaryStats) -> std::io::Result<()> {
+    fn render_history_action(
+        &self,
+        action: HistoryAction,
+        entry: &HistoryEntry,
+    ) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
         writeln!(
             out,
-            "> **{} task(s)** ({} open, {} done)",
-            stats.total, stats.open, stats.completed
+            "> {} `{}` (`{}`): {}",
+            action.as_past_tense(),
+            entry.command,
+            entry.id,
+            entry.files_label()
         )?;
 
         Ok(())
     }
 
-    fn render_migration_count(
+    fn render_store_migrate(&self, migration: &StoreMigration) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(
+            out,
+            "> Migrated `{}` → `{}`: {} day(s), {} task(s), {} focus task(s)",
+            migration.from, migration.to, migration.days, migration.items, migration.focus_items
+        )?;
+
+        Ok(())
+    }
+
+    fn render_config(&self, action: ConfigAction, entries: &[ConfigEntry]) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match action {
+            ConfigAction::Get => {
+                for entry in entries {
+                    writeln!(out, "{}", entry.value.as_deref().unwrap_or_default())?;
+                }
+            }
+            ConfigAction::Set | ConfigAction::Unset => {
+                for entry in entries {
+                    let scope = match entry.source {
+                        ConfigSource::Vault => "for this vault",
+                        _ => "globally",
+                    };
+
+                    match &entry.value {
+                        Some(value) => {
+                            writeln!(out, "> Set `{}` = `{}` {}", entry.key, value, scope)?
+                        }
+                        None => writeln!(out, "> Unset `{}` {}", entry.key, scope)?,
+                    }
+                }
+            }
+            ConfigAction::Ls => {
+                writeln!(out, "# Config")?;
+                writeln!(out)?;
+                writeln!(out, "| Key | Value | Source |")?;
+                writeln!(out, "| --- | --- | --- |")?;
+
+                for entry in entries {
+                    writeln!(
+                        out,
+                        "| `{}` | {} | {} |",
+                        entry.key,
+                        entry
+                            .value
+                            .as_deref()
+                            .unwrap_or_default()
+                            .replace('|', "\\|"),
+                        entry.source.as_str()
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_vaults(&self, vaults: &[VaultSummary]) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Vaults")?;
+        writeln!(out)?;
+        writeln!(out, "| Vault | Days | Open | Done | Focus | Last active |")?;
+        writeln!(out, "| --- | --- | --- | --- | --- | --- |")?;
+
+        for vault in vaults {
+            let name = if vault.current {
+                format!("**{}** (current)", vault.name)
+            } else {
+                vault.name.clone()
+            };
+            let last = vault
+                .last_activity
+                .map(|at| {
+                    at.with_timezone(&Local)
+                        .format(&self.formats.date_time)
+                        .to_string()
+                })
+                .unwrap_or_default();
+
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} |",
+                name, vault.days, vault.open, vault.done, vault.focus, last
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn render_vault_action(
         &self,
-        out: &mut impl Write,
-        items: &[Item],
-        date: NaiveDate,
-        dry_run: bool,
+        action: VaultAction,
+        vault: &str,
+        from: Option<&str>,
     ) -> std::io::Result<()> {
-        let count = items.len();
+        let mut out = io::stdout().lock();
 
-        if count == 0 {
-            return Ok(());
+        match from {
+            Some(from) => writeln!(out, "> {} `{}` → `{}`", action.as_past_tense(), from, vault)?,
+            None => writeln!(out, "> {} `{}`", action.as_past_tense(), vault)?,
         }
 
-        let item_word = if count == 1 { "item" } else { "items" };
-        let details = if dry_run {
-            "will be migrated from:"
-        } else {
-            "migrated:"
-        };
+        Ok(())
+    }
 
-        writeln!(out)?;
+    fn render_export(&self, report: &ExportReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
         writeln!(
             out,
-            "> {} {} {} {}",
-            count,
-            item_word,
-            details,
-            date.format("%a %d %b %Y")
+            "> Exported {} task(s) from {} day(s) to `{}` ({} {} file(s))",
+            report.items,
+            report.days,
+            report.path.display(),
+            report.files,
+            report.format
         )?;
 
         Ok(())
     }
+
+    fn render_import(&self, report: &ImportReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Import")?;
+        writeln!(out, "From `{}` ({})", report.path.display(), report.format)?;
+        writeln!(out)?;
+
+        if report.entries.is_empty() {
+            writeln!(out, "> 🦣 Nothing to import.")?;
+            return Ok(());
+        }
+
+        writeln!(out, "| # | Task | Into | Result |")?;
+        writeln!(out, "|---|------|------|--------|")?;
+
+        for entry in &report.entries {
+            let target = match entry.target {
+                Some(Target::Day(date)) => date.format(&self.formats.date).to_string(),
+                Some(Target::Focus) => "Focus".to_string(),
+                None => String::new(),
+            };
+
+            let result = match &entry.outcome {
+                ImportOutcome::Imported if report.dry_run => "would import".to_string(),
+                ImportOutcome::Imported => "imported".to_string(),
+                ImportOutcome::Skipped(reason) => format!("skipped: {reason}"),
+                ImportOutcome::Rejected(reason) => format!("rejected: {reason}"),
+            };
+
+            writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                entry.entry,
+                entry.text.replace('|', "\\|"),
+                target,
+                result
+            )?;
+        }
+
+        let details = if report.dry_run {
+            "would be imported"
+        } else {
+            "imported"
+        };
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "> {} task(s) {}, {} skipped, {} rejected",
+            report.imported(),
+            details,
+            report.skipped().count(),
+            report.rejected().count()
+        )?;
+
+        Ok(())
+    }
+
+    fn render_rollover(&self, rollover: &Rollover) -> std::io::Result<()> {
+        let mut err = io::stderr().lock();
+
+        writeln!(
+            err,
+            "> Rolled {} open task(s) over from {}",
+            rollover.items.len(),
+            rollover.from.format(&self.formats.date)
+        )
+    }
+
+    fn render_stale(&self, report: &StaleReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Stale: migrated {}+ times", report.min_migrations)?;
+        writeln!(out)?;
+
+        if report.entries.is_empty() {
+            writeln!(
+                out,
+                "> 🦣 No open task has been migrated {} or more times.",
+                report.min_migrations
+            )?;
+
+            return Ok(());
+        }
+
+        for entry in &report.entries {
+            write!(
+                out,
+                "- [ ] {} {} · {} #{} · migrated {}×",
+                entry.item.text,
+                entry.item.priority,
+                entry.date,
+                entry.index,
+                entry.item.migrations()
+            )?;
+
+            let age = entry.item.age_days(report.today);
+            if age > 0 {
+                write!(out, " · {age}d old")?;
+            }
+
+            writeln!(out)?;
+        }
+
+        writeln!(out)?;
+
+        let count = report.entries.len();
+        match report.action {
+            StaleAction::List => writeln!(out, "> **{count} stale task(s)**")?,
+            StaleAction::Drop => writeln!(out, "> **Dropped {count} task(s)**")?,
+            StaleAction::Focus => writeln!(out, "> **Moved {count} task(s) to focus**")?,
+            StaleAction::Reprioritise(p) => {
+                writeln!(out, "> **Set {count} task(s) to {p} priority**")?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_timer(&self, change: &TimerChange) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        if let Some(stopped) = &change.stopped {
+            self.render_timer_entry(&mut out, stopped)?;
+        }
+
+        if let Some(started) = &change.started {
+            self.render_timer_entry(&mut out, started)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_pomodoro(&self, run: &PomodoroRun) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(out, "# Pomodoro")?;
+        writeln!(out)?;
+        writeln!(out, "- **Task:** {} {}", run.reference, run.item.text)?;
+        writeln!(out, "- **Rounds:** {}/{}", run.completed, run.plan.rounds)?;
+        writeln!(out, "- **Pomodoros in total:** {}", run.item.pomodoros())?;
+        writeln!(
+            out,
+            "- **Logged:** {}",
+            format_minutes(run.item.logged().num_minutes())
+        )?;
+
+        if run.marked_done {
+            writeln!(out, "- **Marked done**")?;
+        }
+
+        Ok(())
+    }
+
+    fn render_stats(&self, report: &StatsReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(
+            out,
+            "# Stats: {} → {}",
+            report.start.format(&self.formats.date),
+            report.end.format(&self.formats.date)
+        )?;
+        writeln!(out, "> Last {}", report.range)?;
+        writeln!(out)?;
+
+        if report.counts.total == 0 {
+            writeln!(out, "_No tasks in the last {}._", report.range)?;
+            return Ok(());
+        }
+
+        writeln!(out, "## Summary")?;
+        writeln!(
+            out,
+            "- **Completion:** {} ({} of {} done)",
+            format_rate(report.counts.rate()),
+            report.counts.done,
+            report.counts.total
+        )?;
+        writeln!(
+            out,
+            "- **Items per day:** {:.1} ({} active day(s))",
+            report.items_per_day(),
+            report.active_days
+        )?;
+        writeln!(
+            out,
+            "- **Streak:** {} day(s) (longest {})",
+            report.current_streak, report.longest_streak
+        )?;
+        if let Some(mean) = report.time_to_complete {
+            writeln!(
+                out,
+                "- **Time to complete:** {} on average",
+                format_minutes(mean.num_minutes())
+            )?;
+        }
+        writeln!(
+            out,
+            "- **Migrated:** {} ({} of {})",
+            format_rate(report.migration_rate()),
+            report.migrated,
+            report.counts.total
+        )?;
+        writeln!(out)?;
+
+        writeln!(out, "## Completion over time")?;
+        writeln!(out)?;
+        writeln!(out, "| From | To | Done | Total | Rate |")?;
+        writeln!(out, "| --- | --- | ---: | ---: | ---: |")?;
+        for p in &report.periods {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                p.start.format(&self.formats.date),
+                p.end.format(&self.formats.date),
+                p.counts.done,
+                p.counts.total,
+                format_rate(p.counts.rate())
+            )?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## By priority")?;
+        writeln!(out)?;
+        let priorities = report
+            .by_priority
+            .iter()
+            .map(|(p, c)| (p.to_string(), *c))
+            .collect::<Vec<_>>();
+        Self::render_counts_table(&mut out, "Priority", &priorities)?;
+
+        if !report.by_tag.is_empty() {
+            writeln!(out, "## By tag")?;
+            writeln!(out)?;
+            let tags = report
+                .tags()
+                .into_iter()
+                .map(|(t, c)| (format!("#{t}"), c))
+                .collect::<Vec<_>>();
+            Self::render_counts_table(&mut out, "Tag", &tags)?;
+        }
+
+        writeln!(out, "## Weekdays")?;
+        writeln!(out)?;
+        let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .iter()
+            .zip(&report.weekdays)
+            .map(|(d, c)| (d.to_string(), *c))
+            .collect::<Vec<_>>();
+        Self::render_counts_table(&mut out, "Weekday", &weekdays)?;
+
+        Ok(())
+    }
+
+    fn render_calendar(&self, calendar: &Calendar) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match calendar.span {
+            CalendarSpan::Month(first) => writeln!(out, "# Calendar: {}", first.format("%B %Y"))?,
+            CalendarSpan::Year(year) => writeln!(out, "# Calendar: {year}")?,
+        }
+        writeln!(out)?;
+
+        let (done, total) = calendar.totals();
+        writeln!(
+            out,
+            "> {done} of {total} task(s) done on {} day(s)",
+            calendar.active_days()
+        )?;
+        writeln!(out)?;
+
+        if let CalendarSpan::Month(_) = calendar.span {
+            writeln!(out, "| Mon | Tue | Wed | Thu | Fri | Sat | Sun |")?;
+            writeln!(out, "| --- | --- | --- | --- | --- | --- | --- |")?;
+
+            for week in calendar.weeks() {
+                let cells = week
+                    .iter()
+                    .map(|day| day.map(Self::calendar_cell).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+
+                writeln!(out, "| {cells} |")?;
+            }
+
+            writeln!(out)?;
+            writeln!(out, "_done/total, ↪ open migrated, ! due_")?;
+            return Ok(());
+        }
+
+        writeln!(out, "| Date | Done | Total | Open migrated | Due |")?;
+        writeln!(out, "| --- | ---: | ---: | ---: | ---: |")?;
+
+        for day in calendar.days.iter().filter(|d| d.total > 0 || d.due > 0) {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                day.date.format(&self.formats.date),
+                day.done,
+                day.total,
+                day.open_migrated,
+                day.due
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn render_plan(&self, plan: &Plan) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match plan.span {
+            PlanSpan::Week => {
+                writeln!(out, "# Week of {}", plan.start().format(&self.formats.date))?
+            }
+            PlanSpan::Month => writeln!(out, "# {}", plan.start().format("%B %Y"))?,
+        }
+        writeln!(out)?;
+
+        if !plan.focus.items.is_empty() {
+            writeln!(out, "## Focus Tasks")?;
+            writeln!(out)?;
+            self.render_list(&mut out, &plan.focus.items)?;
+            writeln!(out)?;
+        }
+
+        // A month would mostly be empty days, so only the ones with tasks are listed.
+        let days = plan
+            .days
+            .iter()
+            .filter(|d| plan.span == PlanSpan::Week || !d.dayfile.items.is_empty());
+
+        for day in days {
+            writeln!(out, "## {}", day.date().format(&self.formats.date))?;
+            writeln!(out)?;
+
+            for (idx, item) in day.dayfile.items.iter().enumerate() {
+                let checkbox = if item.done_at.is_some() {
+                    "- [x]"
+                } else {
+                    "- [ ]"
+                };
+                writeln!(
+                    out,
+                    "{checkbox} `{}` {} {}",
+                    day.reference(idx + 1),
+                    item.text,
+                    item.priority
+                )?;
+
+            }
+
+            if !day.dayfile.items.is_empty() {
+                writeln!(out)?;
+            }
+            self.render_footer(&mut out, day.stats())?;
+            writeln!(out)?;
+        }
+
+        writeln!(out, "---")?;
+        writeln!(out)?;
+        self.render_footer(&mut out, plan.stats())?;
+
+        Ok(())
+    }
+
+    fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()> {
+        let mut err = io::stderr().lock();
+
+        writeln!(err, "error: {} (command: {command})", e)?;
+
+        if let Some(hint) = e.hint() {
+            writeln!(err, "hint: {}", hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MarkdownRenderer {
+    fn calendar_cell(day: &CalendarDay) -> String {
+        let mut cell = format!("**{}**", day.date.day());
+
+        if day.total > 0 {
+            cell.push_str(&format!(" {}/{}", day.done, day.total));
+        }
+        if day.open_migrated > 0 {
+            cell.push_str(" ↪");
+        }
+        if day.due > 0 {
+            cell.push_str(" !");
+        }
+
+        cell
+    }
+
+    fn render_counts_table(
+        out: &mut impl Write,
+        heading: &str,
+        groups: &[(String, Counts)],
+    ) -> std::io::Result<()> {
+        writeln!(out, "| {heading} | Done | Total | Rate |")?;
+        writeln!(out, "| --- | ---: | ---: | ---: |")?;
+
+        for (label, counts) in groups {
+            writeln!(
+                out,
+                "| {label} | {} | {} | {} |",
+                counts.done,
+                counts.total,
+                format_rate(counts.rate())
+            )?;
+        }
+
+        writeln!(out)
+    }
+
+    fn render_time_report(&self, out: &mut impl Write, time: &TimeReport) -> std::io::Result<()> {
+        writeln!(out, "## Time")?;
+        writeln!(
+            out,
+            "- **Total:** {}",
+            format_minutes(time.total.num_minutes())
+        )?;
+        writeln!(out)?;
+
+        writeln!(out, "### By day")?;
+        for (date, spent) in &time.by_day {
+            writeln!(
+                out,
+                "- {}: {}",
+                date.format(&self.formats.date),
+                format_minutes(spent.num_minutes())
+            )?;
+        }
+        writeln!(out)?;
+
+        if !time.by_tag.is_empty() {
+            writeln!(out, "### By tag")?;
+            for (tag, spent) in time.tags() {
+                writeln!(out, "- #{tag}: {}", format_minutes(spent.num_minutes()))?;
+            }
+            writeln!(out)?;
+        }
+
+        writeln!(out, "### By priority")?;
+        for (priority, spent) in &time.by_priority {
+            writeln!(out, "- {priority}: {}", format_minutes(spent.num_minutes()))?;
+        }
+        writeln!(out)
+    }
+
+    fn render_timer_entry(&self, out: &mut impl Write, entry: &TimerEntry) -> std::io::Result<()> {
+        let list = match entry.target {
+            TaskTarget::Day => entry.date.format(&self.formats.date).to_string(),
+            TaskTarget::Focus => "focus".to_string(),
+        };
+
+        let Some(elapsed) = entry.elapsed() else {
+            let at = entry.started_at.with_timezone(&Local).format("%H:%M");
+            return writeln!(
+                out,
+                "- **Started:** {} *({list}, at {at})*",
+                entry.item.text
+            );
+        };
+
+        let logged = format_minutes(entry.item.logged().num_minutes());
+        let total = match entry.item.estimate_minutes {
+            Some(estimate) => format!("{logged} of {} logged", format_minutes(estimate.into())),
+            None => format!("{logged} logged"),
+        };
+
+        writeln!(
+            out,
+            "- **Stopped:** {} *({list}, {}, {total})*",
+            entry.item.text,
+            format_minutes(elapsed.num_minutes())
+        )
+    }
+
+    fn render_header(&self, out: &mut impl Write, date: NaiveDate) -> std::io::Result<()> {
+        writeln!(out, "# Tasks")?;
+        writeln!(out)?;
+        writeln!(out, "## {}", date.format(&self.formats.date))?;
+        writeln!(out)
+    }
+
+    fn build_date_line(&self, to_date: NaiveDate, migration_date: Option<NaiveDate>) -> String {
+        if let Some(migration_date) = migration_date {
+            let from_date_s = migration_date.format(&self.formats.date);
+            format!(
+                "From **{}** → **{}**",
+                from_date_s,
+                to_date.format(&self.formats.date)
+            )
+        } else {
+            format!("On **{}**", to_date.format(&self.formats.date))
+        }
+    }
+
+    fn render_list(&self, out: &mut impl Write, items: &[Item]) -> std::io::Result<()> {
+        for item in items {
+            let is_done = item.done_at.is_some();
+            let checkbox = if is_done { "- [x]" } else { "- [ ]" };
+
+            let priority = format!("{}", item.priority);
+            write!(out, "{checkbox} {} {}", item.text, priority)?;
+
+            if let Some((done, total)) = item.progress() {
+                write!(out, " · {done}/{total}")?;
+            }
+
+            self.render_due_label(out, item)?;
+
+            if !is_done {
+                let migrations = item.migrations();
+                if migrations > 0 {
+                    write!(out, " · migrated {migrations}×")?;
+                }
+
+                let age = item.age_days(todays_date());
+                if age > 0 {
+                    write!(out, " · {age}d old")?;
+                }
+            }
+
+            writeln!(out)?;
+
+            for s in &item.subtasks {
+                let checkbox = if s.done_at.is_some() {
+                    "- [x]"
+                } else {
+                    "- [ ]"
+                };
+                writeln!(out, "  {checkbox} {}", s.text)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_due_label(&self, out: &mut impl Write, item: &Item) -> std::io::Result<()> {
+        if let Some(due) = item.due {
+            write!(out, " · due {}", self.formats.format_due(&due))?;
+
+            if item.due_state(Utc::now(), DUE_SOON_DAYS) == Some(DueState::Overdue) {
+                write!(out, " **overdue**")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_footer(&self, out: &mut impl Write, stats: SummaryStats) -> std::io::Result<()> {
+        writeln!(
+            out,
+            "> **{} task(s)** ({} open, {} done)",
+            stats.total, stats.open, stats.completed
+        )?;
+
+        Ok(())
+    }
+
+    fn render_migration_count(
+        &self,
+        out: &mut impl Write,
+        migration: &Migration,
+    ) -> std::io::Result<()> {
+        let count = migration.moved.len();
+
+        writeln!(out)?;
+
+        if count > 0 {
+            let item_word = if count == 1 { "item" } else { "items" };
+            let details = match (migration.dry_run, migration.copy) {
+                (true, false) => "will be migrated from:",
+                (true, true) => "will be copied from:",
+                (false, false) => "migrated:",
+                (false, true) => "copied:",
+            };
+
+            writeln!(
+                out,
+                "> {} {} {} {}",
+                count,
+                item_word,
+                details,
+                migration.from.date.format(&self.formats.date)
+            )?;
+        }
+
+        for (n, what) in [
+            (migration.focused.len(), "sent to focus"),
+            (migration.dropped.len(), "dropped"),
+            (migration.kept, "kept"),
+        ] {
+            if n > 0 {
+                writeln!(out, "> {n} {what}")?;
+            }
+        }
+
+        Ok(())
+    }
 }
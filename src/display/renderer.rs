@@ -3,8 +3,33 @@ use std::io::Error;
 use chrono::NaiveDate;
 
 use crate::{
-    models::{dayfile::DayFile, item::Item},
-    utils::{render::ActionKind, tusk_error::TuskError}, view::agenda::Agenda,
+    models::{dayfile::DayFile, item::Item, recurrence::RecurrenceRule},
+    utils::{
+        render::{ActionKind, ConfigAction, HistoryAction, RuleAction, VaultAction},
+        task_ref::{TaskPath, TaskRef},
+        tusk_error::TuskError,
+    },
+    view::{
+        agenda::Agenda,
+        batch::Batch,
+        calendar::Calendar,
+        config::ConfigEntry,
+        due::DueReport,
+        export::ExportReport,
+        history::{History, HistoryEntry},
+        import::ImportReport,
+        migration::Migration,
+        plan::Plan,
+        pomodoro::PomodoroRun,
+        rollover::Rollover,
+        search::SearchResults,
+        stale::StaleReport,
+        stats::StatsReport,
+        store_migration::StoreMigration,
+        time_report::TimeReport,
+        timer::TimerChange,
+        vault::VaultSummary,
+    },
 };
 
 pub trait Renderer {
@@ -12,15 +37,14 @@ pub trait Renderer {
 
     fn render_day(&self, df: &DayFile) -> std::io::Result<()>;
 
-    fn render_summary(&self, date: Option<NaiveDate>, index: usize, item: &Item) -> std::io::Result<()>;
-
-    fn render_migrate(
+    fn render_summary(
         &self,
-        to_date: NaiveDate,
-        from_df_original: &DayFile,
-        moved_items: &[Item],
-        dry_run: bool,
-    ) -> Result<(), Error>;
+        date: Option<NaiveDate>,
+        reference: &TaskRef,
+        item: &Item,
+    ) -> std::io::Result<()>;
+
+    fn render_migrate(&self, migration: &Migration) -> Result<(), Error>;
 
     fn render_review(
         &self,
@@ -28,15 +52,72 @@ pub trait Renderer {
         end: NaiveDate,
         days: u64,
         dayfiles: &[DayFile],
+        time: &TimeReport,
     ) -> std::io::Result<()>;
 
     fn render_action(
         &self,
-        index: usize,
+        reference: &TaskPath,
         date: NaiveDate,
         action: ActionKind,
         item: Option<&Item>,
     ) -> std::io::Result<()>;
 
+    fn render_batch(&self, batch: &Batch) -> std::io::Result<()>;
+
+    fn render_due(&self, report: &DueReport) -> std::io::Result<()>;
+
+    fn render_search(&self, results: &SearchResults) -> std::io::Result<()>;
+
+    fn render_rules(&self, rules: &[RecurrenceRule], today: NaiveDate) -> std::io::Result<()>;
+
+    fn render_rule_action(
+        &self,
+        reference: &TaskRef,
+        action: RuleAction,
+        rule: &RecurrenceRule,
+    ) -> std::io::Result<()>;
+
+    fn render_history(&self, history: &History) -> std::io::Result<()>;
+
+    fn render_history_action(
+        &self,
+        action: HistoryAction,
+        entry: &HistoryEntry,
+    ) -> std::io::Result<()>;
+
+    fn render_store_migrate(&self, migration: &StoreMigration) -> std::io::Result<()>;
+
+    fn render_export(&self, report: &ExportReport) -> std::io::Result<()>;
+
+    fn render_config(&self, action: ConfigAction, entries: &[ConfigEntry]) -> std::io::Result<()>;
+
+    fn render_vaults(&self, vaults: &[VaultSummary]) -> std::io::Result<()>;
+
+    fn render_vault_action(
+        &self,
+        action: VaultAction,
+        vault: &str,
+        from: Option<&str>,
+    ) -> std::io::Result<()>;
+
+    fn render_import(&self, report: &ImportReport) -> std::io::Result<()>;
+
+    /// A short notice that open items were rolled over, written to stderr so
+    /// it never mixes with the command's own output.
+    fn render_rollover(&self, rollover: &Rollover) -> std::io::Result<()>;
+
+    fn render_stale(&self, report: &StaleReport) -> std::io::Result<()>;
+
+    fn render_timer(&self, change: &TimerChange) -> std::io::Result<()>;
+
+    fn render_pomodoro(&self, run: &PomodoroRun) -> std::io::Result<()>;
+
+    fn render_stats(&self, report: &StatsReport) -> std::io::Result<()>;
+
+    fn render_calendar(&self, calendar: &Calendar) -> std::io::Result<()>;
+
+    fn render_plan(&self, plan: &Plan) -> std::io::Result<()>;
+
     fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()>;
 }
@@ -1,24 +1,99 @@
-use chrono::{Days, NaiveDate};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Utc};
 use colored::Colorize;
-use std::{
-    io::{self, Error, Write},
-    usize,
-};
+use std::io::{self, Error, IsTerminal, Write};
 
 use crate::{
     display::renderer::Renderer,
-    models::{dayfile::DayFile, item::Item},
-    utils::{helpers::{SummaryStats, item_count_meta}, render::ActionKind, theme::Theme, tusk_error::TuskError},
-    view::agenda::Agenda,
+    import::Target,
+    models::{
+        dayfile::DayFile,
+        item::{DUE_SOON_DAYS, DueState, Item, ListMove, STALE_MIGRATIONS},
+        journal::OperationKind,
+        recurrence::RecurrenceRule,
+    },
+    utils::{
+        dates::{format_minutes, is_all_day, todays_date},
+        helpers::{SummaryStats, item_count_meta},
+        render::{ActionKind, ConfigAction, HistoryAction, RuleAction, VaultAction},
+        task_ref::{TaskPath, TaskRef},
+        task_target::TaskTarget,
+        theme::Theme,
+        tusk_error::TuskError,
+    },
+    view::{
+        agenda::Agenda,
+        batch::Batch,
+        calendar::{Calendar, CalendarDay, CalendarSpan, HeatLevel},
+        config::{ConfigEntry, ConfigSource},
+        due::DueReport,
+        export::ExportReport,
+        history::{History, HistoryEntry},
+        import::{ImportOutcome, ImportReport},
+        migration::Migration,
+        plan::{Plan, PlanDay, PlanSpan},
+        pomodoro::PomodoroRun,
+        rollover::Rollover,
+        search::SearchResults,
+        stale::{StaleAction, StaleReport},
+        stats::{Counts, StatsReport, format_rate},
+        store_migration::StoreMigration,
+        time_report::TimeReport,
+        timer::{TimerChange, TimerEntry},
+        vault::VaultSummary,
+    },
 };
 
 pub const DATE_FORMAT: &str = "%a %d %b %Y";
 pub const DATE_WITH_TIME_FORMAT: &str = "%a %d %b %Y %H:%M";
+pub const DUE_FORMAT: &str = "%a %d %b";
+pub const DUE_WITH_TIME_FORMAT: &str = "%a %d %b %H:%M";
+
+const STATS_BAR_WIDTH: usize = 20;
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// How narrow a day's column can get before `week` and `month` stack the days.
+const PLAN_COLUMN_MIN: usize = 18;
+const PLAN_COLUMN_MAX: usize = 32;
+const PLAN_COLUMN_GAP: &str = "  ";
+
+/// The chrono formats dates are shown with, set through `tusk config`.
+#[derive(Debug, Clone)]
+pub struct DateFormats {
+    pub date: String,
+    pub date_time: String,
+    pub due: String,
+    pub due_time: String,
+}
+
+impl Default for DateFormats {
+    fn default() -> Self {
+        Self {
+            date: DATE_FORMAT.to_string(),
+            date_time: DATE_WITH_TIME_FORMAT.to_string(),
+            due: DUE_FORMAT.to_string(),
+            due_time: DUE_WITH_TIME_FORMAT.to_string(),
+        }
+    }
+}
+
+impl DateFormats {
+    /// Formats a due date in local time, leaving off the time for all-day dues.
+    pub fn format_due(&self, due: &DateTime<Utc>) -> String {
+        let local = due.with_timezone(&Local);
+
+        if is_all_day(due) {
+            local.format(&self.due).to_string()
+        } else {
+            local.format(&self.due_time).to_string()
+        }
+    }
+}
 
 pub struct TerminalRenderer {
     pub theme: Theme,
     pub vault: Option<String>,
     pub verbose: bool,
+    pub formats: DateFormats,
 }
 
 impl Renderer for TerminalRenderer {
@@ -57,7 +132,7 @@ impl Renderer for TerminalRenderer {
             writeln!(
                 out,
                 "🦣 {}",
-                self.theme.dim(&format!("No tasks for {}", df.date))
+                self.theme.dim(format!("No tasks for {}", df.date))
             )?;
 
             let hint = r#"tusk add "Drink more water 💦""#;
@@ -75,7 +150,7 @@ impl Renderer for TerminalRenderer {
     fn render_summary(
         &self,
         _date: Option<NaiveDate>,
-        index: usize,
+        reference: &TaskRef,
         item: &Item,
     ) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
@@ -84,7 +159,7 @@ impl Renderer for TerminalRenderer {
         writeln!(
             out,
             "{}  {}",
-            self.theme.info(&format!("#{}", index)),
+            self.theme.info(reference),
             Self::format_text(&item.text, &self.theme)
         )?;
 
@@ -113,25 +188,131 @@ impl Renderer for TerminalRenderer {
             out,
             "    {} {}",
             self.theme.dim("Created:"),
-            item.created_at.format(DATE_WITH_TIME_FORMAT)
+            item.created_at.format(&self.formats.date_time)
         )?;
 
         writeln!(out, "    {} {}", self.theme.dim("Status:"), item.status())?;
 
+        if let Some(due) = item.due {
+            let state = item.due_state(Utc::now(), DUE_SOON_DAYS);
+            let label = match state {
+                Some(s @ (DueState::Overdue | DueState::Today | DueState::Soon)) => {
+                    format!("{} ({})", self.formats.format_due(&due), s)
+                }
+                _ => self.formats.format_due(&due),
+            };
+            writeln!(
+                out,
+                "    {} {}",
+                self.theme.dim("Due:"),
+                self.theme.due(label, state)
+            )?;
+        }
+
         if let Some(ts) = item.done_at {
-            let done_at = ts.format(DATE_WITH_TIME_FORMAT);
+            let done_at = ts.format(&self.formats.date_time);
             writeln!(out, "    {} {}", self.theme.dim("Done:"), done_at)?;
         }
 
+        if let Some(rule_id) = &item.recurrence_id {
+            writeln!(out, "    {} ↻ @{}", self.theme.dim("Recurring:"), rule_id)?;
+        }
+
         if let Some(migrated_from) = item.migrated_from {
             writeln!(
                 out,
                 "    {} {}",
                 self.theme.dim("Migrated from:"),
-                migrated_from.format(DATE_WITH_TIME_FORMAT).to_string()
+                migrated_from.format(&self.formats.date)
+            )?;
+        }
+
+        if item.migrations() > 1 {
+            let history = item
+                .migration_history
+                .iter()
+                .map(|d| d.format(&self.formats.due).to_string())
+                .collect::<Vec<_>>()
+                .join(" → ");
+
+            writeln!(
+                out,
+                "    {} {} time(s): {}",
+                self.theme.dim("Migrated:"),
+                item.migrations(),
+                history
+            )?;
+        }
+
+        for m in &item.list_moves {
+            let (label, detail) = match m {
+                ListMove::Promoted { date, .. } => (
+                    "Promoted:",
+                    format!("to focus from {}", date.format(&self.formats.date)),
+                ),
+                ListMove::Demoted { date, .. } => (
+                    "Demoted:",
+                    format!("from focus to {}", date.format(&self.formats.date)),
+                ),
+            };
+
+            writeln!(out, "    {} {}", self.theme.dim(label), detail)?;
+        }
+
+        writeln!(
+            out,
+            "    {} {} day(s)",
+            self.theme.dim("Age:"),
+            item.age_days(todays_date())
+        )?;
+
+        if item.estimate_minutes.is_some() || !item.sessions.is_empty() {
+            let logged = item.logged().num_minutes();
+
+            let time = match item.estimate_minutes.map(i64::from) {
+                Some(estimate) if logged > estimate => self.theme.warn(format!(
+                    "{} logged of {} estimated",
+                    format_minutes(logged),
+                    format_minutes(estimate)
+                )),
+                Some(estimate) => self.theme.plain(&format!(
+                    "{} logged of {} estimated",
+                    format_minutes(logged),
+                    format_minutes(estimate)
+                )),
+                None => self
+                    .theme
+                    .plain(&format!("{} logged", format_minutes(logged))),
+            };
+
+            writeln!(out, "    {} {}", self.theme.dim("Time:"), time)?;
+        }
+
+        if item.pomodoros() > 0 {
+            writeln!(
+                out,
+                "    {} 🍅 {}",
+                self.theme.dim("Pomodoros:"),
+                item.pomodoros()
             )?;
         }
 
+        // Subtasks
+        if let Some((done, total)) = item.progress() {
+            writeln!(out, "    {} {done}/{total}", self.theme.dim("Subtasks:"))?;
+
+            for (idx, s) in item.subtasks.iter().enumerate() {
+                let boxy = self.theme.checkbox(s.done_at.is_some());
+                let line = format!("{}. {boxy} {}", idx + 1, s.text);
+
+                if s.done_at.is_some() {
+                    writeln!(out, "      {}", self.theme.dim(&line))?;
+                } else {
+                    writeln!(out, "      {line}")?;
+                }
+            }
+        }
+
         // Notes
         if let Some(n) = &item.notes {
             writeln!(out, "    {} ", self.theme.dim("Notes:"))?;
@@ -143,26 +324,42 @@ impl Renderer for TerminalRenderer {
         Ok(())
     }
 
-    fn render_migrate(
-        &self,
-        to_date: NaiveDate,
-        from_df_original: &DayFile,
-        moved_items: &[Item],
-        dry_run: bool,
-    ) -> Result<(), Error> {
+    fn render_migrate(&self, migration: &Migration) -> Result<(), Error> {
         let mut out = io::stdout().lock();
 
-        let title = self.build_title_header(to_date, Some(from_df_original.date));
+        let title = self.build_title_header(migration.to, Some(migration.from.date));
         Self::title_underline(&self.theme, &title, &mut out)?;
 
-        if moved_items.is_empty() {
+        for (pos, item) in &migration.already_done {
+            writeln!(
+                out,
+                "  {} {:>2}. {} {}",
+                self.theme.warn("-"),
+                pos + 1,
+                self.theme.dim(&item.text),
+                self.theme.warn("already done, not migrated")
+            )?;
+        }
+
+        if migration.is_empty() {
             writeln!(out, "🦣 {}", self.theme.dim("No tasks to migrate."))?;
 
             return Ok(());
         }
 
-        self.render_list(&mut out, moved_items)?;
-        self.render_migratation_count(&mut out, moved_items, from_df_original.date, dry_run)?;
+        self.render_list(&mut out, &migration.moved)?;
+
+        if !migration.focused.is_empty() {
+            writeln!(out, "{}", self.theme.subtitle("Sent to focus"))?;
+            self.render_list(&mut out, &migration.focused)?;
+        }
+
+        if !migration.dropped.is_empty() {
+            writeln!(out, "{}", self.theme.subtitle("Dropped"))?;
+            self.render_list(&mut out, &migration.dropped)?;
+        }
+
+        self.render_migratation_count(&mut out, migration)?;
 
         Ok(())
     }
@@ -173,6 +370,7 @@ impl Renderer for TerminalRenderer {
         end: NaiveDate,
         days: u64,
         dayfiles: &[DayFile],
+        time: &TimeReport,
     ) -> std::io::Result<()> {
         let mut out = io::stdout().lock();
 
@@ -181,8 +379,8 @@ impl Renderer for TerminalRenderer {
             .expect("end should always be at least one day after start");
 
         // Build title
-        let start_s = start.format(DATE_FORMAT);
-        let end_s = display_end.format(DATE_FORMAT);
+        let start_s = start.format(&self.formats.date);
+        let end_s = display_end.format(&self.formats.date);
 
         let raw_title = format!("Review: {start_s} → {end_s}");
 
@@ -201,7 +399,7 @@ impl Renderer for TerminalRenderer {
         writeln!(
             &mut out,
             "Last {} {} {}\n",
-            self.theme.info(&days.to_string()),
+            self.theme.info(days.to_string()),
             if days == 1 { "day" } else { "days" },
             self.theme.dim("(excluding today)"),
         )?;
@@ -213,28 +411,32 @@ impl Renderer for TerminalRenderer {
             &mut out,
             "  {} {}",
             self.theme.dim("Total:"),
-            self.theme.info(&count.total)
+            self.theme.info(count.total)
         )?;
         writeln!(
             &mut out,
             "  {} {}",
             self.theme.dim("Open:"),
-            self.theme.warn(&count.open)
+            self.theme.warn(count.open)
         )?;
         writeln!(
             &mut out,
             "  {} {}",
             self.theme.dim("Completed:"),
-            self.theme.ok(&count.complete)
+            self.theme.ok(count.complete)
         )?;
         writeln!(
             &mut out,
             "  {} {}",
             self.theme.dim("Active days:"),
-            self.theme.info(&dayfiles.len())
+            self.theme.info(dayfiles.len())
         )?;
         writeln!(&mut out)?;
 
+        if !time.is_empty() {
+            self.render_time_report(&mut out, time)?;
+        }
+
         for df in dayfiles {
             let total_item_count: usize = df.items.len();
             let total_open_item_count: usize =
@@ -242,7 +444,7 @@ impl Renderer for TerminalRenderer {
 
             let title = format!(
                 "{} • {} task(s) ({} open, {} done)",
-                df.date.format(DATE_FORMAT),
+                df.date.format(&self.formats.date),
                 total_item_count,
                 total_open_item_count,
                 total_item_count - total_open_item_count
@@ -266,7 +468,7 @@ impl Renderer for TerminalRenderer {
                     self.theme.plain(&item.text)
                 };
 
-                writeln!(
+                write!(
                     &mut out,
                     "{}. {} {} {}",
                     idx,
@@ -274,6 +476,9 @@ impl Renderer for TerminalRenderer {
                     text,
                     self.theme.priority(&item.priority)
                 )?;
+
+                self.render_due_label(&mut out, item)?;
+                writeln!(&mut out)?;
             }
         }
 
@@ -282,159 +487,1504 @@ impl Renderer for TerminalRenderer {
 
     fn render_action(
         &self,
-        _index: usize,
-        _date: NaiveDate,
-        _action: ActionKind,
-        _item: Option<&Item>,
+        reference: &TaskPath,
+        date: NaiveDate,
+        action: ActionKind,
+        item: Option<&Item>,
     ) -> std::io::Result<()> {
-        Ok(())
+        // Done, undone and rm stay quiet, an edit shows the item as it is now.
+        match (action, item) {
+            (ActionKind::Edited, Some(item)) => {
+                self.render_summary(Some(date), &reference.task, item)
+            }
+            _ => Ok(()),
+        }
     }
 
-    fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()> {
-        let mut err = io::stderr().lock();
+    fn render_batch(&self, batch: &Batch) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        if batch.entries.is_empty() {
+            writeln!(out, "🦣 {}", self.theme.dim("No matching items."))?;
+
+            return Ok(());
+        }
+
+        let destination = match batch.action {
+            ActionKind::Promoted => " to focus".to_string(),
+            ActionKind::Demoted => format!(" to {}", batch.date.format(&self.formats.date)),
+            _ => String::new(),
+        };
 
         writeln!(
-            err,
-            "{} {} {}",
-            self.theme.error("error:"),
-            e,
-            self.theme.dim(format!("(command: {command})"))
+            out,
+            "{} {} item(s){}:",
+            batch.action.as_past_tense(),
+            self.theme.info(batch.entries.len()),
+            destination
         )?;
 
-        if let Some(hint) = e.hint() {
-            writeln!(err, "{} {}", self.theme.hint("hint:"), hint)?;
+        for entry in &batch.entries {
+            let source = match entry.target {
+                TaskTarget::Day => entry.reference.to_string(),
+                TaskTarget::Focus => format!("focus {}", entry.reference),
+            };
+
+            let (text, is_done) = match &entry.subtask {
+                Some(s) => (&s.text, s.done_at.is_some()),
+                None => (&entry.item.text, entry.item.done_at.is_some()),
+            };
+
+            writeln!(
+                out,
+                "  {} {} {}",
+                self.theme.dim(source),
+                self.theme.checkbox(is_done),
+                Self::format_text(text, &self.theme)
+            )?;
         }
 
         Ok(())
     }
-}
-
-impl TerminalRenderer {
-    fn build_title_header(&self, to_date: NaiveDate, migration_date: Option<NaiveDate>) -> String {
-        let date_str = to_date.format("%a %d %b %Y").to_string();
 
-        let mut title = if let Some(date) = migration_date {
-            let from_date_str = date.format("%a %d %b %Y").to_string();
-            format!(
-                "Migration from {} → {}",
-                self.theme.info(&from_date_str),
-                self.theme.info(&date_str)
-            )
-        } else {
-            format!("Tasks for {}", date_str)
-        };
+    fn render_due(&self, report: &DueReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
+        let mut title = format!(
+            "Due • {}",
+            report.now.with_timezone(&Local).format(&self.formats.date)
+        );
         if let Some(v) = &self.vault {
             title.push_str(&format!(" • vault: {}", v));
         }
+        Self::title_underline(&self.theme, &title, &mut out)?;
 
-        title
-    }
-
-    fn render_list(&self, out: &mut impl Write, items: &[Item]) -> Result<(), Error> {
-        let width = items.len().to_string().len();
+        if report.entries.is_empty() {
+            writeln!(
+                out,
+                "🦣 {}",
+                self.theme.dim(format!(
+                    "Nothing due in the next {} day(s).",
+                    report.soon_days
+                ))
+            )?;
 
-        for (idx, i) in items.iter().enumerate() {
-            let n = idx + 1;
-            let is_done = i.done_at.is_some();
-            let boxy = self.theme.checkbox(is_done);
+            return Ok(());
+        }
 
-            let short_id = if self.verbose {
-                let id = format!("({})", Self::abbrev_id(&i.id, 6));
-                self.theme.dim(&id).to_string()
-            } else {
-                String::new()
-            };
+        let sections = [
+            (DueState::Overdue, "Overdue".to_string()),
+            (DueState::Today, "Due today".to_string()),
+            (
+                DueState::Soon,
+                format!("Due in the next {} day(s)", report.soon_days),
+            ),
+        ];
+
+        for (state, heading) in sections {
+            if report.count(state) == 0 {
+                continue;
+            }
 
-            let spacer = if short_id.is_empty() { "" } else { " " };
-            let line = format!(
-                "{n:>width$}. {boxy} {short_id}{spacer}{}",
-                Self::format_text(&i.text, &self.theme),
-                width = width
-            );
+            writeln!(out, "{}", self.theme.subtitle(heading))?;
 
-            let prio = format!(" {}", self.theme.priority(&i.priority));
+            for entry in report.with_state(state) {
+                let source = match entry.date {
+                    Some(d) => format!("{} #{}", d, entry.index),
+                    None => format!("focus #{}", entry.index),
+                };
 
-            if is_done {
-                write!(out, "{}{prio}", self.theme.dim(&line))?;
-            } else {
-                write!(out, "{line}{prio}")?;
-            }
+                write!(
+                    out,
+                    "  {} {} {} {}",
+                    self.theme.dim(source),
+                    self.theme.checkbox(false),
+                    Self::format_text(&entry.item.text, &self.theme),
+                    self.theme.priority(&entry.item.priority)
+                )?;
 
-            if let Some(migrated_from) = i.migrated_from {
-                let date_str = migrated_from.format("%a, %d %b").to_string();
-                write!(out, "  ↪ {}", self.theme.dim(&date_str))?;
+                self.render_due_label(&mut out, &entry.item)?;
+                writeln!(out)?;
             }
 
             writeln!(out)?;
         }
 
+        writeln!(
+            out,
+            "{} overdue, {} due today, {} due soon",
+            self.theme.error(report.count(DueState::Overdue)),
+            self.theme.warn(report.count(DueState::Today)),
+            self.theme.info(report.count(DueState::Soon))
+        )?;
+
         Ok(())
     }
 
-    fn render_footer(&self, out: &mut impl Write, dayfile: &DayFile) -> Result<(), Error> {
-        let stats = SummaryStats::from(dayfile);
+    fn render_search(&self, results: &SearchResults) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = format!("Search: {}", results.query);
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if results.hits.is_empty() {
+            writeln!(out, "🦣 {}", self.theme.dim("No matching tasks."))?;
+
+            return Ok(());
+        }
+
+        let mut current: Option<Option<NaiveDate>> = None;
+
+        for hit in &results.hits {
+            if current != Some(hit.date) {
+                if current.is_some() {
+                    writeln!(out)?;
+                }
+
+                let heading = match hit.date {
+                    Some(d) => format!("{} ({})", d.format(&self.formats.date), d),
+                    None => "Focus Tasks".to_string(),
+                };
+                writeln!(out, "{}", self.theme.subtitle(heading))?;
+                current = Some(hit.date);
+            }
+
+            let is_done = hit.item.done_at.is_some();
+            let line = format!(
+                "{:>3}. {} {}",
+                hit.index,
+                self.theme.checkbox(is_done),
+                Self::format_text(&hit.item.text, &self.theme)
+            );
+
+            if is_done {
+                write!(out, "{}", self.theme.dim(&line))?;
+            } else {
+                write!(out, "{line}")?;
+            }
+
+            write!(out, " {}", self.theme.priority(&hit.item.priority))?;
+            self.render_due_label(&mut out, &hit.item)?;
+            writeln!(out)?;
+        }
 
         writeln!(
             out,
-            "\n{} task(s) ({} open, {} done)",
-            &self.theme.info(stats.total),
-            &self.theme.warn(stats.open),
-            &self.theme.ok(stats.completed)
+            "\n{} match(es) across {} day(s)",
+            self.theme.info(results.hits.len()),
+            self.theme.info(results.days_searched)
         )?;
 
         Ok(())
     }
 
-    fn render_migratation_count(
-        &self,
-        out: &mut impl Write,
-        items: &[Item],
-        date: NaiveDate,
-        dry_run: bool,
-    ) -> std::io::Result<()> {
-        let count = items.len();
+    fn render_rules(&self, rules: &[RecurrenceRule], today: NaiveDate) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = "Recurring Tasks".to_string();
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if rules.is_empty() {
+            writeln!(out, "🦣 {}", self.theme.dim("No recurring tasks."))?;
+
+            let hint = r#"tusk recur add "Stand-up prep" --every weekdays"#;
+            writeln!(out, "   Add one with: {}", self.theme.ok(hint))?;
 
-        if count == 0 {
             return Ok(());
         }
 
-        let item_word = if count == 1 { "item" } else { "items" };
-        let details = if dry_run {
-            "will be migrated from:"
-        } else {
-            "migrated:"
-        };
+        let width = rules.len().to_string().len();
+
+        for (idx, rule) in rules.iter().enumerate() {
+            let n = idx + 1;
+            let line = format!(
+                "{n:>width$}. ↻ {} {}",
+                self.theme.dim(format!("(@{})", rule.id)),
+                Self::format_text(&rule.text, &self.theme),
+                width = width
+            );
+
+            let next = if rule.paused {
+                self.theme.warn("paused").to_string()
+            } else {
+                match rule.next_on_or_after(today) {
+                    Some(d) => self
+                        .theme
+                        .dim(format!("next {}", d.format(&self.formats.due)))
+                        .to_string(),
+                    None => self.theme.dim("never").to_string(),
+                }
+            };
+
+            writeln!(
+                out,
+                "{line} {}  {}  {}",
+                self.theme.priority(&rule.priority),
+                self.theme.info(&rule.schedule),
+                next
+            )?;
+        }
+
+        Ok(())
+    }
 
-        let date_str = date.format("%a %d %b %Y").to_string();
+    fn render_rule_action(
+        &self,
+        _reference: &TaskRef,
+        action: RuleAction,
+        rule: &RecurrenceRule,
+    ) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
         writeln!(
             out,
-            "  ↪ {} {} {} {}",
-            self.theme.info(&count.to_string()),
-            item_word,
-            details,
-            self.theme.info(&date_str)
+            "↻ {} {}  {} {}",
+            self.theme.ok(action.as_past_tense()),
+            self.theme.dim(format!("@{}", rule.id)),
+            Self::format_text(&rule.text, &self.theme),
+            self.theme.info(format!("({})", rule.schedule))
         )?;
 
         Ok(())
     }
 
-    // Utils
+    fn render_history(&self, history: &History) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
 
-    fn title_underline(theme: &Theme, title: &str, out: &mut impl Write) -> Result<(), Error> {
-        writeln!(out)?;
-        writeln!(out, "{}", theme.title(title))?;
-        let underline = "-".repeat(title.chars().count());
-        writeln!(out, "{underline}")?;
+        let mut title = "Recent Changes".to_string();
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if history.entries.is_empty() {
+            writeln!(out, "🦣 {}", self.theme.dim("No changes recorded yet."))?;
+
+            return Ok(());
+        }
+
+        let width = history
+            .entries
+            .iter()
+            .map(|e| e.command.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for entry in &history.entries {
+            let at = entry
+                .at
+                .with_timezone(&Local)
+                .format(&self.formats.date_time);
+            let command = format!("{:<width$}", entry.command, width = width);
+
+            write!(
+                out,
+                "{}  {}  {}  {}",
+                self.theme.dim(&entry.id),
+                self.theme.dim(at.to_string()),
+                self.theme.info(command),
+                entry.files_label()
+            )?;
+
+            match &entry.kind {
+                OperationKind::Do => {}
+                OperationKind::Undo { target } | OperationKind::Redo { target } => {
+                    write!(out, " {}", self.theme.dim(format!("(of {target})")))?;
+                }
+            }
+
+            if entry.undone {
+                write!(out, "  {}", self.theme.warn("undone"))?;
+            }
+
+            writeln!(out)?;
+        }
+
+        if history.total > history.entries.len() {
+            writeln!(
+                out,
+                "\n{}",
+                self.theme.dim(format!(
+                    "Showing {} of {} changes, use --limit to see more.",
+                    history.entries.len(),
+                    history.total
+                ))
+            )?;
+        }
 
         Ok(())
     }
 
-    fn title_underline_styled(
-        raw_title: &str,
-        styled_title: &str,
-        out: &mut impl Write,
+    fn render_history_action(
+        &self,
+        action: HistoryAction,
+        entry: &HistoryEntry,
+    ) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let arrow = match action {
+            HistoryAction::Undo => "↶",
+            HistoryAction::Redo => "↷",
+        };
+
+        writeln!(
+            out,
+            "{arrow} {} {}  {} {}",
+            self.theme.ok(action.as_past_tense()),
+            self.theme.info(&entry.command),
+            self.theme.dim(format!("({})", entry.id)),
+            entry.files_label()
+        )?;
+
+        Ok(())
+    }
+
+    fn render_store_migrate(&self, migration: &StoreMigration) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(
+            out,
+            "{} {} → {}  {} day(s), {} task(s), {} focus task(s)",
+            self.theme.ok("Migrated"),
+            self.theme.info(migration.from),
+            self.theme.info(migration.to),
+            migration.days,
+            migration.items,
+            migration.focus_items
+        )?;
+        writeln!(
+            out,
+            "{}",
+            self.theme.dim(format!(
+                "The vault now uses the {} store, the {} data was left in place.",
+                migration.to, migration.from
+            ))
+        )?;
+
+        Ok(())
+    }
+
+    fn render_config(&self, action: ConfigAction, entries: &[ConfigEntry]) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match action {
+            ConfigAction::Get => {
+                for entry in entries {
+                    writeln!(out, "{}", entry.value.as_deref().unwrap_or_default())?;
+                }
+            }
+            ConfigAction::Set | ConfigAction::Unset => {
+                for entry in entries {
+                    let scope = match entry.source {
+                        ConfigSource::Vault => "for this vault",
+                        _ => "globally",
+                    };
+
+                    match &entry.value {
+                        Some(value) => writeln!(
+                            out,
+                            "{} {} = {} {}",
+                            self.theme.ok("Set"),
+                            self.theme.title(&entry.key),
+                            value,
+                            self.theme.dim(scope)
+                        )?,
+                        None => writeln!(
+                            out,
+                            "{} {} {}",
+                            self.theme.ok("Unset"),
+                            self.theme.title(&entry.key),
+                            self.theme.dim(scope)
+                        )?,
+                    }
+                }
+            }
+            ConfigAction::Ls => {
+                let width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+
+                writeln!(out, "{}", self.theme.title("Config"))?;
+
+                for entry in entries {
+                    let value = entry.value.as_deref().unwrap_or_default();
+                    let value = match entry.source {
+                        ConfigSource::Default => self.theme.dim(value),
+                        _ => self.theme.plain(value),
+                    };
+
+                    writeln!(
+                        out,
+                        "  {:<width$}  {} {}",
+                        entry.key,
+                        value,
+                        self.theme.dim(format!("({})", entry.source.as_str()))
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_vaults(&self, vaults: &[VaultSummary]) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        Self::title_underline(&self.theme, "Vaults", &mut out)?;
+
+        if vaults.is_empty() {
+            writeln!(out, "🦣 {}", self.theme.dim("No vaults yet."))?;
+            return Ok(());
+        }
+
+        let width = vaults
+            .iter()
+            .map(|v| v.name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for vault in vaults {
+            let marker = if vault.current { "*" } else { " " };
+            let name = format!("{:<width$}", vault.name);
+            let last = match vault.last_activity {
+                Some(at) => format!(
+                    "last active {}",
+                    at.with_timezone(&Local).format(&self.formats.date_time)
+                ),
+                None => "no activity".to_string(),
+            };
+
+            writeln!(
+                out,
+                "{} {}  {} day(s) · {} open · {} done · {} focus  {}",
+                self.theme.ok(marker),
+                if vault.current {
+                    self.theme.title(&name)
+                } else {
+                    self.theme.plain(&name)
+                },
+                vault.days,
+                vault.open,
+                vault.done,
+                vault.focus,
+                self.theme.dim(last)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn render_vault_action(
+        &self,
+        action: VaultAction,
+        vault: &str,
+        from: Option<&str>,
+    ) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        match from {
+            Some(from) => writeln!(
+                out,
+                "{} {} → {}",
+                self.theme.ok(action.as_past_tense()),
+                self.theme.info(from),
+                self.theme.info(vault)
+            )?,
+            None => writeln!(
+                out,
+                "{} {}",
+                self.theme.ok(action.as_past_tense()),
+                self.theme.info(vault)
+            )?,
+        }
+
+        Ok(())
+    }
+
+    fn render_export(&self, report: &ExportReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(
+            out,
+            "{} {} task(s) from {} day(s) to {}",
+            self.theme.ok("Exported"),
+            report.items,
+            report.days,
+            self.theme.info(report.path.display())
+        )?;
+
+        if report.files > 1 {
+            writeln!(
+                out,
+                "{}",
+                self.theme
+                    .dim(format!("{} {} files written.", report.files, report.format))
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn render_import(&self, report: &ImportReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+        let imported = report.imported();
+
+        let summary = if report.dry_run {
+            format!(
+                "Would import {imported} task(s) from {}",
+                report.path.display()
+            )
+        } else {
+            format!("Imported {imported} task(s) from {}", report.path.display())
+        };
+        writeln!(out, "{}", self.theme.title(&summary))?;
+
+        for entry in &report.entries {
+            let target = match entry.target {
+                Some(Target::Day(date)) => date.format(&self.formats.date).to_string(),
+                Some(Target::Focus) => "Focus".to_string(),
+                None => "-".to_string(),
+            };
+
+            match &entry.outcome {
+                ImportOutcome::Imported => writeln!(
+                    out,
+                    "  {} {:>4}. {} {}",
+                    self.theme.ok("+"),
+                    entry.entry,
+                    entry.text,
+                    self.theme.dim(format!("→ {target}"))
+                )?,
+                ImportOutcome::Skipped(reason) => writeln!(
+                    out,
+                    "  {} {:>4}. {} {}",
+                    self.theme.warn("-"),
+                    entry.entry,
+                    self.theme.dim(&entry.text),
+                    self.theme.warn(format!("skipped: {reason}"))
+                )?,
+                ImportOutcome::Rejected(reason) => writeln!(
+                    out,
+                    "  {} {:>4}. {} {}",
+                    self.theme.error("×"),
+                    entry.entry,
+                    self.theme.dim(&entry.text),
+                    self.theme.error(format!("rejected: {reason}"))
+                )?,
+            }
+        }
+
+        let counts = [
+            (report.skipped().count(), "skipped"),
+            (report.rejected().count(), "rejected"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, what)| format!("{n} {what}"))
+        .collect::<Vec<_>>();
+
+        if !counts.is_empty() {
+            writeln!(out, "{}", self.theme.dim(format!("{}.", counts.join(", "))))?;
+        }
+
+        if report.dry_run {
+            writeln!(out, "{}", self.theme.hint("Dry run, nothing was written."))?;
+        }
+
+        Ok(())
+    }
+
+    fn render_rollover(&self, rollover: &Rollover) -> std::io::Result<()> {
+        let mut err = io::stderr().lock();
+
+        writeln!(
+            err,
+            "{} {} open task(s) over from {}",
+            self.theme.ok("↪ Rolled"),
+            rollover.items.len(),
+            rollover.from.format(&self.formats.date)
+        )
+    }
+
+    fn render_stale(&self, report: &StaleReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = format!("Stale • migrated {}+ times", report.min_migrations);
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if report.entries.is_empty() {
+            writeln!(
+                out,
+                "🦣 {}",
+                self.theme.dim(format!(
+                    "No open task has been migrated {} or more times.",
+                    report.min_migrations
+                ))
+            )?;
+
+            return Ok(());
+        }
+
+        for entry in &report.entries {
+            write!(
+                out,
+                "  {} {} {} {} {}",
+                self.theme.dim(format!("{} #{}", entry.date, entry.index)),
+                self.theme.checkbox(false),
+                Self::format_text(&entry.item.text, &self.theme),
+                self.theme.priority(&entry.item.priority),
+                self.theme.warn(format!("×{}", entry.item.migrations()))
+            )?;
+
+            let age = entry.item.age_days(report.today);
+            if age > 0 {
+                write!(out, " {}", self.theme.dim(format!("{age}d old")))?;
+            }
+
+            writeln!(out)?;
+        }
+
+        let count = report.entries.len();
+        let summary = match report.action {
+            StaleAction::List => format!(
+                "{} stale task(s). Use --drop, --focus or --priority to deal with them.",
+                self.theme.info(count)
+            ),
+            StaleAction::Drop => format!("Dropped {} task(s).", self.theme.ok(count)),
+            StaleAction::Focus => format!("Moved {} task(s) to focus.", self.theme.ok(count)),
+            StaleAction::Reprioritise(p) => format!(
+                "Set {} task(s) to {} priority.",
+                self.theme.ok(count),
+                self.theme.priority(&p)
+            ),
+        };
+        writeln!(out, "\n{summary}")?;
+
+        Ok(())
+    }
+
+    fn render_timer(&self, change: &TimerChange) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        if let Some(stopped) = &change.stopped {
+            self.render_timer_entry(&mut out, stopped)?;
+        }
+
+        if let Some(started) = &change.started {
+            self.render_timer_entry(&mut out, started)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_pomodoro(&self, run: &PomodoroRun) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        writeln!(
+            out,
+            "{} {}/{} round(s) on {}  {}",
+            self.theme.ok("🍅 Finished"),
+            run.completed,
+            run.plan.rounds,
+            self.theme.info(&run.reference),
+            Self::format_text(&run.item.text, &self.theme)
+        )?;
+
+        writeln!(
+            out,
+            "   {}",
+            self.theme.dim(format!(
+                "{} pomodoro(s) in total · {} logged",
+                run.item.pomodoros(),
+                format_minutes(run.item.logged().num_minutes())
+            ))
+        )?;
+
+        if run.marked_done {
+            writeln!(out, "   {} Marked done", self.theme.checkbox(true))?;
+        }
+
+        Ok(())
+    }
+
+    fn render_stats(&self, report: &StatsReport) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = format!(
+            "Stats • last {} • {} → {}",
+            report.range,
+            report.start.format(&self.formats.date),
+            report.end.format(&self.formats.date)
+        );
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if report.counts.total == 0 {
+            writeln!(
+                out,
+                "🦣 {}",
+                self.theme
+                    .dim(format!("No tasks in the last {}.", report.range))
+            )?;
+
+            return Ok(());
+        }
+
+        let rates = report
+            .periods
+            .iter()
+            .map(|p| p.counts.rate())
+            .collect::<Vec<_>>();
+
+        writeln!(
+            out,
+            "{}  {}  {} {}",
+            self.theme.subtitle("Completion"),
+            self.theme.ok(Self::sparkline(&rates)),
+            self.theme.info(format_rate(report.counts.rate())),
+            self.theme.dim(format!(
+                "({} of {} done)",
+                report.counts.done, report.counts.total
+            ))
+        )?;
+
+        writeln!(
+            out,
+            "  {} {:.1} {}",
+            self.theme.dim("Items per day:   "),
+            report.items_per_day(),
+            self.theme
+                .dim(format!("({} active day(s))", report.active_days))
+        )?;
+
+        writeln!(
+            out,
+            "  {} {} day(s) {}",
+            self.theme.dim("Streak:          "),
+            self.theme.info(report.current_streak),
+            self.theme
+                .dim(format!("(longest {})", report.longest_streak))
+        )?;
+
+        if let Some(mean) = report.time_to_complete {
+            writeln!(
+                out,
+                "  {} {} on average",
+                self.theme.dim("Time to complete:"),
+                format_minutes(mean.num_minutes())
+            )?;
+        }
+
+        writeln!(
+            out,
+            "  {} {} {}",
+            self.theme.dim("Migrated:        "),
+            format_rate(report.migration_rate()),
+            self.theme
+                .dim(format!("({} of {})", report.migrated, report.counts.total))
+        )?;
+
+        writeln!(out)?;
+        writeln!(out, "{}", self.theme.subtitle("By priority"))?;
+
+        let priorities = report
+            .by_priority
+            .iter()
+            .map(|(p, c)| (p.to_string(), *c))
+            .collect::<Vec<_>>();
+        self.render_count_bars(&mut out, &priorities)?;
+
+        if !report.by_tag.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{}", self.theme.subtitle("By tag"))?;
+
+            let tags = report
+                .tags()
+                .into_iter()
+                .map(|(t, c)| (format!("#{t}"), c))
+                .collect::<Vec<_>>();
+            self.render_count_bars(&mut out, &tags)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "{}", self.theme.subtitle("Busiest weekdays"))?;
+
+        let busiest = report.weekdays.iter().map(|c| c.total).max().unwrap_or(0);
+
+        for (day, counts) in WEEKDAYS.iter().zip(&report.weekdays) {
+            let bar = format!(
+                "{:<STATS_BAR_WIDTH$}",
+                Self::bar(counts.total, busiest, STATS_BAR_WIDTH)
+            );
+
+            if counts.total == busiest {
+                writeln!(out, "  {day}  {} {}", self.theme.ok(bar), counts.total)?;
+            } else {
+                writeln!(out, "  {day}  {} {}", bar, counts.total)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_calendar(&self, calendar: &Calendar) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = match calendar.span {
+            CalendarSpan::Month(first) => format!("Calendar • {}", first.format("%B %Y")),
+            CalendarSpan::Year(year) => format!("Calendar • {year}"),
+        };
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        match calendar.span {
+            CalendarSpan::Month(_) => self.render_month_grid(&mut out, calendar)?,
+            CalendarSpan::Year(_) => self.render_year_heatmap(&mut out, calendar)?,
+        }
+
+        let (done, total) = calendar.totals();
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{}",
+            self.theme.dim(format!(
+                "{done} of {total} task(s) done on {} day(s)",
+                calendar.active_days()
+            ))
+        )?;
+
+        let legend = HeatLevel::ALL
+            .iter()
+            .zip(["none", "0%", "under half", "half or more", "all"])
+            .map(|(level, label)| format!("{} {}", self.theme.heat(*level), self.theme.dim(label)))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        write!(out, "{legend}")?;
+
+        if matches!(calendar.span, CalendarSpan::Month(_)) {
+            write!(
+                out,
+                "  {} {}  {} {}",
+                self.theme.warn("↪"),
+                self.theme.dim("open migrated"),
+                self.theme.error("!"),
+                self.theme.dim("due")
+            )?;
+        }
+
+        writeln!(out)
+    }
+
+    fn render_plan(&self, plan: &Plan) -> std::io::Result<()> {
+        let mut out = io::stdout().lock();
+
+        let mut title = match plan.span {
+            PlanSpan::Week => format!("Week of {}", plan.start().format(&self.formats.date)),
+            PlanSpan::Month => plan.start().format("%B %Y").to_string(),
+        };
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+        Self::title_underline(&self.theme, &title, &mut out)?;
+
+        if !plan.focus.items.is_empty() {
+            writeln!(out, "{}", self.theme.subtitle("Focus Tasks"))?;
+            self.render_list(&mut out, &plan.focus.items)?;
+
+            let underline = "-".repeat(title.chars().count());
+            writeln!(out, "{underline}")?;
+        }
+
+        let width = io::stdout()
+            .is_terminal()
+            .then(crossterm::terminal::size)
+            .and_then(Result::ok)
+            .map(|(w, _)| w as usize)
+            .unwrap_or_default();
+        let gaps = PLAN_COLUMN_GAP.len() * 6;
+        let column = (width.saturating_sub(gaps) / 7).min(PLAN_COLUMN_MAX);
+
+        if column >= PLAN_COLUMN_MIN {
+            for (n, week) in plan.weeks().iter().enumerate() {
+                if n > 0 {
+                    writeln!(out)?;
+                }
+                self.render_plan_week(&mut out, week, plan.today, column)?;
+            }
+        } else {
+            self.render_plan_stacked(&mut out, plan)?;
+        }
+
+        let stats = plan.stats();
+        writeln!(
+            out,
+            "\n{} task(s) ({} open, {} done)",
+            &self.theme.info(stats.total),
+            &self.theme.warn(stats.open),
+            &self.theme.ok(stats.completed)
+        )?;
+
+        let example = plan
+            .days
+            .iter()
+            .find(|d| d.date() == plan.today)
+            .unwrap_or(&plan.days[0])
+            .reference(1);
+        let anchor = plan
+            .anchor()
+            .map(|d| format!(" --date {d}"))
+            .unwrap_or_default();
+
+        writeln!(
+            out,
+            "{}",
+            self.theme.dim(format!(
+                "Pick a day's items by its prefix, e.g. tusk done {example}{anchor}"
+            ))
+        )
+    }
+
+    fn render_error(&self, command: &'static str, e: &TuskError) -> std::io::Result<()> {
+        let mut err = io::stderr().lock();
+
+        writeln!(
+            err,
+            "{} {} {}",
+            self.theme.error("error:"),
+            e,
+            self.theme.dim(format!("(command: {command})"))
+        )?;
+
+        if let Some(hint) = e.hint() {
+            writeln!(err, "{} {}", self.theme.hint("hint:"), hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TerminalRenderer {
+    fn build_title_header(&self, to_date: NaiveDate, migration_date: Option<NaiveDate>) -> String {
+        let date_str = to_date.format(&self.formats.date).to_string();
+
+        let mut title = if let Some(date) = migration_date {
+            let from_date_str = date.format(&self.formats.date).to_string();
+            format!(
+                "Migration from {} → {}",
+                self.theme.info(&from_date_str),
+                self.theme.info(&date_str)
+            )
+        } else {
+            format!("Tasks for {}", date_str)
+        };
+
+        if let Some(v) = &self.vault {
+            title.push_str(&format!(" • vault: {}", v));
+        }
+
+        title
+    }
+
+    fn render_list(&self, out: &mut impl Write, items: &[Item]) -> Result<(), Error> {
+        let width = items.len().to_string().len();
+
+        for (idx, i) in items.iter().enumerate() {
+            let n = idx + 1;
+            let is_done = i.done_at.is_some();
+            let boxy = self.theme.checkbox(is_done);
+
+            let short_id = if self.verbose {
+                let id = format!("({})", Self::abbrev_id(&i.id, 6));
+                self.theme.dim(&id).to_string()
+            } else {
+                String::new()
+            };
+
+            let spacer = if short_id.is_empty() { "" } else { " " };
+            let line = format!(
+                "{n:>width$}. {boxy} {short_id}{spacer}{}",
+                Self::format_text(&i.text, &self.theme),
+                width = width
+            );
+
+            let prio = format!(" {}", self.theme.priority(&i.priority));
+
+            if is_done {
+                write!(out, "{}{prio}", self.theme.dim(&line))?;
+            } else {
+                write!(out, "{line}{prio}")?;
+            }
+
+            if let Some((done, total)) = i.progress() {
+                write!(out, " {}", self.theme.dim(format!("{done}/{total}")))?;
+            }
+
+            if let Some(migrated_from) = i.migrated_from {
+                let mut label = migrated_from.format("%a, %d %b").to_string();
+                let migrations = i.migrations();
+
+                if migrations > 1 {
+                    label.push_str(&format!(" ×{migrations}"));
+                }
+
+                if migrations >= STALE_MIGRATIONS && !is_done {
+                    write!(out, "  ↪ {}", self.theme.warn(&label))?;
+                } else {
+                    write!(out, "  ↪ {}", self.theme.dim(&label))?;
+                }
+            }
+
+            let age = i.age_days(todays_date());
+            if age > 0 && !is_done {
+                write!(out, " {}", self.theme.dim(format!("{age}d old")))?;
+            }
+
+            if i.recurrence_id.is_some() {
+                write!(out, " {}", self.theme.dim("↻"))?;
+            }
+
+            self.render_due_label(out, i)?;
+
+            writeln!(out)?;
+
+            for (sub_idx, s) in i.subtasks.iter().enumerate() {
+                let line = format!(
+                    "{:width$}  {n}.{} {} {}",
+                    "",
+                    sub_idx + 1,
+                    self.theme.checkbox(s.done_at.is_some()),
+                    s.text,
+                    width = width
+                );
+
+                if s.done_at.is_some() || is_done {
+                    writeln!(out, "{}", self.theme.dim(&line))?;
+                } else {
+                    writeln!(out, "{line}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_due_label(&self, out: &mut impl Write, item: &Item) -> Result<(), Error> {
+        if let Some(due) = item.due {
+            let state = item.due_state(Utc::now(), DUE_SOON_DAYS);
+            let label = format!("⏰ {}", self.formats.format_due(&due));
+            write!(out, "  {}", self.theme.due(label, state))?;
+        }
+
+        Ok(())
+    }
+
+    fn render_footer(&self, out: &mut impl Write, dayfile: &DayFile) -> Result<(), Error> {
+        let stats = SummaryStats::from(dayfile);
+
+        writeln!(
+            out,
+            "\n{} task(s) ({} open, {} done)",
+            &self.theme.info(stats.total),
+            &self.theme.warn(stats.open),
+            &self.theme.ok(stats.completed)
+        )?;
+
+        Ok(())
+    }
+
+    fn render_migratation_count(
+        &self,
+        out: &mut impl Write,
+        migration: &Migration,
+    ) -> std::io::Result<()> {
+        let count = migration.moved.len();
+
+        if count > 0 {
+            let item_word = if count == 1 { "item" } else { "items" };
+            let details = match (migration.dry_run, migration.copy) {
+                (true, false) => "will be migrated from:",
+                (true, true) => "will be copied from:",
+                (false, false) => "migrated:",
+                (false, true) => "copied:",
+            };
+
+            let date_str = migration.from.date.format(&self.formats.date).to_string();
+
+            writeln!(
+                out,
+                "  ↪ {} {} {} {}",
+                self.theme.info(count.to_string()),
+                item_word,
+                details,
+                self.theme.info(&date_str)
+            )?;
+        }
+
+        let others = [
+            (migration.focused.len(), "sent to focus"),
+            (migration.dropped.len(), "dropped"),
+            (migration.kept, "kept"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, what)| format!("{n} {what}"))
+        .collect::<Vec<_>>();
+
+        if !others.is_empty() {
+            writeln!(out, "  {}", self.theme.dim(others.join(", ")))?;
+        }
+
+        Ok(())
+    }
+
+    fn render_time_report(&self, out: &mut impl Write, time: &TimeReport) -> io::Result<()> {
+        writeln!(out, "Time")?;
+        writeln!(
+            out,
+            "  {} {}",
+            self.theme.dim("Total:"),
+            self.theme.info(format_minutes(time.total.num_minutes()))
+        )?;
+
+        writeln!(out, "  {}", self.theme.dim("By day:"))?;
+        for (date, spent) in &time.by_day {
+            writeln!(
+                out,
+                "    {}  {}",
+                date.format(&self.formats.date),
+                format_minutes(spent.num_minutes())
+            )?;
+        }
+
+        if !time.by_tag.is_empty() {
+            writeln!(out, "  {}", self.theme.dim("By tag:"))?;
+            for (tag, spent) in time.tags() {
+                writeln!(out, "    #{tag}  {}", format_minutes(spent.num_minutes()))?;
+            }
+        }
+
+        writeln!(out, "  {}", self.theme.dim("By priority:"))?;
+        for (priority, spent) in &time.by_priority {
+            writeln!(
+                out,
+                "    {priority}  {}",
+                format_minutes(spent.num_minutes())
+            )?;
+        }
+
+        writeln!(out)
+    }
+
+    fn render_timer_entry(&self, out: &mut impl Write, entry: &TimerEntry) -> io::Result<()> {
+        let list = match entry.target {
+            TaskTarget::Day => entry.date.format(&self.formats.date).to_string(),
+            TaskTarget::Focus => "focus".to_string(),
+        };
+
+        let text = Self::format_text(&entry.item.text, &self.theme);
+
+        let Some(elapsed) = entry.elapsed() else {
+            let at = entry.started_at.with_timezone(&Local).format("%H:%M");
+            return writeln!(
+                out,
+                "{} {} {}",
+                self.theme.ok("▶ Started"),
+                text,
+                self.theme.dim(format!("· {list} · at {at}"))
+            );
+        };
+
+        let logged = format_minutes(entry.item.logged().num_minutes());
+        let total = match entry.item.estimate_minutes {
+            Some(estimate) => format!("{logged} of {} logged", format_minutes(estimate.into())),
+            None => format!("{logged} logged"),
+        };
+
+        writeln!(
+            out,
+            "{} {} {}",
+            self.theme.warn("■ Stopped"),
+            text,
+            self.theme.dim(format!(
+                "· {list} · {} · {total}",
+                format_minutes(elapsed.num_minutes())
+            ))
+        )
+    }
+
+    /// One line per group: a bar as long as its share of the largest group,
+    /// filled in as far as its items are done.
+    fn render_count_bars(
+        &self,
+        out: &mut impl Write,
+        groups: &[(String, Counts)],
+    ) -> io::Result<()> {
+        let largest = groups.iter().map(|(_, c)| c.total).max().unwrap_or(0);
+        let label_width = groups
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (label, counts) in groups {
+            let done = Self::bar(counts.done, largest, STATS_BAR_WIDTH);
+            let cells = Self::bar(counts.total, largest, STATS_BAR_WIDTH)
+                .chars()
+                .count();
+            let open = "░".repeat(cells - done.chars().count());
+            let pad = " ".repeat(STATS_BAR_WIDTH - cells);
+
+            writeln!(
+                out,
+                "  {label:<label_width$}  {}{}{pad}  {}/{}  {}",
+                self.theme.ok(done),
+                self.theme.dim(open),
+                counts.done,
+                counts.total,
+                format_rate(counts.rate())
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// A week per line, each day with its heat, number and markers.
+    /// A week of days side by side, each column `width` characters wide.
+    fn render_plan_week(
+        &self,
+        out: &mut impl Write,
+        week: &[Option<&PlanDay>; 7],
+        today: NaiveDate,
+        width: usize,
+    ) -> io::Result<()> {
+        let columns = week
+            .iter()
+            .map(|day| {
+                day.map(|d| self.plan_column(d, today, width))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let height = columns.iter().map(Vec::len).max().unwrap_or_default();
+        let blank = " ".repeat(width);
+
+        for row in 0..height {
+            let line = columns
+                .iter()
+                .map(|c| c.get(row).map_or(blank.as_str(), String::as_str))
+                .collect::<Vec<_>>()
+                .join(PLAN_COLUMN_GAP);
+
+            writeln!(out, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+
+    /// The lines of a day's column, padded before they're coloured so the
+    /// columns line up.
+    fn plan_column(&self, day: &PlanDay, today: NaiveDate, width: usize) -> Vec<String> {
+        let fit = |s: &str| {
+            if s.chars().count() > width {
+                let cut = s.chars().take(width - 1).collect::<String>();
+                format!("{cut}…")
+            } else {
+                format!("{s:<width$}")
+            }
+        };
+
+        let header = day.date().format("%a %d").to_string();
+        let mut lines = if day.date() == today {
+            vec![
+                self.theme
+                    .info(fit(&format!("{header} • today")))
+                    .to_string(),
+            ]
+        } else {
+            vec![self.theme.subtitle(fit(&header)).to_string()]
+        };
+
+        let stats = day.stats();
+        let count = if stats.total == 0 {
+            "no tasks".to_string()
+        } else {
+            format!("{}/{} done", stats.completed, stats.total)
+        };
+        lines.push(self.theme.dim(fit(&count)).to_string());
+
+        for (idx, item) in day.dayfile.items.iter().enumerate() {
+            let is_done = item.done_at.is_some();
+            let line = fit(&format!(
+                "{}. {} {}",
+                idx + 1,
+                self.theme.checkbox(is_done),
+                item.text
+            ));
+
+            if is_done {
+                lines.push(self.theme.dim(line).to_string());
+            } else {
+                lines.push(self.theme.plain(&line).to_string());
+            }
+        }
+
+        lines
+    }
+
+    /// The days one after another, for terminals too narrow for columns. A
+    /// month leaves out the days without tasks.
+    fn render_plan_stacked(&self, out: &mut impl Write, plan: &Plan) -> io::Result<()> {
+        let days = plan.days.iter().filter(|d| {
+            plan.span == PlanSpan::Week || !d.dayfile.items.is_empty() || d.date() == plan.today
+        });
+
+        for (n, day) in days.enumerate() {
+            if n > 0 {
+                writeln!(out)?;
+            }
+
+            let header = day.date().format(&self.formats.date).to_string();
+            let stats = day.stats();
+
+            write!(out, "{}", self.theme.subtitle(&header))?;
+            if day.date() == plan.today {
+                write!(out, " {}", self.theme.info("• today"))?;
+            }
+            writeln!(
+                out,
+                "  {}",
+                self.theme.dim(format!(
+                    "{}: {}/{} done",
+                    day.day, stats.completed, stats.total
+                ))
+            )?;
+
+            if day.dayfile.items.is_empty() {
+                writeln!(out, "{}", self.theme.dim("No tasks"))?;
+            } else {
+                self.render_list(out, &day.dayfile.items)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_month_grid(&self, out: &mut impl Write, calendar: &Calendar) -> io::Result<()> {
+        let header = WEEKDAYS
+            .iter()
+            .map(|d| format!("{d:<6}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(out, "{}", self.theme.dim(header.trim_end()))?;
+
+        for week in calendar.weeks() {
+            let cells = week
+                .iter()
+                .map(|day| match day {
+                    Some(day) => self.calendar_cell(day, calendar.today),
+                    None => " ".repeat(6),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(out, "{}", cells.trim_end())?;
+        }
+
+        Ok(())
+    }
+
+    fn calendar_cell(&self, day: &CalendarDay, today: NaiveDate) -> String {
+        let number = format!("{:>2}", day.date.day());
+        let number = if day.date == today {
+            self.theme.info(number).to_string()
+        } else {
+            number
+        };
+
+        let migrated = if day.open_migrated > 0 {
+            self.theme.warn("↪").to_string()
+        } else {
+            " ".to_string()
+        };
+
+        let due = if day.due > 0 {
+            self.theme.error("!").to_string()
+        } else {
+            " ".to_string()
+        };
+
+        format!("{} {number}{migrated}{due}", self.theme.heat(day.level()))
+    }
+
+    /// A column per week and a row per weekday, like a contribution graph.
+    fn render_year_heatmap(&self, out: &mut impl Write, calendar: &Calendar) -> io::Result<()> {
+        let weeks = calendar.weeks();
+
+        let mut months = vec![' '; weeks.len() * 2];
+        for (col, week) in weeks.iter().enumerate() {
+            let Some(first) = week.iter().flatten().find(|d| d.date.day() == 1) else {
+                continue;
+            };
+
+            for (i, c) in first.date.format("%b").to_string().chars().enumerate() {
+                if let Some(slot) = months.get_mut(col * 2 + i) {
+                    *slot = c;
+                }
+            }
+        }
+
+        let months = months.into_iter().collect::<String>();
+        writeln!(out, "    {}", self.theme.dim(months.trim_end()))?;
+
+        for (row, name) in WEEKDAYS.iter().enumerate() {
+            let label = if row % 2 == 0 { *name } else { "" };
+
+            let cells = weeks
+                .iter()
+                .map(|week| match week[row] {
+                    Some(day) => self.theme.heat(day.level()).to_string(),
+                    None => " ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(
+                out,
+                "{} {}",
+                self.theme.dim(format!("{label:<3}")),
+                cells.trim_end()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Utils
+
+    fn title_underline(theme: &Theme, title: &str, out: &mut impl Write) -> Result<(), Error> {
+        writeln!(out)?;
+        writeln!(out, "{}", theme.title(title))?;
+        let underline = "-".repeat(title.chars().count());
+        writeln!(out, "{underline}")?;
+
+        Ok(())
+    }
+
+    fn title_underline_styled(
+        raw_title: &str,
+        styled_title: &str,
+        out: &mut impl Write,
     ) -> io::Result<()> {
         writeln!(out)?;
         writeln!(out, "{styled_title}")?;
@@ -444,6 +1994,30 @@ impl TerminalRenderer {
         Ok(())
     }
 
+    /// A bar of `width` cells scaled so `max` fills it. Anything above zero
+    /// gets at least one cell.
+    fn bar(value: usize, max: usize, width: usize) -> String {
+        if value == 0 || max == 0 {
+            return String::new();
+        }
+
+        let cells = (value * width).div_ceil(max).clamp(1, width);
+        "█".repeat(cells)
+    }
+
+    /// A sparkline of shares from 0 to 1, with a dot where there's no data.
+    fn sparkline(rates: &[Option<f64>]) -> String {
+        const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        rates
+            .iter()
+            .map(|r| match r {
+                Some(r) => TICKS[((r * 7.0).round() as usize).min(7)],
+                None => '·',
+            })
+            .collect()
+    }
+
     fn abbrev_id(id: &str, len: usize) -> String {
         id.chars().take(len).collect()
     }
@@ -0,0 +1,60 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{display::terminal::DateFormats, view::export::Export};
+
+pub mod csv;
+pub mod html;
+pub mod ics;
+pub mod json;
+pub mod markdown;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[value(alias = "markdown")]
+    Md,
+    Csv,
+    #[value(alias = "ical")]
+    Ics,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Md => "md",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ics => "ics",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn write(
+        &self,
+        out: &mut impl Write,
+        export: &Export,
+        formats: &DateFormats,
+    ) -> io::Result<()> {
+        match self {
+            ExportFormat::Md => markdown::write(out, export, formats),
+            ExportFormat::Csv => csv::write(out, export),
+            ExportFormat::Ics => ics::write(out, export),
+            ExportFormat::Html => html::write(out, export, formats),
+            ExportFormat::Json => json::write(out, export),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
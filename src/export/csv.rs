@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::{models::item::Item, view::export::Export};
+
+const HEADER: &[&str] = &[
+    "list",
+    "date",
+    "index",
+    "id",
+    "text",
+    "status",
+    "priority",
+    "tags",
+    "due",
+    "created_at",
+    "done_at",
+    "migrated_from",
+    "migration_history",
+    "notes",
+];
+
+pub fn write(out: &mut impl Write, export: &Export) -> io::Result<()> {
+    write_row(out, HEADER.iter().map(|h| h.to_string()))?;
+
+    for df in &export.days {
+        for (idx, item) in df.items.iter().enumerate() {
+            write_item(out, "day", Some(df.date), idx, item)?;
+        }
+    }
+
+    if let Some(ff) = &export.focus {
+        for (idx, item) in ff.items.iter().enumerate() {
+            write_item(out, "focus", None, idx, item)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_item(
+    out: &mut impl Write,
+    list: &str,
+    date: Option<NaiveDate>,
+    idx: usize,
+    item: &Item,
+) -> io::Result<()> {
+    let timestamp = |t: Option<DateTime<Utc>>| t.map(|t| t.to_rfc3339()).unwrap_or_default();
+
+    write_row(
+        out,
+        [
+            list.to_string(),
+            date.map(|d| d.to_string()).unwrap_or_default(),
+            (idx + 1).to_string(),
+            item.id.clone(),
+            item.text.clone(),
+            item.status().to_string(),
+            item.priority.to_string(),
+            item.tags.join(" "),
+            timestamp(item.due),
+            timestamp(Some(item.created_at)),
+            timestamp(item.done_at),
+            item.migrated_from
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            item.migration_history
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            item.notes.clone().unwrap_or_default(),
+        ],
+    )?;
+
+    // Subtasks follow their item as rows of their own, indexed `3.1`, `3.2`...
+    for (sub_idx, s) in item.subtasks.iter().enumerate() {
+        write_row(
+            out,
+            [
+                list.to_string(),
+                date.map(|d| d.to_string()).unwrap_or_default(),
+                format!("{}.{}", idx + 1, sub_idx + 1),
+                s.id.clone(),
+                s.text.clone(),
+                s.status().to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                timestamp(Some(s.created_at)),
+                timestamp(s.done_at),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_row(out: &mut impl Write, fields: impl IntoIterator<Item = String>) -> io::Result<()> {
+    let row = fields
+        .into_iter()
+        .map(|f| escape(&f))
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(out, "{row}")
+}
+
+/// Quotes a field if it holds a comma, quote or line break, doubling any quotes.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
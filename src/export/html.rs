@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+
+use chrono::Local;
+
+use crate::{display::terminal::DateFormats, models::item::Item, view::export::Export};
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;color:#222}\
+h2{border-bottom:1px solid #ddd;padding-bottom:.25rem}\
+ul{list-style:none;padding:0}li{margin:.75rem 0}\
+.done .text{text-decoration:line-through;color:#888}\
+.meta{color:#666;font-size:.85rem}.tag{color:#0a6}\
+.high{color:#c00}.medium{color:#b70}.low{color:#888}\
+blockquote{margin:.25rem 0 0 1.5rem;color:#444;white-space:pre-wrap}";
+
+pub fn write(out: &mut impl Write, export: &Export, formats: &DateFormats) -> io::Result<()> {
+    let title = if export.from == export.to {
+        format!("Tasks: {}", export.from.format(&formats.date))
+    } else {
+        format!(
+            "Tasks: {} → {}",
+            export.from.format(&formats.date),
+            export.to.format(&formats.date)
+        )
+    };
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", escape(&title))?;
+    writeln!(out, "<style>{STYLE}</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>{}</h1>", escape(&title))?;
+
+    for df in &export.days {
+        let date = df.date.format(&formats.date).to_string();
+        write_section(out, &date, Some(&df.date.to_string()), &df.items, formats)?;
+    }
+
+    if let Some(ff) = &export.focus {
+        write_section(out, "Focus", None, &ff.items, formats)?;
+    }
+
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")
+}
+
+fn write_section(
+    out: &mut impl Write,
+    heading: &str,
+    id: Option<&str>,
+    items: &[Item],
+    formats: &DateFormats,
+) -> io::Result<()> {
+    match id {
+        Some(id) => writeln!(out, "<section id=\"{}\">", escape(id))?,
+        None => writeln!(out, "<section>")?,
+    }
+
+    writeln!(out, "<h2>{}</h2>", escape(heading))?;
+
+    if items.is_empty() {
+        writeln!(out, "<p class=\"meta\">No tasks.</p>")?;
+    } else {
+        writeln!(out, "<ul>")?;
+
+        for item in items {
+            write_item(out, item, formats)?;
+        }
+
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "</section>")
+}
+
+fn write_item(out: &mut impl Write, item: &Item, formats: &DateFormats) -> io::Result<()> {
+    let done = item.done_at.is_some();
+
+    writeln!(
+        out,
+        "<li class=\"{}\" id=\"{}\">",
+        if done { "done" } else { "open" },
+        escape(&item.id)
+    )?;
+    writeln!(
+        out,
+        "<input type=\"checkbox\" disabled{}> <span class=\"text\">{}</span> <span class=\"{}\">{}</span>",
+        if done { " checked" } else { "" },
+        escape(&item.text),
+        item.priority,
+        item.priority
+    )?;
+
+    let mut meta = vec![format!(
+        "Created {}",
+        item.created_at
+            .with_timezone(&Local)
+            .format(&formats.date_time)
+    )];
+
+    if let Some(due) = item.due {
+        meta.push(format!("Due {}", formats.format_due(&due)));
+    }
+
+    if let Some(done_at) = item.done_at {
+        meta.push(format!(
+            "Done {}",
+            done_at.with_timezone(&Local).format(&formats.date_time)
+        ));
+    }
+
+    if let Some(from) = item.migrated_from {
+        meta.push(format!("Migrated from {}", from.format(&formats.date)));
+    }
+
+    let mut meta: Vec<String> = meta.iter().map(|m| escape(m)).collect();
+
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item
+            .tags
+            .iter()
+            .map(|t| format!("<span class=\"tag\">#{}</span>", escape(t)))
+            .collect();
+        meta.push(tags.join(" "));
+    }
+
+    writeln!(out, "<div class=\"meta\">{}</div>", meta.join(" · "))?;
+
+    if let Some(notes) = item.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        writeln!(out, "<blockquote>{}</blockquote>", escape(notes.trim_end()))?;
+    }
+
+    if !item.subtasks.is_empty() {
+        writeln!(out, "<ul class=\"subtasks\">")?;
+
+        for s in &item.subtasks {
+            let done = s.done_at.is_some();
+            writeln!(
+                out,
+                "<li class=\"{}\"><input type=\"checkbox\" disabled{}> {}</li>",
+                if done { "done" } else { "open" },
+                if done { " checked" } else { "" },
+                escape(&s.text)
+            )?;
+        }
+
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "</li>")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
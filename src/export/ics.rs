@@ -0,0 +1,246 @@
+use std::io::{self, Write};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::{
+    models::item::{Item, ItemPriority},
+    utils::dates::{is_all_day, start_of_day},
+    view::export::Export,
+};
+
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Writes every item as a VTODO, so the export opens in calendar and reminder apps.
+pub fn write(out: &mut impl Write, export: &Export) -> io::Result<()> {
+    let stamp = Utc::now().format(UTC_FORMAT).to_string();
+
+    write_line(out, "BEGIN:VCALENDAR")?;
+    write_line(out, "VERSION:2.0")?;
+    write_line(out, "PRODID:-//tusk//tusk export//EN")?;
+    write_line(out, "CALSCALE:GREGORIAN")?;
+
+    for df in &export.days {
+        for item in &df.items {
+            write_todo(out, item, Some(df.date), &stamp)?;
+        }
+    }
+
+    if let Some(ff) = &export.focus {
+        for item in &ff.items {
+            write_todo(out, item, None, &stamp)?;
+        }
+    }
+
+    write_line(out, "END:VCALENDAR")
+}
+
+fn write_todo(
+    out: &mut impl Write,
+    item: &Item,
+    date: Option<NaiveDate>,
+    stamp: &str,
+) -> io::Result<()> {
+    write_line(out, "BEGIN:VTODO")?;
+    write_line(out, &format!("UID:{}@tusk", item.id))?;
+    write_line(out, &format!("DTSTAMP:{stamp}"))?;
+    write_line(out, &format!("CREATED:{}", utc(&item.created_at)))?;
+    write_line(out, &format!("SUMMARY:{}", escape(&item.text)))?;
+
+    // DUE has to share DTSTART's value type and can't come before it, so a
+    // day's items start at the day's midnight and both are date-times. An
+    // item due before its day, e.g. after a migration, keeps its due instead.
+    let start = date
+        .map(start_of_day)
+        .filter(|s| item.due.is_none_or(|d| d >= *s));
+
+    if let Some(start) = &start {
+        write_line(out, &format!("DTSTART:{}", utc(start)))?;
+    }
+
+    if let Some(due) = &item.due {
+        if is_all_day(due) && start.is_none() {
+            let day = due.with_timezone(&Local).date_naive();
+            write_line(out, &format!("DUE;VALUE=DATE:{}", day.format("%Y%m%d")))?;
+        } else {
+            write_line(out, &format!("DUE:{}", utc(due)))?;
+        }
+    }
+
+    match &item.done_at {
+        Some(done_at) => {
+            write_line(out, "STATUS:COMPLETED")?;
+            write_line(out, &format!("COMPLETED:{}", utc(done_at)))?;
+        }
+        None => write_line(out, "STATUS:NEEDS-ACTION")?,
+    }
+
+    // iCalendar priorities run from 1 (highest) to 9 (lowest).
+    let priority = match item.priority {
+        ItemPriority::High => 1,
+        ItemPriority::Medium => 5,
+        ItemPriority::Low => 9,
+    };
+    write_line(out, &format!("PRIORITY:{priority}"))?;
+
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item.tags.iter().map(|t| escape(t)).collect();
+        write_line(out, &format!("CATEGORIES:{}", tags.join(",")))?;
+    }
+
+    if let Some(notes) = item.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        write_line(out, &format!("DESCRIPTION:{}", escape(notes.trim_end())))?;
+    }
+
+    if let Some(from) = item.migrated_from {
+        write_line(
+            out,
+            &format!("X-TUSK-MIGRATED-FROM;VALUE=DATE:{}", from.format("%Y%m%d")),
+        )?;
+    }
+
+    if !item.migration_history.is_empty() {
+        let history: Vec<String> = item
+            .migration_history
+            .iter()
+            .map(|d| d.format("%Y%m%d").to_string())
+            .collect();
+        write_line(
+            out,
+            &format!("X-TUSK-MIGRATION-HISTORY;VALUE=DATE:{}", history.join(",")),
+        )?;
+    }
+
+    write_line(out, "END:VTODO")
+}
+
+fn utc(t: &DateTime<Utc>) -> String {
+    t.format(UTC_FORMAT).to_string()
+}
+
+/// Escapes the characters iCalendar treats specially in text values.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Writes a content line, folded at 75 bytes as RFC 5545 asks.
+fn write_line(out: &mut impl Write, line: &str) -> io::Result<()> {
+    let mut width = 0;
+
+    for c in line.chars() {
+        let len = c.len_utf8();
+
+        if width + len > 75 {
+            out.write_all(b"\r\n ")?;
+            width = 1;
+        }
+
+        write!(out, "{c}")?;
+        width += len;
+    }
+
+    out.write_all(b"\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::dayfile::DayFile,
+        utils::dates::{end_of_day, ymd},
+    };
+
+    fn todo_lines(item: Item, date: NaiveDate) -> Vec<String> {
+        let mut df = DayFile::new(date);
+        df.items.push(item);
+        let export = Export {
+            from: date,
+            to: date,
+            days: vec![df],
+            focus: None,
+        };
+
+        let mut out = Vec::new();
+        write(&mut out, &export).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .split("\r\n")
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn value<'a>(lines: &'a [String], name: &str) -> Option<&'a str> {
+        lines
+            .iter()
+            .find_map(|l| l.strip_prefix(name)?.strip_prefix(':'))
+    }
+
+    #[test]
+    fn dtstart_and_due_are_both_date_times() {
+        let date = ymd("2020-01-01");
+        let mut item = Item::new("one".into(), ItemPriority::Low, vec![], None);
+        item.due = Some(end_of_day(ymd("2020-01-03")));
+
+        let lines = todo_lines(item, date);
+
+        assert_eq!(
+            value(&lines, "DTSTART"),
+            Some(utc(&start_of_day(date)).as_str())
+        );
+        assert_eq!(
+            value(&lines, "DUE"),
+            Some(utc(&end_of_day(ymd("2020-01-03"))).as_str())
+        );
+        assert!(!lines.iter().any(|l| l.contains("VALUE=DATE:")));
+    }
+
+    #[test]
+    fn due_before_the_day_leaves_out_dtstart() {
+        let date = ymd("2020-01-05");
+        let mut item = Item::new("late".into(), ItemPriority::Low, vec![], None);
+        item.due = Some(end_of_day(ymd("2020-01-03")));
+
+        let lines = todo_lines(item, date);
+
+        assert_eq!(value(&lines, "DTSTART"), None);
+        assert_eq!(value(&lines, "DUE;VALUE=DATE"), Some("20200103"));
+    }
+
+    #[test]
+    fn migrations_are_listed_oldest_first() {
+        let date = ymd("2020-01-03");
+        let mut item = Item::new("moved".into(), ItemPriority::Low, vec![], None);
+        item.record_migration(ymd("2020-01-01"));
+        item.record_migration(ymd("2020-01-02"));
+
+        let lines = todo_lines(item, date);
+
+        assert_eq!(
+            value(&lines, "X-TUSK-MIGRATED-FROM;VALUE=DATE"),
+            Some("20200102")
+        );
+        assert_eq!(
+            value(&lines, "X-TUSK-MIGRATION-HISTORY;VALUE=DATE"),
+            Some("20200101,20200102")
+        );
+    }
+
+    #[test]
+    fn items_without_a_due_start_on_their_day() {
+        let date = ymd("2020-01-01");
+        let item = Item::new("one".into(), ItemPriority::High, vec![], None);
+
+        let lines = todo_lines(item, date);
+
+        assert_eq!(
+            value(&lines, "DTSTART"),
+            Some(utc(&start_of_day(date)).as_str())
+        );
+        assert_eq!(value(&lines, "DUE"), None);
+        assert_eq!(value(&lines, "PRIORITY"), Some("1"));
+        assert_eq!(value(&lines, "STATUS"), Some("NEEDS-ACTION"));
+    }
+}
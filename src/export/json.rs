@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{dayfile::DayFile, focus_file::FocusFile},
+    view::export::Export,
+};
+
+pub const EXPORT_VERSION: u32 = 1;
+
+/// The items exactly as tusk stores them, so an export can be read back in.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportDocument {
+    pub version: u32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<DayFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus: Option<FocusFile>,
+}
+
+pub fn write(out: &mut impl Write, export: &Export) -> io::Result<()> {
+    let document = ExportDocument {
+        version: EXPORT_VERSION,
+        from: export.from,
+        to: export.to,
+        days: export.days.clone(),
+        focus: export.focus.clone(),
+    };
+
+    serde_json::to_writer_pretty(&mut *out, &document)?;
+    writeln!(out)
+}
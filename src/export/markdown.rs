@@ -0,0 +1,98 @@
+use std::io::{self, Write};
+
+use chrono::Local;
+
+use crate::{display::terminal::DateFormats, models::item::Item, view::export::Export};
+
+pub fn write(out: &mut impl Write, export: &Export, formats: &DateFormats) -> io::Result<()> {
+    if export.from == export.to {
+        writeln!(out, "# Tasks: {}", export.from.format(&formats.date))?;
+    } else {
+        writeln!(
+            out,
+            "# Tasks: {} → {}",
+            export.from.format(&formats.date),
+            export.to.format(&formats.date)
+        )?;
+    }
+
+    for df in &export.days {
+        writeln!(out)?;
+        writeln!(out, "## {}", df.date.format(&formats.date))?;
+        write_items(out, &df.items, formats)?;
+    }
+
+    if let Some(ff) = &export.focus {
+        writeln!(out)?;
+        writeln!(out, "## Focus")?;
+        write_items(out, &ff.items, formats)?;
+    }
+
+    Ok(())
+}
+
+fn write_items(out: &mut impl Write, items: &[Item], formats: &DateFormats) -> io::Result<()> {
+    writeln!(out)?;
+
+    if items.is_empty() {
+        writeln!(out, "_No tasks._")?;
+        return Ok(());
+    }
+
+    for item in items {
+        let checkbox = if item.done_at.is_some() {
+            "- [x]"
+        } else {
+            "- [ ]"
+        };
+        write!(out, "{checkbox} {} · {}", item.text, item.priority)?;
+
+        if let Some(due) = item.due {
+            write!(out, " · due {}", formats.format_due(&due))?;
+        }
+
+        writeln!(out)?;
+
+        let mut meta = vec![format!(
+            "Created {}",
+            item.created_at
+                .with_timezone(&Local)
+                .format(&formats.date_time)
+        )];
+
+        if let Some(done_at) = item.done_at {
+            meta.push(format!(
+                "Done {}",
+                done_at.with_timezone(&Local).format(&formats.date_time)
+            ));
+        }
+
+        if let Some(from) = item.migrated_from {
+            meta.push(format!("Migrated from {}", from.format(&formats.date)));
+        }
+
+        if !item.tags.is_empty() {
+            let tags: Vec<String> = item.tags.iter().map(|t| format!("#{t}")).collect();
+            meta.push(format!("Tags {}", tags.join(" ")));
+        }
+
+        writeln!(out, "  - {}", meta.join(" · "))?;
+
+        if let Some(notes) = item.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            for line in notes.trim_end().lines() {
+                writeln!(out, "  > {line}")?;
+            }
+        }
+
+        for s in &item.subtasks {
+            let checkbox = if s.done_at.is_some() {
+                "- [x]"
+            } else {
+                "- [ ]"
+            };
+            writeln!(out, "  {checkbox} {}", s.text)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use chrono::{Local, NaiveDate};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    display::terminal::DateFormats,
+    models::item::{Item, ItemPriority},
+    utils::{dates::start_of_day, helpers::sanitise_str},
+};
+
+pub mod markdown;
+pub mod taskwarrior;
+pub mod todotxt;
+pub mod tusk_json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportFormat {
+    #[value(alias = "todo.txt")]
+    Todotxt,
+    Taskwarrior,
+    #[value(alias = "md")]
+    Markdown,
+    TuskJson,
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ImportFormat::Todotxt => "todotxt",
+            ImportFormat::Taskwarrior => "taskwarrior",
+            ImportFormat::Markdown => "markdown",
+            ImportFormat::TuskJson => "tusk-json",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Where an imported item goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Target {
+    Day(NaiveDate),
+    Focus,
+}
+
+/// Why an entry isn't imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// Left out on purpose, like a task deleted in the other app.
+    Skipped(String),
+    /// Invalid, like a task without text or with a date that can't be read.
+    Rejected(String),
+}
+
+/// One entry read from the source file, ready to import or with the reason it can't be.
+pub struct Parsed {
+    /// The entry's line in the file, or its position in a JSON list.
+    pub entry: usize,
+    pub text: String,
+    pub result: Result<(Target, Item), Refusal>,
+}
+
+impl Parsed {
+    pub fn skipped(entry: usize, text: &str, reason: impl Into<String>) -> Self {
+        Self {
+            entry,
+            text: text.to_string(),
+            result: Err(Refusal::Skipped(reason.into())),
+        }
+    }
+
+    pub fn rejected(entry: usize, text: &str, reason: impl Into<String>) -> Self {
+        Self {
+            entry,
+            text: text.to_string(),
+            result: Err(Refusal::Rejected(reason.into())),
+        }
+    }
+}
+
+/// What the parsers fall back to for details the source doesn't have.
+pub struct ImportDefaults {
+    pub today: NaiveDate,
+    pub priority: ItemPriority,
+    pub formats: DateFormats,
+}
+
+impl ImportFormat {
+    pub fn parse(&self, input: &str, defaults: &ImportDefaults) -> Result<Vec<Parsed>, String> {
+        match self {
+            ImportFormat::Todotxt => Ok(todotxt::parse(input, defaults)),
+            ImportFormat::Taskwarrior => taskwarrior::parse(input, defaults),
+            ImportFormat::Markdown => Ok(markdown::parse(input, defaults)),
+            ImportFormat::TuskJson => tusk_json::parse(input),
+        }
+    }
+}
+
+/// Files an item under the day it was created, or else the day it's due.
+pub fn day_for(item: &Item, created: Option<NaiveDate>, defaults: &ImportDefaults) -> Target {
+    let date = created
+        .or_else(|| item.due.map(|due| due.with_timezone(&Local).date_naive()))
+        .unwrap_or(defaults.today);
+
+    Target::Day(date)
+}
+
+/// Builds an item with a fresh id and the given creation date, if known.
+/// The text is checked like text typed into `tusk add`.
+pub fn new_item(
+    text: &str,
+    priority: ItemPriority,
+    created: Option<NaiveDate>,
+) -> Result<Item, String> {
+    let mut item = Item::new(String::new(), priority, Vec::new(), None);
+    item.set_text(imported_text(text)?);
+
+    if let Some(date) = created {
+        item.created_at = start_of_day(date);
+    }
+
+    Ok(item)
+}
+
+/// Imported text, trimmed, or the reason it's rejected.
+pub fn imported_text(text: &str) -> Result<String, String> {
+    sanitise_str(text).map_err(|_| "no task text".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dates::ymd;
+
+    /// What the importers' tests parse with: today is 2025-09-10.
+    pub fn defaults() -> ImportDefaults {
+        ImportDefaults {
+            today: ymd("2025-09-10"),
+            priority: ItemPriority::Medium,
+            formats: DateFormats::default(),
+        }
+    }
+
+    #[test]
+    fn new_items_have_their_text_checked() {
+        let created = ymd("2025-09-01");
+        let item = new_item("  Call mum #family  ", ItemPriority::High, Some(created)).unwrap();
+
+        assert_eq!(item.text, "Call mum #family");
+        assert_eq!(item.tags, ["family"]);
+        assert_eq!(item.created_at, start_of_day(created));
+        assert_eq!(
+            new_item(" \t ", ItemPriority::Low, None).unwrap_err(),
+            "no task text"
+        );
+    }
+
+    #[test]
+    fn items_go_under_the_day_they_were_created() {
+        let defaults = defaults();
+        let created = ymd("2025-09-01");
+        let item = new_item("a", ItemPriority::Low, Some(created)).unwrap();
+
+        assert_eq!(
+            day_for(&item, Some(created), &defaults),
+            Target::Day(created)
+        );
+        assert_eq!(day_for(&item, None, &defaults), Target::Day(defaults.today));
+    }
+}
@@ -0,0 +1,309 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use crate::{
+    import::{ImportDefaults, Parsed, Target, day_for, imported_text, new_item},
+    models::item::{Item, ItemPriority, Subtask},
+    utils::dates::{end_of_day, parse_due},
+};
+
+/// Reads `- [ ]` / `- [x]` checklists. A heading holding a date files the
+/// tasks under it on that day, and a `Focus` heading makes them focus tasks.
+/// The details `tusk export --format md` writes under each task are read back
+/// too, and indented checkboxes become subtasks.
+pub fn parse(input: &str, defaults: &ImportDefaults) -> Vec<Parsed> {
+    let mut parsed: Vec<Parsed> = Vec::new();
+    let mut section: Option<Target> = None;
+    // Whether the last line was a task we've taken, so the lines under it belong to it.
+    let mut open = false;
+
+    for (idx, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            section = heading_target(heading.trim_start_matches('#').trim(), defaults);
+            open = false;
+            continue;
+        }
+
+        let indented = line.starts_with(char::is_whitespace);
+
+        // An indented checkbox under a task is one of its subtasks.
+        if let Some((done, rest)) = checkbox(trimmed).filter(|_| open && indented) {
+            if let Some(Ok((_, item))) = parsed.last_mut().map(|p| p.result.as_mut())
+                && let Ok(text) = imported_text(rest)
+            {
+                let mut subtask = Subtask::new(text);
+                subtask.done_at = done.then(|| item.done_at.unwrap_or_else(Utc::now));
+                item.subtasks.push(subtask);
+            }
+            continue;
+        }
+
+        if let Some((done, rest)) = checkbox(trimmed) {
+            parsed.push(parse_task(idx + 1, done, rest, section, defaults));
+            open = parsed.last().is_some_and(|p| p.result.is_ok());
+            continue;
+        }
+
+        if !open || !indented {
+            open = false;
+            continue;
+        }
+
+        let Some(Ok((_, item))) = parsed.last_mut().map(|p| p.result.as_mut()) else {
+            continue;
+        };
+
+        if let Some(note) = trimmed.strip_prefix('>') {
+            let note = note.strip_prefix(' ').unwrap_or(note);
+            let notes = item.notes.get_or_insert_with(String::new);
+
+            if !notes.is_empty() {
+                notes.push('\n');
+            }
+            notes.push_str(note);
+        } else if let Some(meta) = trimmed.strip_prefix("- ") {
+            apply_meta(item, meta, defaults);
+        }
+    }
+
+    parsed
+}
+
+fn heading_target(heading: &str, defaults: &ImportDefaults) -> Option<Target> {
+    let heading = heading.strip_prefix("Tasks:").unwrap_or(heading).trim();
+
+    if heading.eq_ignore_ascii_case("focus") {
+        return Some(Target::Focus);
+    }
+
+    parse_date(heading, defaults).map(Target::Day)
+}
+
+fn checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))?
+        .trim_start();
+
+    if let Some(rest) = rest.strip_prefix("[ ]") {
+        Some((false, rest.trim()))
+    } else if let Some(rest) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        Some((true, rest.trim()))
+    } else {
+        None
+    }
+}
+
+/// Tasks can carry ` · priority` and ` · due <date>` after the text, as tusk writes them.
+fn parse_task(
+    entry: usize,
+    done: bool,
+    rest: &str,
+    section: Option<Target>,
+    defaults: &ImportDefaults,
+) -> Parsed {
+    let mut parts = rest.split(" · ");
+    let text = parts.next().unwrap_or_default().trim();
+    let mut priority = defaults.priority;
+    let mut due = None;
+
+    for part in parts {
+        let part = part.trim();
+        let part = part.strip_suffix("**overdue**").unwrap_or(part).trim();
+
+        if let Some(value) = part.strip_prefix("due ") {
+            match parse_due_value(value, defaults) {
+                Some(d) => due = Some(d),
+                None => {
+                    return Parsed::rejected(entry, text, format!("invalid due date '{value}'"));
+                }
+            }
+        } else if let Some(p) = priority_word(part) {
+            priority = p;
+        }
+    }
+
+    let mut item = match new_item(text, priority, None) {
+        Ok(item) => item,
+        Err(reason) => return Parsed::rejected(entry, rest, reason),
+    };
+    item.due = due;
+    item.done_at = done.then(Utc::now);
+
+    let target = section.unwrap_or_else(|| day_for(&item, None, defaults));
+
+    Parsed {
+        entry,
+        text: text.to_string(),
+        result: Ok((target, item)),
+    }
+}
+
+/// Reads the `Created … · Done … · Tags #a #b` line tusk writes under a task.
+fn apply_meta(item: &mut Item, meta: &str, defaults: &ImportDefaults) {
+    for part in meta.split(" · ") {
+        let part = part.trim();
+
+        if let Some(value) = part.strip_prefix("Created ") {
+            if let Some(t) = parse_date_time(value, defaults) {
+                item.created_at = t;
+            }
+        } else if let Some(value) = part.strip_prefix("Done ") {
+            if let Some(t) = parse_date_time(value, defaults) {
+                item.done_at = Some(t);
+            }
+        } else if let Some(value) = part.strip_prefix("Migrated from ") {
+            item.migrated_from = parse_date(value, defaults);
+        } else if let Some(value) = part.strip_prefix("Tags ") {
+            for tag in value.split_whitespace().filter_map(|t| t.strip_prefix('#')) {
+                if !item.tags.iter().any(|t| t == tag) {
+                    item.tags.push(tag.to_string());
+                }
+            }
+        }
+    }
+}
+
+fn priority_word(s: &str) -> Option<ItemPriority> {
+    match s.to_ascii_lowercase().as_str() {
+        "high" => Some(ItemPriority::High),
+        "medium" => Some(ItemPriority::Medium),
+        "low" => Some(ItemPriority::Low),
+        _ => None,
+    }
+}
+
+fn parse_date(s: &str, defaults: &ImportDefaults) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, &defaults.formats.date))
+        .ok()
+}
+
+fn parse_date_time(s: &str, defaults: &ImportDefaults) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, &defaults.formats.date_time))
+        .ok()
+        .and_then(local)
+}
+
+/// Dues are written without a year by default, so those are read as this year's.
+fn parse_due_value(s: &str, defaults: &ImportDefaults) -> Option<DateTime<Utc>> {
+    if let Some(date) = parse_date(s, defaults) {
+        return Some(end_of_day(date));
+    }
+
+    let with_year = format!("{s} {}", defaults.today.year());
+
+    if let Ok(date) = NaiveDate::parse_from_str(&with_year, &format!("{} %Y", defaults.formats.due))
+    {
+        return Some(end_of_day(date));
+    }
+
+    NaiveDateTime::parse_from_str(&with_year, &format!("{} %Y", defaults.formats.due_time))
+        .ok()
+        .and_then(local)
+        .or_else(|| parse_due(s).ok())
+}
+
+fn local(naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        import::{Refusal, tests::defaults},
+        utils::dates::ymd,
+    };
+
+    const INPUT: &str = "\
+# Tasks: 2025-09-01
+
+- [ ] Write report · high · due 2025-09-05
+  - Created 2025-09-01 09:00 · Tags #work
+  > draft in docs
+  > send to Sam
+  - [x] outline
+  - [ ]
+  - [ ] intro
+- [X] Post letter
+-  [ ]
+- [ ] Call back · due someday
+
+## Focus
+
+* [ ] Learn Rust
+";
+
+    #[test]
+    fn reads_tasks_with_their_details() {
+        let parsed = parse(INPUT, &defaults());
+        let (target, item) = parsed[0].result.as_ref().unwrap();
+
+        assert_eq!(*target, Target::Day(ymd("2025-09-01")));
+        assert_eq!(item.text, "Write report");
+        assert_eq!(item.priority, ItemPriority::High);
+        assert_eq!(item.due, Some(end_of_day(ymd("2025-09-05"))));
+        assert_eq!(item.tags, ["work"]);
+        assert_eq!(
+            item.created_at.with_timezone(&Local).date_naive(),
+            ymd("2025-09-01")
+        );
+        assert_eq!(item.notes.as_deref(), Some("draft in docs\nsend to Sam"));
+
+        // Blank subtasks are left out.
+        let subtasks = item
+            .subtasks
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(subtasks, ["outline", "intro"]);
+        assert!(item.subtasks[0].done_at.is_some());
+
+        let (_, posted) = parsed[1].result.as_ref().unwrap();
+        assert!(posted.done_at.is_some());
+        assert_eq!(posted.priority, ItemPriority::Medium);
+
+        let (target, learn) = parsed[4].result.as_ref().unwrap();
+        assert_eq!(*target, Target::Focus);
+        assert_eq!(learn.text, "Learn Rust");
+    }
+
+    #[test]
+    fn rejects_tasks_it_cant_read() {
+        let parsed = parse(INPUT, &defaults());
+
+        assert_eq!(parsed.len(), 5);
+        assert_eq!(parsed[2].entry, 11);
+        assert_eq!(
+            parsed[2].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("no task text".into())
+        );
+        assert_eq!(
+            parsed[3].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("invalid due date 'someday'".into())
+        );
+    }
+
+    #[test]
+    fn tasks_outside_a_dated_heading_go_by_their_due_date() {
+        let parsed = parse("# Notes\n- [ ] a\n- [ ] b · due 2025-09-12\n", &defaults());
+
+        assert_eq!(
+            parsed[0].result.as_ref().unwrap().0,
+            Target::Day(defaults().today)
+        );
+        assert_eq!(
+            parsed[1].result.as_ref().unwrap().0,
+            Target::Day(ymd("2025-09-12"))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    import::{ImportDefaults, Parsed, day_for, new_item},
+    models::item::ItemPriority,
+};
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Deserialize)]
+struct Task {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    status: String,
+    entry: Option<String>,
+    end: Option<String>,
+    due: Option<String>,
+    priority: Option<String>,
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Annotation {
+    description: String,
+}
+
+/// Reads the output of `task export`: a JSON array, or one task object per line.
+pub fn parse(input: &str, defaults: &ImportDefaults) -> Result<Vec<Parsed>, String> {
+    let tasks: Vec<serde_json::Value> = if input.trim_start().starts_with('[') {
+        serde_json::from_str(input).map_err(|e| format!("Invalid Taskwarrior export: {e}"))?
+    } else {
+        input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Invalid Taskwarrior export: {e}"))?
+    };
+
+    Ok(tasks
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| parse_task(idx + 1, value, defaults))
+        .collect())
+}
+
+fn parse_task(entry: usize, value: serde_json::Value, defaults: &ImportDefaults) -> Parsed {
+    let task: Task = match serde_json::from_value(value) {
+        Ok(t) => t,
+        Err(e) => return Parsed::rejected(entry, "", format!("unreadable task: {e}")),
+    };
+
+    let text = task.description.trim();
+    let skip = |reason: &str| Parsed::skipped(entry, text, reason);
+
+    match task.status.as_str() {
+        "deleted" => return skip("deleted in Taskwarrior"),
+        // The template of a recurring task; its instances are exported separately.
+        "recurring" => return skip("recurring template"),
+        _ => {}
+    }
+
+    let (created, end, due) = match (
+        timestamp(task.entry.as_deref()),
+        timestamp(task.end.as_deref()),
+        timestamp(task.due.as_deref()),
+    ) {
+        (Ok(c), Ok(e), Ok(d)) => (c, e, d),
+        (Err(bad), _, _) | (_, Err(bad), _) | (_, _, Err(bad)) => {
+            return Parsed::rejected(entry, text, format!("invalid date '{bad}'"));
+        }
+    };
+
+    let priority = match task.priority.as_deref() {
+        Some("H") => ItemPriority::High,
+        Some("M") => ItemPriority::Medium,
+        Some("L") => ItemPriority::Low,
+        _ => defaults.priority,
+    };
+
+    let created_date = created.map(|c| c.with_timezone(&Local).date_naive());
+    let mut item = match new_item(text, priority, created_date) {
+        Ok(item) => item,
+        Err(reason) => return Parsed::rejected(entry, text, reason),
+    };
+
+    if let Some(created) = created {
+        item.created_at = created;
+    }
+
+    for tag in task.project.into_iter().chain(task.tags) {
+        if !item.tags.contains(&tag) {
+            item.tags.push(tag);
+        }
+    }
+
+    item.due = due;
+    item.done_at = match task.status.as_str() {
+        "completed" => Some(end.unwrap_or(item.created_at)),
+        _ => None,
+    };
+
+    if !task.annotations.is_empty() {
+        let notes: Vec<&str> = task
+            .annotations
+            .iter()
+            .map(|a| a.description.as_str())
+            .collect();
+        item.notes = Some(notes.join("\n"));
+    }
+
+    Parsed {
+        entry,
+        text: text.to_string(),
+        result: Ok((day_for(&item, created_date, defaults), item)),
+    }
+}
+
+fn timestamp(s: Option<&str>) -> Result<Option<DateTime<Utc>>, String> {
+    s.map(|s| {
+        NaiveDateTime::parse_from_str(s, DATE_FORMAT)
+            .map(|dt| dt.and_utc())
+            .map_err(|_| s.to_string())
+    })
+    .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::{Refusal, Target, tests::defaults};
+
+    #[test]
+    fn reads_an_export() {
+        let input = r#"[
+            {"description": " Write report ", "status": "completed", "entry": "20250901T080000Z",
+             "end": "20250902T170000Z", "priority": "H", "project": "work", "tags": ["q3", "work"],
+             "annotations": [{"description": "draft in docs"}, {"description": "send to Sam"}]},
+            {"description": "Walk dog", "status": "pending", "due": "20250915T120000Z"}
+        ]"#;
+
+        let parsed = parse(input, &defaults()).unwrap();
+
+        let (_, report) = parsed[0].result.as_ref().unwrap();
+        assert_eq!(report.text, "Write report");
+        assert_eq!(report.priority, ItemPriority::High);
+        assert_eq!(report.tags, ["work", "q3"]);
+        assert_eq!(
+            report.done_at.unwrap().to_rfc3339(),
+            "2025-09-02T17:00:00+00:00"
+        );
+        assert_eq!(report.notes.as_deref(), Some("draft in docs\nsend to Sam"));
+
+        let (target, walk) = parsed[1].result.as_ref().unwrap();
+        assert_eq!(walk.priority, ItemPriority::Medium);
+        assert_eq!(walk.done_at, None);
+        let due = walk.due.unwrap();
+        assert_eq!(*target, Target::Day(due.with_timezone(&Local).date_naive()));
+    }
+
+    #[test]
+    fn reads_one_task_per_line() {
+        let input = "{\"description\": \"a\"}\n\n{\"description\": \"b\"}\n";
+        assert_eq!(parse(input, &defaults()).unwrap().len(), 2);
+        assert!(parse("[{]", &defaults()).is_err());
+    }
+
+    #[test]
+    fn skips_and_rejects_tasks() {
+        let input = r#"[
+            {"description": "gone", "status": "deleted"},
+            {"description": "template", "status": "recurring"},
+            {"description": "   ", "status": "pending"},
+            {"description": "late", "due": "next week"},
+            {"description": 42}
+        ]"#;
+
+        let refusals = parse(input, &defaults())
+            .unwrap()
+            .into_iter()
+            .map(|p| p.result.unwrap_err())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            refusals[0],
+            Refusal::Skipped("deleted in Taskwarrior".into())
+        );
+        assert_eq!(refusals[1], Refusal::Skipped("recurring template".into()));
+        assert_eq!(refusals[2], Refusal::Rejected("no task text".into()));
+        assert_eq!(
+            refusals[3],
+            Refusal::Rejected("invalid date 'next week'".into())
+        );
+        assert!(matches!(refusals[4], Refusal::Rejected(_)));
+    }
+}
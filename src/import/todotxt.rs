@@ -0,0 +1,184 @@
+use chrono::NaiveDate;
+
+use crate::{
+    import::{ImportDefaults, Parsed, day_for, new_item},
+    models::item::ItemPriority,
+    utils::dates::{end_of_day, start_of_day},
+};
+
+/// Reads one task per line in the todo.txt format:
+/// `x 2025-01-02 2025-01-01 (A) Call mum +family @phone due:2025-01-05`.
+pub fn parse(input: &str, defaults: &ImportDefaults) -> Vec<Parsed> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse_line(idx + 1, line.trim(), defaults))
+        .collect()
+}
+
+fn parse_line(entry: usize, line: &str, defaults: &ImportDefaults) -> Parsed {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let mut done = None;
+    let mut priority = None;
+
+    if words.first() == Some(&"x") {
+        words.remove(0);
+        done = Some(words.first().and_then(|w| date(w)));
+
+        if done.flatten().is_some() {
+            words.remove(0);
+        }
+    }
+
+    if let Some(p) = words.first().and_then(|w| letter_priority(w)) {
+        priority = Some(p);
+        words.remove(0);
+    }
+
+    let created = words.first().and_then(|w| date(w));
+    if created.is_some() {
+        words.remove(0);
+    }
+
+    let mut due = None;
+    let mut text = Vec::new();
+
+    for word in words {
+        match word.split_once(':') {
+            Some(("due", value)) => match date(value) {
+                Some(d) => due = Some(d),
+                None => {
+                    return Parsed::rejected(entry, line, format!("invalid due date '{value}'"));
+                }
+            },
+            // Completed tasks keep their priority as `pri:A`.
+            Some(("pri", value)) if priority.is_none() => {
+                priority = letter_priority(&format!("({value})"));
+            }
+            Some((key, value)) if is_meta(key, value) => {}
+            _ => text.push(word),
+        }
+    }
+
+    let text = text.join(" ");
+    let mut item = match new_item(&text, priority.unwrap_or(defaults.priority), created) {
+        Ok(item) => item,
+        Err(reason) => return Parsed::rejected(entry, line, reason),
+    };
+    item.tags = tags(&text);
+    item.due = due.map(end_of_day);
+    item.done_at = done.map(|d| d.map_or(item.created_at, start_of_day));
+
+    Parsed {
+        entry,
+        text: text.clone(),
+        result: Ok((day_for(&item, created, defaults), item)),
+    }
+}
+
+fn date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+}
+
+/// `(A)` is high, `(B)` medium and anything further down the alphabet low.
+fn letter_priority(s: &str) -> Option<ItemPriority> {
+    let letter = s.strip_prefix('(')?.strip_suffix(')')?;
+
+    match letter {
+        "A" => Some(ItemPriority::High),
+        "B" => Some(ItemPriority::Medium),
+        l if l.len() == 1 && l.chars().all(|c| c.is_ascii_uppercase()) => Some(ItemPriority::Low),
+        _ => None,
+    }
+}
+
+/// `key:value` extensions such as `t:` or `rec:` are dropped, but words that
+/// merely contain a colon, like a URL or a time, stay in the text.
+fn is_meta(key: &str, value: &str) -> bool {
+    !key.is_empty()
+        && !value.is_empty()
+        && !value.starts_with('/')
+        && key.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Projects, contexts and hashtags all become tags.
+fn tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        let tag = ["+", "@", "#"]
+            .iter()
+            .find_map(|p| word.strip_prefix(p))
+            .filter(|t| !t.is_empty());
+
+        if let Some(tag) = tag
+            && !tags.iter().any(|t| t == tag)
+        {
+            tags.push(tag.to_string());
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        import::{Refusal, Target, tests::defaults},
+        utils::dates::ymd,
+    };
+
+    #[test]
+    fn reads_a_full_line() {
+        let parsed = parse(
+            "x 2025-01-02 2025-01-01 Call mum +family @phone due:2025-01-05 https://x.io pri:A",
+            &defaults(),
+        );
+
+        let (target, item) = parsed[0].result.as_ref().unwrap();
+        assert_eq!(*target, Target::Day(ymd("2025-01-01")));
+        assert_eq!(item.text, "Call mum +family @phone https://x.io");
+        assert_eq!(item.priority, ItemPriority::High);
+        assert_eq!(item.tags, ["family", "phone"]);
+        assert_eq!(item.due, Some(end_of_day(ymd("2025-01-05"))));
+        assert_eq!(item.done_at, Some(start_of_day(ymd("2025-01-02"))));
+    }
+
+    #[test]
+    fn falls_back_to_the_defaults() {
+        let input = "\n  Buy milk  \n(C) later pri:A t:2025-01-03\nx Pay rent pri:A\n";
+        let parsed = parse(input, &defaults());
+
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].entry, 2);
+
+        let (target, item) = parsed[0].result.as_ref().unwrap();
+        assert_eq!(*target, Target::Day(defaults().today));
+        assert_eq!(item.priority, ItemPriority::Medium);
+        assert_eq!(item.done_at, None);
+
+        // `pri:` only stands in for a priority the line doesn't have.
+        let (_, item) = parsed[1].result.as_ref().unwrap();
+        assert_eq!(item.priority, ItemPriority::Low);
+
+        let (_, item) = parsed[2].result.as_ref().unwrap();
+        assert_eq!(item.priority, ItemPriority::High);
+        assert!(item.done_at.is_some());
+    }
+
+    #[test]
+    fn rejects_lines_it_cant_read() {
+        let parsed = parse("x 2025-01-02\nfix bug due:soon\n", &defaults());
+
+        assert_eq!(
+            parsed[0].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("no task text".into())
+        );
+        assert_eq!(
+            parsed[1].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("invalid due date 'soon'".into())
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use nanoid::nanoid;
+
+use crate::{
+    export::json::ExportDocument,
+    import::{Parsed, Target, imported_text},
+};
+
+/// Reads a `tusk export --format json` document back in. Items get fresh ids
+/// and lose their recurrence links, since both belong to the vault they came from.
+/// Their text is checked like anything else imported, as the file may have
+/// been edited by hand.
+pub fn parse(input: &str) -> Result<Vec<Parsed>, String> {
+    let document: ExportDocument =
+        serde_json::from_str(input).map_err(|e| format!("Invalid tusk export: {e}"))?;
+
+    let days = document.days.into_iter().flat_map(|df| {
+        df.items
+            .into_iter()
+            .map(move |item| (Target::Day(df.date), item))
+    });
+    let focus = document
+        .focus
+        .into_iter()
+        .flat_map(|ff| ff.items.into_iter().map(|item| (Target::Focus, item)));
+
+    Ok(days
+        .chain(focus)
+        .enumerate()
+        .map(|(idx, (target, mut item))| {
+            let entry = idx + 1;
+
+            let text = match imported_text(&item.text) {
+                Ok(text) => text,
+                Err(reason) => return Parsed::rejected(entry, &item.text, reason),
+            };
+
+            for subtask in &mut item.subtasks {
+                match imported_text(&subtask.text) {
+                    Ok(text) => subtask.text = text,
+                    Err(_) => return Parsed::rejected(entry, &text, "a subtask has no text"),
+                }
+            }
+
+            item.set_text(text);
+            item.id = nanoid!(6);
+            item.recurrence_id = None;
+
+            Parsed {
+                entry,
+                text: item.text.clone(),
+                result: Ok((target, item)),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        export,
+        import::Refusal,
+        models::{
+            dayfile::DayFile,
+            focus_file::FocusFile,
+            item::{Item, ItemPriority, Subtask},
+        },
+        utils::dates::ymd,
+        view::export::Export,
+    };
+
+    fn round_trip(days: Vec<DayFile>, focus: Option<FocusFile>) -> Vec<Parsed> {
+        let date = ymd("2025-09-01");
+        let export = Export {
+            from: date,
+            to: date,
+            days,
+            focus,
+        };
+
+        let mut out = Vec::new();
+        export::json::write(&mut out, &export).unwrap();
+        parse(&String::from_utf8(out).unwrap()).unwrap()
+    }
+
+    fn item(text: &str) -> Item {
+        Item::new(text.to_string(), ItemPriority::High, vec![], None)
+    }
+
+    #[test]
+    fn reads_an_export_back_with_new_ids() {
+        let mut df = DayFile::new(ymd("2025-09-01"));
+        let mut report = item("Write report");
+        report.recurrence_id = Some("rule1".into());
+        report.subtasks.push(Subtask::new("outline".into()));
+        df.items.push(report.clone());
+
+        let mut ff = FocusFile::new();
+        ff.items.push(item("Learn Rust"));
+
+        let parsed = round_trip(vec![df.clone()], Some(ff));
+        assert_eq!(parsed.len(), 2);
+
+        let (target, read) = parsed[0].result.as_ref().unwrap();
+        assert_eq!(*target, Target::Day(df.date));
+        assert_eq!(read.text, report.text);
+        assert_eq!(read.subtasks.len(), 1);
+        assert_ne!(read.id, report.id);
+        assert_eq!(read.recurrence_id, None);
+
+        assert_eq!(parsed[1].result.as_ref().unwrap().0, Target::Focus);
+    }
+
+    #[test]
+    fn checks_hand_edited_text() {
+        let mut df = DayFile::new(ymd("2025-09-01"));
+        df.items.push(item("  Tidy #home  "));
+        df.items.push(item("   "));
+        let mut blank_subtask = item("Pack");
+        blank_subtask.subtasks.push(Subtask::new(" ".into()));
+        df.items.push(blank_subtask);
+
+        let parsed = round_trip(vec![df], None);
+
+        let (_, tidy) = parsed[0].result.as_ref().unwrap();
+        assert_eq!(tidy.text, "Tidy #home");
+        assert_eq!(tidy.tags, ["home"]);
+        assert_eq!(
+            parsed[1].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("no task text".into())
+        );
+        assert_eq!(
+            parsed[2].result.as_ref().unwrap_err(),
+            &Refusal::Rejected("a subtask has no text".into())
+        );
+        assert!(parse("{}").is_err());
+    }
+}
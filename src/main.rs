@@ -1,41 +1,114 @@
-use std::io::{self};
+use std::{
+    collections::{BTreeMap, BTreeSet, btree_map::Entry},
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
-use chrono::{Days, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
 use clap::Parser;
 
 use crate::{
-    cli::command::{Cli, CommandContext, Commands, FocusCommands},
+    cli::command::{
+        Cli, CommandContext, Commands, ConfigCommands, EditArgs, FocusCommands, OperationContext,
+        RecurCommands, StoreCommands, VaultCommands, load_settings,
+    },
+    display::terminal::DateFormats,
+    export::ExportFormat,
+    import::{ImportDefaults, ImportFormat, Refusal, Target},
     models::{
         dayfile::DayFile,
-        item::{Item, ItemPriority},
+        focus_file::FocusFile,
+        item::{DUE_SOON_DAYS, Item, ItemPriority, ListMove, STALE_MIGRATIONS, Subtask},
+        journal::OperationKind,
+        recurrence::{RecurrenceRule, Schedule},
+        settings::{MAX_REVIEW_DAYS, Settings},
+        vault_config::{ActiveTimer, StoreBackend},
+    },
+    store::{
+        fs::{settings_store::FsSettingsStore, shared::vault_name},
+        read_only::refused,
+        settings_store::SettingsStore,
+        vault_registry::VaultRegistry,
     },
     utils::{
+        alias::expand_aliases,
         dates::todays_date,
+        day_ref::{parse_day_pick_list, resolve_day},
         editor::edit_in_editor,
-        files::{load_day_or_empty, load_focus_or_empty, save_dayfile, save_focusfile},
-        helpers::{extract_tags, sanitise_str, validate_index, warn_dayfile_error},
+        files::{
+            file_state, list_day_dates, load_day_or_empty, load_focus_or_empty, load_journal,
+            load_recurring_or_empty, load_vault_config, migrate_store, restore_file, save_dayfile,
+            save_focusfile, save_recurring, save_vault_config, stop_timer_on,
+        },
+        helpers::{extract_tags, sanitise_str, warn_dayfile_error},
         list_scope::ListScope,
-        render::{ActionKind, make_renderer},
+        query::Query,
+        render::{
+            ActionKind, ConfigAction, HistoryAction, RenderOpts, RuleAction, VaultAction,
+            make_renderer,
+        },
+        selection::{Selection, parse_pick_list, picks_then_text},
+        stats_range::StatsRange,
+        task_ref::TaskRef,
         task_target::TaskTarget,
         tusk_error::TuskError,
     },
-    view::agenda::Agenda,
+    view::{
+        agenda::Agenda,
+        batch::{Batch, BatchEntry},
+        calendar::{Calendar, CalendarSpan},
+        config::{ConfigEntry, ConfigSource},
+        due::DueReport,
+        export::{Export, ExportReport},
+        history::{History, HistoryEntry},
+        import::{ImportEntry, ImportOutcome, ImportReport},
+        migration::{Migration, MigrationChoice},
+        plan::{Plan, PlanSpan},
+        pomodoro::{PomodoroPlan, PomodoroRun},
+        rollover::Rollover,
+        search::SearchResults,
+        stale::{StaleAction, StaleReport},
+        stats::StatsReport,
+        time_report::TimeReport,
+        timer::{TimerChange, TimerEntry},
+        vault::VaultSummary,
+    },
 };
 
 mod cli;
 mod display;
+mod export;
+mod import;
 mod models;
+mod pomodoro;
 mod store;
+mod ui;
 mod utils;
 mod view;
 
 fn main() {
-    let cli = Cli::parse();
-    let cmd_ctx = CommandContext::from(&cli);
-    let renderer = make_renderer(&cmd_ctx.render_opts);
+    let cli = Cli::parse_from(expand_aliases(env::args_os().collect()));
     let cmd_name = command_name(cli.command.as_ref());
+    let cmd_ctx = CommandContext::try_from(&cli);
+
+    // The config couldn't be read if there's no context, so errors fall back to the defaults.
+    let renderer = match &cmd_ctx {
+        Ok(cmd_ctx) => make_renderer(&cmd_ctx.render_opts),
+        Err(_) => make_renderer(&RenderOpts::new(
+            &cli,
+            &Settings::default(),
+            cli.vault.clone(),
+        )),
+    };
+
+    let result = cmd_ctx.and_then(|mut cmd_ctx| {
+        cmd_ctx.operation.command = cmd_name;
+        dispatch(cli, cmd_ctx)
+    });
 
-    if let Err(e) = dispatch(cli, cmd_ctx) {
+    if let Err(e) = result {
         if let Err(render_err) = renderer.render_error(cmd_name, &e) {
             eprintln!("Tusk: {e}");
             eprintln!("Failed to render error: {render_err}");
@@ -44,61 +117,305 @@ fn main() {
     }
 }
 
-fn dispatch(cli: Cli, ctx: CommandContext) -> Result<(), TuskError> {
+fn dispatch(cli: Cli, mut ctx: CommandContext) -> Result<(), TuskError> {
+    // Vault commands can touch several vaults, so they lock each one themselves.
+    if let Some(Commands::Vault(vault_commands)) = cli.command {
+        return dispatch_vault(vault_commands, ctx);
+    }
+
+    let lock = ctx.lock()?;
+
+    run_rollover(&mut ctx)?;
+
+    // The UI stays open for a while, so it only locks the vault for each change.
+    if let Some(Commands::Ui) = cli.command {
+        drop(lock);
+        return ui::run(ctx);
+    }
+
+    // So does a pomodoro, which locks it for each round it logs.
+    if let Some(Commands::Focus(FocusCommands::Pomodoro { .. })) = cli.command {
+        drop(lock);
+    }
+
     match cli.command {
         Some(Commands::Add {
             date,
             text,
             priority,
             attach_notes,
-        }) => run_add(date, text, priority, attach_notes, ctx, TaskTarget::Day),
+            due,
+            estimate,
+            under,
+        }) => match under {
+            Some(parent) => run_add_subtask(date, parent, text, ctx, TaskTarget::Day),
+            None => {
+                let options = AddOptions {
+                    priority,
+                    attach_notes,
+                    due,
+                    estimate,
+                };
+                run_add(date, text, options, ctx, TaskTarget::Day)
+            }
+        },
         Some(Commands::Ls { date, tags, scope }) => {
-            run_ls(date, tags, ctx, scope.unwrap_or(ListScope::Day))
+            let scope = scope.unwrap_or(ctx.settings.list_scope());
+            run_ls(date, tags, ctx, scope)
+        }
+        Some(Commands::Done {
+            date,
+            items,
+            filters,
+        }) => {
+            let (date, items) = resolve_day(date, items)?;
+            run_done(date, filters.select(items), true, ctx, TaskTarget::Day)
         }
-        Some(Commands::Done { date, index }) => run_done(date, index, true, ctx, TaskTarget::Day),
-        Some(Commands::Undone { date, index }) => {
-            run_done(date, index, false, ctx, TaskTarget::Day)
+        Some(Commands::Undone {
+            date,
+            items,
+            filters,
+        }) => {
+            let (date, items) = resolve_day(date, items)?;
+            run_done(date, filters.select(items), false, ctx, TaskTarget::Day)
+        }
+        Some(Commands::Rm {
+            date,
+            items,
+            filters,
+        }) => {
+            let (date, items) = resolve_day(date, items)?;
+            run_rm(date, filters.select(items), ctx, TaskTarget::Day)
         }
-        Some(Commands::Rm { date, index }) => run_rm(date, index, ctx, TaskTarget::Day),
         Some(Commands::Edit {
             date,
-            index,
-            text,
-            attach_notes,
-            priority,
-        }) => run_edit(date, index, text, attach_notes, priority, ctx),
-        Some(Commands::Show { date, index }) => run_show(date, index, ctx, TaskTarget::Day),
+            items,
+            filters,
+            changes,
+        }) => {
+            let (items, text) = picks_then_text(items, parse_day_pick_list)?;
+            let (date, items) = resolve_day(date, items)?;
+            let picks = items.into_iter().flat_map(|l| l.0).collect();
+            let changes = changes.with_text(text)?;
+            run_edit(date, filters.select(picks), changes, ctx, TaskTarget::Day)
+        }
+        Some(Commands::Promote {
+            date,
+            items,
+            filters,
+        }) => {
+            let (date, items) = resolve_day(date, items)?;
+            run_move(date, filters.select(items), TaskTarget::Day, ctx)
+        }
+        Some(Commands::Show { date, reference }) => {
+            let (date, reference) = reference.resolve(date)?;
+            run_show(date, reference, ctx, TaskTarget::Day)
+        }
+        Some(Commands::Start { date, reference }) => {
+            let (date, reference) = reference.resolve(date)?;
+            run_start(date, reference, ctx, TaskTarget::Day)
+        }
+        Some(Commands::Stop) => run_stop(ctx),
         Some(Commands::Migrate {
             from_date,
             to_date,
+            items,
+            tags,
+            priority,
+            ids,
+            interactive,
+            copy,
             dry_run,
-        }) => run_migrate(from_date, to_date, dry_run, ctx),
+        }) => {
+            let (from_date, mut items) = resolve_day(from_date, items)?;
+            items.extend(ids);
+            let selection = Selection {
+                picks: items,
+                tags,
+                priority,
+                open_only: true,
+            };
+            let mode = MigrateMode {
+                interactive,
+                copy,
+                dry_run,
+            };
+            run_migrate(from_date, to_date, selection, mode, ctx)
+        }
         Some(Commands::Review { days }) => run_review(days, ctx),
+        Some(Commands::Stats { range }) => run_stats(range, ctx),
+        Some(Commands::Week { date }) => run_plan(PlanSpan::Week, date, ctx),
+        Some(Commands::Month { date }) => run_plan(PlanSpan::Month, date, ctx),
+        Some(Commands::Cal { month, year }) => run_cal(month, year, ctx),
+        Some(Commands::Due { soon }) => run_due(soon, ctx),
+        Some(Commands::Stale {
+            min_migrations,
+            drop,
+            focus,
+            priority,
+        }) => {
+            let action = match (drop, focus, priority) {
+                (true, _, _) => StaleAction::Drop,
+                (_, true, _) => StaleAction::Focus,
+                (_, _, Some(p)) => StaleAction::Reprioritise(p),
+                _ => StaleAction::List,
+            };
+            run_stale(min_migrations, action, ctx)
+        }
+        Some(Commands::Undo { force }) => run_history_step(HistoryAction::Undo, force, ctx),
+        Some(Commands::Redo { force }) => run_history_step(HistoryAction::Redo, force, ctx),
+        Some(Commands::Log { limit }) => run_log(limit, ctx),
+        Some(Commands::Search { query, scope }) => {
+            run_search(query.join(" "), scope.unwrap_or(ListScope::All), ctx)
+        }
+        Some(Commands::Export {
+            from_date,
+            to_date,
+            format,
+            focus,
+            out,
+        }) => run_export(from_date, to_date, format, focus, out, ctx),
+        Some(Commands::Import {
+            file,
+            format,
+            dry_run,
+        }) => run_import(file, format, dry_run, ctx),
         Some(Commands::Focus(focus_commands)) => dispatch_focus(focus_commands, ctx),
-        None => run_ls(None, vec![], ctx, ListScope::Day),
+        Some(Commands::Recur(recur_commands)) => dispatch_recur(recur_commands, ctx),
+        Some(Commands::Store(StoreCommands::Migrate { to })) => run_store_migrate(to, ctx),
+        Some(Commands::Config(config_commands)) => dispatch_config(config_commands, ctx),
+        Some(Commands::Vault(_)) | Some(Commands::Ui) => {
+            unreachable!("vault commands and the UI are dispatched above")
+        }
+        None => {
+            let scope = ctx.settings.list_scope();
+            run_ls(None, vec![], ctx, scope)
+        }
     }
 }
 
 fn dispatch_focus(commands: FocusCommands, ctx: CommandContext) -> Result<(), TuskError> {
     match commands {
-        FocusCommands::Add { text } => run_add(None, text, None, false, ctx, TaskTarget::Focus),
-        FocusCommands::Ls {} => run_ls(None, vec![], ctx, ListScope::Focus),
-        FocusCommands::Done { date, index } => run_done(date, index, true, ctx, TaskTarget::Focus),
-        FocusCommands::Undone { date, index } => {
-            run_done(date, index, true, ctx, TaskTarget::Focus)
+        FocusCommands::Add {
+            text,
+            due,
+            estimate,
+            under,
+        } => match under {
+            Some(parent) => run_add_subtask(None, parent, text, ctx, TaskTarget::Focus),
+            None => {
+                let options = AddOptions {
+                    due,
+                    estimate,
+                    ..AddOptions::default()
+                };
+                run_add(None, text, options, ctx, TaskTarget::Focus)
+            }
+        },
+        FocusCommands::Ls => run_ls(None, vec![], ctx, ListScope::Focus),
+        FocusCommands::Done {
+            date,
+            items,
+            filters,
+        } => run_done(date, filters.select(items), true, ctx, TaskTarget::Focus),
+        FocusCommands::Undone {
+            date,
+            items,
+            filters,
+        } => run_done(date, filters.select(items), false, ctx, TaskTarget::Focus),
+        FocusCommands::Rm {
+            date,
+            items,
+            filters,
+        } => run_rm(date, filters.select(items), ctx, TaskTarget::Focus),
+        FocusCommands::Edit {
+            date,
+            items,
+            filters,
+            changes,
+        } => {
+            let (items, text) = picks_then_text(items, parse_pick_list)?;
+            let picks = items.into_iter().flat_map(|l| l.0).collect();
+            let changes = changes.with_text(text)?;
+            run_edit(date, filters.select(picks), changes, ctx, TaskTarget::Focus)
         }
-        FocusCommands::Rm { date, index } => run_rm(date, index, ctx, TaskTarget::Focus),
-        FocusCommands::Show { date, index } => run_show(date, index, ctx, TaskTarget::Focus),
+        FocusCommands::Demote {
+            date,
+            items,
+            filters,
+        } => run_move(date, filters.select(items), TaskTarget::Focus, ctx),
+        FocusCommands::Show { date, reference } => {
+            run_show(date, reference, ctx, TaskTarget::Focus)
+        }
+        FocusCommands::Start { reference } => run_start(None, reference, ctx, TaskTarget::Focus),
+        FocusCommands::Pomodoro {
+            reference,
+            work,
+            rest,
+            rounds,
+        } => run_pomodoro(reference, PomodoroPlan { work, rest, rounds }, ctx),
+    }
+}
+
+fn dispatch_recur(commands: RecurCommands, ctx: CommandContext) -> Result<(), TuskError> {
+    match commands {
+        RecurCommands::Ls => run_recur_ls(ctx),
+        RecurCommands::Add {
+            text,
+            schedule,
+            priority,
+            start,
+        } => run_recur_add(text, schedule, priority, start, ctx),
+        RecurCommands::Rm { reference } => run_recur_rm(reference, ctx),
+        RecurCommands::Pause { reference } => run_recur_pause(reference, true, ctx),
+        RecurCommands::Resume { reference } => run_recur_pause(reference, false, ctx),
+    }
+}
+
+fn dispatch_config(commands: ConfigCommands, ctx: CommandContext) -> Result<(), TuskError> {
+    match commands {
+        ConfigCommands::Get { key } => run_config_get(key, ctx),
+        ConfigCommands::Set { key, value, local } => run_config_set(key, Some(value), local, ctx),
+        ConfigCommands::Unset { key, local } => run_config_set(key, None, local, ctx),
+        ConfigCommands::Ls => run_config_ls(ctx),
+    }
+}
+
+fn dispatch_vault(commands: VaultCommands, ctx: CommandContext) -> Result<(), TuskError> {
+    let changes_vaults = !matches!(
+        commands,
+        VaultCommands::Ls | VaultCommands::Current { name: None }
+    );
+
+    if ctx.store.is_read_only() && changes_vaults {
+        return Err(refused().into());
+    }
+
+    match commands {
+        VaultCommands::Ls => run_vault_ls(ctx),
+        VaultCommands::Create { name } => run_vault_create(name, ctx),
+        VaultCommands::Rename { from, to } => run_vault_rename(from, to, ctx),
+        VaultCommands::Rm { name, force } => run_vault_rm(name, force, ctx),
+        VaultCommands::Copy { from, to } => run_vault_copy(from, to, ctx),
+        VaultCommands::Current { name } => run_vault_current(name, ctx),
     }
 }
 
 // command handler functions
 
+/// What `add` sets on a new item besides its text.
+#[derive(Default)]
+struct AddOptions {
+    priority: Option<ItemPriority>,
+    attach_notes: bool,
+    due: Option<DateTime<Utc>>,
+    estimate: Option<u32>,
+}
+
 fn run_add(
     date: Option<NaiveDate>,
     text: String,
-    priority: Option<ItemPriority>,
-    attach_notes: bool,
+    options: AddOptions,
     ctx: CommandContext,
     target: TaskTarget,
 ) -> Result<(), TuskError> {
@@ -106,16 +423,18 @@ fn run_add(
     let new_text = sanitise_str(&text)?;
     let tags = extract_tags(&new_text);
 
-    let item = Item::new(
+    let mut item = Item::new(
         new_text,
-        priority.unwrap_or(ItemPriority::Low),
+        options.priority.unwrap_or(ctx.settings.default_priority()),
         tags,
-        if attach_notes {
-            Some(edit_in_editor("")?)
+        if options.attach_notes {
+            Some(edit_in_editor("", ctx.settings.editor.as_deref())?)
         } else {
             None
         },
     );
+    item.due = options.due;
+    item.estimate_minutes = options.estimate;
 
     let renderer = make_renderer(&ctx.render_opts);
 
@@ -126,7 +445,7 @@ fn run_add(
             save_dayfile(&ctx, &df)?;
 
             if let Some(item) = df.items.last() {
-                renderer.render_summary(Some(df.date), df.items.len(), item)?;
+                renderer.render_summary(Some(df.date), &TaskRef::Index(df.items.len()), item)?;
             }
         }
         TaskTarget::Focus => {
@@ -135,7 +454,7 @@ fn run_add(
             save_focusfile(&ctx, &ff)?;
 
             if let Some(item) = ff.items.last() {
-                renderer.render_summary(None, 0, item)?;
+                renderer.render_summary(None, &TaskRef::Index(ff.items.len()), item)?;
             }
         }
     }
@@ -143,6 +462,43 @@ fn run_add(
     Ok(())
 }
 
+fn run_add_subtask(
+    date: Option<NaiveDate>,
+    parent: TaskRef,
+    text: String,
+    ctx: CommandContext,
+    target: TaskTarget,
+) -> Result<(), TuskError> {
+    let date = date.unwrap_or(todays_date());
+    let subtask = Subtask::new(sanitise_str(&text)?);
+
+    let mut df = load_day_or_empty(&ctx, date)?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let (target, pos) = parent.resolve(target, &df.items, &ff.items)?;
+
+    let (date, item) = match target {
+        TaskTarget::Day => {
+            let item = &mut df.items[pos];
+            item.subtasks.push(subtask);
+            item.sync_with_subtasks();
+            save_dayfile(&ctx, &df)?;
+            (Some(date), &df.items[pos])
+        }
+        TaskTarget::Focus => {
+            let item = &mut ff.items[pos];
+            item.subtasks.push(subtask);
+            item.sync_with_subtasks();
+            save_focusfile(&ctx, &ff)?;
+            (None, &ff.items[pos])
+        }
+    };
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_summary(date, &parent.canonical(item), item)?;
+
+    Ok(())
+}
+
 fn run_ls(
     date: Option<NaiveDate>,
     tags: Vec<String>,
@@ -181,7 +537,7 @@ fn run_ls(
 
 fn run_done(
     date: Option<NaiveDate>,
-    index: usize,
+    selection: Selection,
     mark_done: bool,
     ctx: CommandContext,
     target: TaskTarget,
@@ -195,43 +551,61 @@ fn run_done(
         ActionKind::Undone
     };
 
-    let mark_item = |i: &mut Item| {
-        i.done_at = if mark_done {
-            i.done_at.take().or(Some(Utc::now()))
-        } else {
-            None
-        }
-    };
+    let mut df = load_day_or_empty(&ctx, date)?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let picked = selection.resolve_in(target, &df.items, &ff.items)?;
 
-    match target {
-        TaskTarget::Day => {
-            let mut df = load_day_or_empty(&ctx, date)?;
-            let pos = validate_index(index, df.items.len())?;
+    let now = Utc::now();
+    let mut batch = Batch::new(date, action);
 
-            {
-                let item = &mut df.items[pos];
-                mark_item(item);
+    for p in &picked {
+        let item = match p.target {
+            TaskTarget::Day => &mut df.items[p.pos],
+            TaskTarget::Focus => &mut ff.items[p.pos],
+        };
+        let before = item.clone();
+
+        match p.subtask {
+            Some(n) => {
+                let subtask = &mut item.subtasks[n];
+                subtask.done_at = if mark_done {
+                    subtask.done_at.or(Some(now))
+                } else {
+                    None
+                };
+                item.sync_with_subtasks();
             }
+            None if mark_done => item.mark_done(now),
+            None => item.mark_undone(),
+        }
 
-            save_dayfile(&ctx, &df)?;
+        if before.done_at.is_none() && item.done_at.is_some() {
+            stop_timer_on(&ctx, item, now)?;
+        }
 
-            let item = &df.items[pos];
-            renderer.render_action(index, date, action, Some(item))?;
+        // Lists and filters only report what actually changed.
+        if *item == before && selection.single().is_none() {
+            continue;
         }
-        TaskTarget::Focus => {
-            let mut ff = load_focus_or_empty(&ctx)?;
-            let pos = validate_index(index, ff.items.len())?;
 
-            {
-                let item = &mut ff.items[pos];
-                mark_item(item);
-            }
+        batch.entries.push(BatchEntry {
+            target: p.target,
+            reference: p.path(),
+            subtask: p.subtask.map(|n| item.subtasks[n].clone()),
+            item: item.clone(),
+        });
+    }
 
-            save_focusfile(&ctx, &ff)?;
+    save_touched(&ctx, &batch, &df, &ff)?;
 
-            let item = &ff.items[pos];
-            renderer.render_action(index, date, action, Some(item))?;
-        }
+    match (selection.single(), batch.entries.first()) {
+        (Some(reference), Some(entry)) => renderer.render_action(
+            &reference.canonical(&entry.item),
+            date,
+            action,
+            Some(&entry.item),
+        )?,
+        _ => renderer.render_batch(&batch)?,
     }
 
     Ok(())
@@ -239,55 +613,123 @@ fn run_done(
 
 fn run_rm(
     date: Option<NaiveDate>,
-    index: usize,
+    selection: Selection,
     ctx: CommandContext,
     target: TaskTarget,
 ) -> Result<(), TuskError> {
     let date = date.unwrap_or(todays_date());
 
-    let item = match target {
-        TaskTarget::Day => {
-            let mut df = load_day_or_empty(&ctx, date)?;
-            let pos = validate_index(index, df.items.len())?;
-            let item = df.items.remove(pos);
-            save_dayfile(&ctx, &df)?;
-            item
-        }
-        TaskTarget::Focus => {
-            let mut ff = load_focus_or_empty(&ctx)?;
-            let pos = validate_index(index, ff.items.len())?;
-            let item = ff.items.remove(pos);
-            save_focusfile(&ctx, &ff)?;
-            item
-        }
+    let mut df = load_day_or_empty(&ctx, date)?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let picked = selection.resolve_in(target, &df.items, &ff.items)?;
+
+    let removing_item = |t: TaskTarget, pos: usize| {
+        picked
+            .iter()
+            .any(|p| p.target == t && p.pos == pos && p.subtask.is_none())
     };
 
+    let mut batch = Batch::new(date, ActionKind::Removed);
+
+    // Back to front, so every removal leaves the positions still to come alone.
+    for p in picked.iter().rev() {
+        let items = match p.target {
+            TaskTarget::Day => &mut df.items,
+            TaskTarget::Focus => &mut ff.items,
+        };
+
+        // Removing a subtask leaves the item in place, with what's left of its checklist.
+        let (item, subtask) = match p.subtask {
+            Some(_) if removing_item(p.target, p.pos) => continue,
+            Some(n) => {
+                let item = &mut items[p.pos];
+                let subtask = item.subtasks.remove(n);
+                item.sync_with_subtasks();
+                (item.clone(), Some(subtask))
+            }
+            None => (items.remove(p.pos), None),
+        };
+
+        batch.entries.push(BatchEntry {
+            target: p.target,
+            reference: p.path(),
+            item,
+            subtask,
+        });
+    }
+
+    batch.entries.reverse();
+
+    save_touched(&ctx, &batch, &df, &ff)?;
+
     let renderer = make_renderer(&ctx.render_opts);
-    renderer.render_action(index, date, ActionKind::Removed, Some(&item))?;
+
+    match (selection.single(), batch.entries.first()) {
+        (Some(reference), Some(entry)) => renderer.render_action(
+            &reference.canonical(&entry.item),
+            date,
+            ActionKind::Removed,
+            Some(&entry.item),
+        )?,
+        _ => renderer.render_batch(&batch)?,
+    }
 
     Ok(())
 }
 
 fn run_edit(
     date: Option<NaiveDate>,
-    index: usize,
-    text: Option<String>,
-    attach_notes: bool,
-    priority: Option<ItemPriority>,
+    selection: Selection,
+    changes: EditArgs,
     ctx: CommandContext,
+    target: TaskTarget,
 ) -> Result<(), TuskError> {
     let date = date.unwrap_or(todays_date());
     let mut df = load_day_or_empty(&ctx, date)?;
-    let pos = validate_index(index, df.items.len())?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let picked = selection.resolve_in(target, &df.items, &ff.items)?;
+
+    if picked.iter().any(|p| p.subtask.is_some()) {
+        return Err(TuskError::InvalidInput {
+            message: "Subtasks can't be edited, edit their item instead.".to_string(),
+        });
+    }
+
+    let single = selection.single();
+
+    if single.is_none() && (changes.text.is_some() || changes.attach_notes) {
+        return Err(TuskError::InvalidInput {
+            message: "The text and notes can only be edited one item at a time.".to_string(),
+        });
+    }
+
+    let mut batch = Batch::new(date, ActionKind::Edited);
+
+    for p in &picked {
+        let item = match p.target {
+            TaskTarget::Day => &mut df.items[p.pos],
+            TaskTarget::Focus => &mut ff.items[p.pos],
+        };
+
+        if let Some(s) = &changes.text {
+            item.set_text(sanitise_str(s)?);
+        }
+
+        if changes.clear_tags {
+            item.clear_tags();
+        }
 
-    if let Some(item) = df.items.get_mut(pos) {
-        if let Some(s) = text {
-            item.text = sanitise_str(&s)?;
+        for tag in &changes.rm_tags {
+            item.remove_tag(tag);
         }
 
-        let notes = if attach_notes {
+        for tag in &changes.add_tags {
+            item.add_tag(tag);
+        }
+
+        let notes = if changes.attach_notes {
             let template = item.notes.as_deref().unwrap_or("");
-            Some(edit_in_editor(&template)?)
+            Some(edit_in_editor(template, ctx.settings.editor.as_deref())?)
         } else {
             None
         };
@@ -296,148 +738,1974 @@ fn run_edit(
             item.notes = notes;
         }
 
-        if let Some(p) = priority {
+        if let Some(p) = changes.priority {
             item.priority = p;
         }
 
-        save_dayfile(&ctx, &df)?;
+        if changes.due.is_some() {
+            item.due = changes.due;
+        } else if changes.clear_due {
+            item.due = None;
+        }
+
+        if changes.estimate.is_some() {
+            item.estimate_minutes = changes.estimate;
+        } else if changes.clear_estimate {
+            item.estimate_minutes = None;
+        }
+
+        batch.entries.push(BatchEntry {
+            target: p.target,
+            reference: p.path(),
+            item: item.clone(),
+            subtask: None,
+        });
     }
 
-    Ok(())
-}
+    save_touched(&ctx, &batch, &df, &ff)?;
 
-fn run_show(
-    date: Option<NaiveDate>,
-    index: usize,
-    ctx: CommandContext,
-    target: TaskTarget,
-) -> Result<(), TuskError> {
-    let date = date.unwrap_or(todays_date());
     let renderer = make_renderer(&ctx.render_opts);
 
-    match target {
-        TaskTarget::Day => {
-            let df = load_day_or_empty(&ctx, date)?;
-            let pos = validate_index(index, df.items.len())?;
-            let item = &df.items[pos];
-            renderer.render_summary(Some(date), index, item)?;
-        }
-        TaskTarget::Focus => {
-            let ff = load_focus_or_empty(&ctx)?;
-            let pos = validate_index(index, ff.items.len())?;
-            let item = &ff.items[pos];
-            renderer.render_summary(Some(date), index, item)?;
-        }
-    };
+    match (single, batch.entries.first()) {
+        (Some(reference), Some(entry)) => renderer.render_action(
+            &reference.canonical(&entry.item),
+            date,
+            ActionKind::Edited,
+            Some(&entry.item),
+        )?,
+        _ => renderer.render_batch(&batch)?,
+    }
 
     Ok(())
 }
 
-fn run_migrate(
-    from_date: Option<NaiveDate>,
-    to_date: Option<NaiveDate>,
-    dry_run: bool,
+/// Moves items between the day's list and the focus tasks, keeping the items
+/// as they are and noting the move in their history.
+fn run_move(
+    date: Option<NaiveDate>,
+    selection: Selection,
+    from: TaskTarget,
     ctx: CommandContext,
 ) -> Result<(), TuskError> {
-    let from_date = from_date.unwrap_or(todays_date());
-    let to_date = to_date.unwrap_or(todays_date());
+    let date = date.unwrap_or(todays_date());
 
-    if from_date == to_date {
+    let mut df = load_day_or_empty(&ctx, date)?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let picked = selection.resolve_in(from, &df.items, &ff.items)?;
+
+    if picked.iter().any(|p| p.subtask.is_some()) {
         return Err(TuskError::InvalidInput {
-            message: "Both `--from` and `--to` are the same value, check your input.".to_string(),
+            message: "Subtasks move with their item, pick the item instead.".to_string(),
         });
     }
 
-    let mut from_df = load_day_or_empty(&ctx, from_date)?;
-    let from_df_before = from_df.clone();
+    if picked.iter().any(|p| p.target != from) {
+        let message = match from {
+            TaskTarget::Day => "That item is already one of your focus tasks.",
+            TaskTarget::Focus => "That item is already on a day's list.",
+        };
 
-    let mut to_df = load_day_or_empty(&ctx, to_date)?;
-    let renderer = make_renderer(&ctx.render_opts);
+        return Err(TuskError::InvalidInput {
+            message: message.to_string(),
+        });
+    }
 
-    if dry_run {
-        let mut pending_items = from_df.migratable_items();
+    let (source, dest, action) = match from {
+        TaskTarget::Day => (&mut df.items, &mut ff.items, ActionKind::Promoted),
+        TaskTarget::Focus => (&mut ff.items, &mut df.items, ActionKind::Demoted),
+    };
 
-        for i in pending_items.iter_mut() {
-            i.migrated_from = Some(from_date);
-        }
+    let now = Utc::now();
+    let mut batch = Batch::new(date, action);
 
-        renderer.render_migrate(to_df.date, &from_df_before, &pending_items, true)?;
-    } else {
-        let (mut to_move, to_keep): (Vec<Item>, Vec<Item>) =
-            from_df.items.into_iter().partition(|i| i.done_at.is_none());
+    for p in picked.iter().rev() {
+        let mut item = source.remove(p.pos);
 
-        for i in &mut to_move {
-            i.migrated_from = Some(from_date);
-        }
+        item.list_moves.push(match from {
+            TaskTarget::Day => ListMove::Promoted { date, at: now },
+            TaskTarget::Focus => ListMove::Demoted { date, at: now },
+        });
 
-        from_df.items = to_keep;
-        let moved_items = to_move.clone();
-        to_df.items.extend(to_move);
+        batch.entries.push(BatchEntry {
+            target: from,
+            reference: p.path(),
+            item,
+            subtask: None,
+        });
+    }
 
-        save_dayfile(&ctx, &from_df)?;
-        save_dayfile(&ctx, &to_df)?;
+    batch.entries.reverse();
+    dest.extend(batch.entries.iter().map(|e| e.item.clone()));
 
-        renderer.render_migrate(to_df.date, &from_df_before, &moved_items, false)?;
+    if !batch.entries.is_empty() {
+        save_dayfile(&ctx, &df)?;
+        save_focusfile(&ctx, &ff)?;
     }
 
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_batch(&batch)?;
+
     Ok(())
 }
 
-fn run_review(days: Option<u64>, ctx: CommandContext) -> Result<(), TuskError> {
-    let days = days.unwrap_or(1);
-
-    if days > 365 {
-        return Err(TuskError::InvalidInput {
-            message: "Review can't be more than 365 days".to_string(),
-        });
+/// Saves the day and focus files a batch changed anything in.
+fn save_touched(
+    ctx: &CommandContext,
+    batch: &Batch,
+    df: &DayFile,
+    ff: &FocusFile,
+) -> Result<(), TuskError> {
+    if batch.entries.iter().any(|e| e.target == TaskTarget::Day) {
+        save_dayfile(ctx, df)?;
     }
 
-    let today = todays_date();
+    if batch.entries.iter().any(|e| e.target == TaskTarget::Focus) {
+        save_focusfile(ctx, ff)?;
+    }
 
-    let start = today
-        .checked_sub_days(Days::new(days))
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "data underflow"))?;
+    Ok(())
+}
 
-    let end = today;
+fn run_show(
+    date: Option<NaiveDate>,
+    reference: TaskRef,
+    ctx: CommandContext,
+    target: TaskTarget,
+) -> Result<(), TuskError> {
+    let date = date.unwrap_or(todays_date());
+    let renderer = make_renderer(&ctx.render_opts);
 
-    let mut dayfiles: Vec<DayFile> = Vec::new();
+    let df = load_day_or_empty(&ctx, date)?;
+    let ff = load_focus_or_empty(&ctx)?;
+    let (target, pos) = reference.resolve(target, &df.items, &ff.items)?;
 
-    for d in start.iter_days().take_while(|d| *d < end) {
-        match load_day_or_empty(&ctx, d) {
-            Ok(df) => {
-                if !df.items.is_empty() {
-                    dayfiles.push(df);
-                }
-            }
-            Err(e) => warn_dayfile_error(d, &e, ctx.render_opts.verbose),
-        }
-    }
+    let item = match target {
+        TaskTarget::Day => &df.items[pos],
+        TaskTarget::Focus => &ff.items[pos],
+    };
 
-    let renderer = make_renderer(&ctx.render_opts);
-    renderer.render_review(start, end, days, &dayfiles)?;
+    renderer.render_summary(Some(date), &reference.canonical(item), item)?;
 
     Ok(())
 }
 
-fn command_name(cmd: Option<&Commands>) -> &'static str {
-    match cmd {
-        Some(Commands::Ls { .. }) => "ls",
-        Some(Commands::Add { .. }) => "add",
-        Some(Commands::Done { .. }) => "done",
-        Some(Commands::Undone { .. }) => "undone",
-        Some(Commands::Rm { .. }) => "rm",
-        Some(Commands::Edit { .. }) => "edit",
-        Some(Commands::Show { .. }) => "show",
-        Some(Commands::Migrate { .. }) => "migrate",
+/// Starts a timer on an item. A vault runs one timer at a time, so any
+/// already running is stopped and logged first.
+fn run_start(
+    date: Option<NaiveDate>,
+    reference: TaskRef,
+    ctx: CommandContext,
+    target: TaskTarget,
+) -> Result<(), TuskError> {
+    let date = date.unwrap_or(todays_date());
+    let mut config = load_vault_config(&ctx)?;
+
+    let df = load_day_or_empty(&ctx, date)?;
+    let ff = load_focus_or_empty(&ctx)?;
+    let (target, pos) = reference.resolve(target, &df.items, &ff.items)?;
+
+    let item = match target {
+        TaskTarget::Day => &df.items[pos],
+        TaskTarget::Focus => &ff.items[pos],
+    };
+
+    if item.done_at.is_some() {
+        return Err(TuskError::InvalidInput {
+            message: format!("{} is already done.", reference.canonical(item)),
+        });
+    }
+
+    if config.timer.as_ref().is_some_and(|t| t.item_id == item.id) {
+        return Err(TuskError::InvalidInput {
+            message: format!(
+                "A timer is already running on {}.",
+                reference.canonical(item)
+            ),
+        });
+    }
+
+    let now = Utc::now();
+
+    // A timer whose item has since been removed has nothing to log against.
+    let stopped = match config.timer.take() {
+        Some(timer) => match stop_timer(&ctx, &timer, now) {
+            Ok(entry) => Some(entry),
+            Err(TuskError::ItemNotFound { .. }) => None,
+            Err(e) => return Err(e),
+        },
+        None => None,
+    };
+
+    config.timer = Some(ActiveTimer {
+        item_id: item.id.clone(),
+        target,
+        date,
+        started_at: now,
+    });
+    save_vault_config(&ctx, &config)?;
+
+    let started = TimerEntry {
+        target,
+        date,
+        item: item.clone(),
+        started_at: now,
+        stopped_at: None,
+    };
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_timer(&TimerChange {
+        stopped,
+        started: Some(started),
+    })?;
+
+    Ok(())
+}
+
+fn run_stop(ctx: CommandContext) -> Result<(), TuskError> {
+    let mut config = load_vault_config(&ctx)?;
+
+    let Some(timer) = config.timer.take() else {
+        return Err(TuskError::InvalidInput {
+            message: "No timer is running, start one with `tusk start <INDEX|@ID>`.".to_string(),
+        });
+    };
+
+    // Cleared even when the item is gone, so the timer can't get stuck.
+    let stopped = stop_timer(&ctx, &timer, Utc::now());
+    save_vault_config(&ctx, &config)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_timer(&TimerChange {
+        stopped: Some(stopped?),
+        started: None,
+    })?;
+
+    Ok(())
+}
+
+/// Logs a running timer as a session on its item. The item is looked for on
+/// the day the timer started, then in case it's been migrated or promoted
+/// since, on today's list and in the focus tasks.
+fn stop_timer(
+    ctx: &CommandContext,
+    timer: &ActiveTimer,
+    now: DateTime<Utc>,
+) -> Result<TimerEntry, TuskError> {
+    let session = timer.session(now);
+
+    let stopped = |target, date, item: &Item| TimerEntry {
+        target,
+        date,
+        item: item.clone(),
+        started_at: timer.started_at,
+        stopped_at: Some(now),
+    };
+
+    let mut dates = vec![timer.date];
+    if timer.date != todays_date() {
+        dates.push(todays_date());
+    }
+
+    for date in dates {
+        let mut df = load_day_or_empty(ctx, date)?;
+
+        if let Some(item) = df.items.iter_mut().find(|i| i.id == timer.item_id) {
+            item.sessions.push(session);
+            let entry = stopped(TaskTarget::Day, date, item);
+            save_dayfile(ctx, &df)?;
+            return Ok(entry);
+        }
+    }
+
+    let mut ff = load_focus_or_empty(ctx)?;
+
+    if let Some(item) = ff.items.iter_mut().find(|i| i.id == timer.item_id) {
+        item.sessions.push(session);
+        let entry = stopped(TaskTarget::Focus, timer.date, item);
+        save_focusfile(ctx, &ff)?;
+        return Ok(entry);
+    }
+
+    Err(TuskError::ItemNotFound {
+        reference: TaskRef::Id(timer.item_id.clone()).to_string(),
+    })
+}
+
+/// Runs pomodoro rounds on a focus item, offering to mark it done once
+/// they're all finished.
+fn run_pomodoro(
+    reference: TaskRef,
+    plan: PomodoroPlan,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    if plan.rounds == 0 || plan.work <= chrono::Duration::zero() {
+        return Err(TuskError::InvalidInput {
+            message: "A pomodoro needs at least one round of work.".to_string(),
+        });
+    }
+
+    let item = {
+        let _lock = ctx.lock()?;
+        let df = load_day_or_empty(&ctx, todays_date())?;
+        let ff = load_focus_or_empty(&ctx)?;
+
+        match reference.resolve(TaskTarget::Focus, &df.items, &ff.items)? {
+            (TaskTarget::Focus, pos) => ff.items[pos].clone(),
+            (TaskTarget::Day, _) => {
+                return Err(TuskError::InvalidInput {
+                    message: format!(
+                        "{reference} is on today's list, pomodoros run on focus tasks. \
+                         Move it there with `tusk promote` first."
+                    ),
+                });
+            }
+        }
+    };
+
+    if item.done_at.is_some() {
+        return Err(TuskError::InvalidInput {
+            message: format!("{} is already done.", reference.canonical(&item)),
+        });
+    }
+
+    let completed = pomodoro::run(&ctx, &item.id, &plan)?;
+    let marked_done = pomodoro::ask_done(&item)?;
+
+    let _lock = ctx.lock()?;
+    let mut ff = load_focus_or_empty(&ctx)?;
+    let pos = ff
+        .items
+        .iter()
+        .position(|i| i.id == item.id)
+        .ok_or_else(|| TuskError::ItemNotFound {
+            reference: format!("@{}", item.id),
+        })?;
+
+    if marked_done {
+        ff.items[pos].mark_done(Utc::now());
+        save_focusfile(&ctx, &ff)?;
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_pomodoro(&PomodoroRun {
+        reference: reference.canonical(&item),
+        plan,
+        item: ff.items[pos].clone(),
+        completed,
+        marked_done,
+    })?;
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct MigrateMode {
+    interactive: bool,
+    copy: bool,
+    dry_run: bool,
+}
+
+fn run_migrate(
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    selection: Selection,
+    mode: MigrateMode,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let from_date = from_date.unwrap_or(todays_date());
+    let to_date = to_date.unwrap_or(todays_date());
+
+    if from_date == to_date {
+        return Err(TuskError::InvalidInput {
+            message: "Both `--from` and `--to` are the same value, check your input.".to_string(),
+        });
+    }
+
+    let mut from_df = load_day_or_empty(&ctx, from_date)?;
+    let mut to_df = load_day_or_empty(&ctx, to_date)?;
+
+    // Items picked by index, range or id are resolved done or not, so the
+    // done ones can be reported instead of quietly left behind.
+    let picked = match selection.picks.is_empty() {
+        true => selection.resolve(&from_df.items)?,
+        false => Selection {
+            open_only: false,
+            ..selection
+        }
+        .resolve(&from_df.items)?,
+    };
+
+    let (already_done, open): (Vec<_>, Vec<_>) = picked
+        .into_iter()
+        .partition(|&pos| from_df.items[pos].done_at.is_some());
+
+    let mut choices = Vec::new();
+    for pos in open {
+        let choice = if mode.interactive {
+            ask_migration_choice(pos + 1, &from_df.items[pos])?
+        } else {
+            MigrationChoice::Move
+        };
+        choices.push((pos, choice));
+    }
+
+    let choice_at = |pos: usize| {
+        choices
+            .iter()
+            .find(|(p, _)| *p == pos)
+            .map(|(_, c)| *c)
+            .unwrap_or(MigrationChoice::Keep)
+    };
+
+    let mut migration = Migration::new(from_df.clone(), to_date, mode.dry_run, mode.copy);
+    migration.already_done = already_done
+        .into_iter()
+        .map(|pos| (pos, from_df.items[pos].clone()))
+        .collect();
+
+    let leaving = choices
+        .iter()
+        .filter(|(_, c)| matches!(c, MigrationChoice::Move | MigrationChoice::Focus))
+        .map(|(pos, _)| *pos)
+        .collect::<Vec<_>>();
+
+    let taken = if mode.copy {
+        from_df.copy_for_migration(&leaving)
+    } else {
+        from_df.take_for_migration(&leaving)
+    };
+
+    for (pos, item) in taken {
+        match choice_at(pos) {
+            MigrationChoice::Focus => migration.focused.push(item),
+            _ => migration.moved.push(item),
+        }
+    }
+
+    for (pos, _) in choices.iter().filter(|(_, c)| *c == MigrationChoice::Drop) {
+        let dropped = migration.from.items[*pos].clone();
+        from_df.items.retain(|i| i.id != dropped.id);
+        migration.dropped.push(dropped);
+    }
+
+    migration.kept = migration.from.migratable_positions().len()
+        - migration.moved.len()
+        - migration.focused.len()
+        - migration.dropped.len();
+
+    if !mode.dry_run && !migration.is_empty() {
+        if !mode.copy || !migration.dropped.is_empty() {
+            save_dayfile(&ctx, &from_df)?;
+        }
+
+        if !migration.moved.is_empty() {
+            to_df.items.extend(migration.moved.iter().cloned());
+            save_dayfile(&ctx, &to_df)?;
+        }
+
+        if !migration.focused.is_empty() {
+            let mut ff = load_focus_or_empty(&ctx)?;
+            ff.items.extend(migration.focused.iter().cloned());
+            save_focusfile(&ctx, &ff)?;
+        }
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_migrate(&migration)?;
+
+    Ok(())
+}
+
+/// Asks on stderr what to do with an item during `migrate --interactive`,
+/// moving it if the answer is left empty.
+fn ask_migration_choice(index: usize, item: &Item) -> Result<MigrationChoice, TuskError> {
+    let mut err = io::stderr().lock();
+
+    loop {
+        write!(
+            err,
+            "{index}. {} [m]ove, [k]eep, [d]rop, [f]ocus? ",
+            item.text
+        )?;
+        err.flush()?;
+
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer)? == 0 {
+            return Err(TuskError::InvalidInput {
+                message: "No answer given, nothing was migrated.".to_string(),
+            });
+        }
+
+        match answer.trim().to_lowercase().as_str() {
+            "" | "m" | "move" => return Ok(MigrationChoice::Move),
+            "k" | "keep" => return Ok(MigrationChoice::Keep),
+            "d" | "drop" => return Ok(MigrationChoice::Drop),
+            "f" | "focus" => return Ok(MigrationChoice::Focus),
+            _ => writeln!(err, "Answer m, k, d or f.")?,
+        }
+    }
+}
+
+/// With `rollover` on, the first command of a new day moves the open items of
+/// the last day that has any forward to today, as its own undoable change.
+fn run_rollover(ctx: &mut CommandContext) -> Result<(), TuskError> {
+    if !ctx.settings.rollover() || ctx.store.is_read_only() {
+        return Ok(());
+    }
+
+    let today = todays_date();
+    let mut config = load_vault_config(ctx)?;
+
+    if config.rolled_over_on.is_some_and(|d| d >= today) {
+        return Ok(());
+    }
+
+    // Not just yesterday, so Friday's tasks still reach Monday.
+    let mut from_df = None;
+    for date in list_day_dates(ctx)?
+        .into_iter()
+        .filter(|d| *d < today)
+        .rev()
+    {
+        let df = load_day_or_empty(ctx, date)?;
+
+        if !df.items.is_empty() {
+            from_df = Some(df);
+            break;
+        }
+    }
+
+    let command = ctx.operation.command;
+    ctx.operation = OperationContext::new("rollover");
+
+    let mut moved = None;
+
+    if let Some(mut from_df) = from_df {
+        let items = from_df.take_migratable();
+
+        if !items.is_empty() {
+            let mut to_df = load_day_or_empty(ctx, today)?;
+            to_df.items.extend(items.iter().cloned());
+
+            save_dayfile(ctx, &from_df)?;
+            save_dayfile(ctx, &to_df)?;
+
+            moved = Some(Rollover {
+                from: from_df.date,
+                to: today,
+                items,
+            });
+        }
+    }
+
+    ctx.operation = OperationContext::new(command);
+
+    config.rolled_over_on = Some(today);
+    save_vault_config(ctx, &config)?;
+
+    if let Some(rollover) = moved {
+        make_renderer(&ctx.render_opts).render_rollover(&rollover)?;
+    }
+
+    Ok(())
+}
+
+fn run_review(days: Option<u64>, ctx: CommandContext) -> Result<(), TuskError> {
+    let days = days.unwrap_or(ctx.settings.review_days());
+
+    if days > MAX_REVIEW_DAYS {
+        return Err(TuskError::InvalidInput {
+            message: format!("Review can't be more than {MAX_REVIEW_DAYS} days"),
+        });
+    }
+
+    let today = todays_date();
+
+    let start = today
+        .checked_sub_days(Days::new(days))
+        .ok_or_else(|| io::Error::other("data underflow"))?;
+
+    let end = today;
+
+    let mut dayfiles: Vec<DayFile> = Vec::new();
+
+    for d in start.iter_days().take_while(|d| *d < end) {
+        match load_day_or_empty(&ctx, d) {
+            Ok(df) => {
+                if !df.items.is_empty() {
+                    dayfiles.push(df);
+                }
+            }
+            Err(e) => warn_dayfile_error(d, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    // Sessions count towards the day they happened, so items that have moved
+    // on to today or the focus tasks since still add to the range.
+    let mut time = TimeReport::new(start, end);
+    let today_df = load_day_or_empty(&ctx, today)?;
+    let ff = load_focus_or_empty(&ctx)?;
+
+    for item in dayfiles
+        .iter()
+        .chain([&today_df])
+        .flat_map(|df| &df.items)
+        .chain(&ff.items)
+    {
+        time.add(item);
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_review(start, end, days, &dayfiles, &time)?;
+
+    Ok(())
+}
+
+fn run_plan(span: PlanSpan, date: Option<NaiveDate>, ctx: CommandContext) -> Result<(), TuskError> {
+    let today = todays_date();
+    let mut plan = Plan::new(
+        span,
+        date.unwrap_or(today),
+        today,
+        load_focus_or_empty(&ctx)?,
+    );
+
+    for date in plan.dates().collect::<Vec<_>>() {
+        match load_day_or_empty(&ctx, date) {
+            Ok(df) => plan.add_day(df),
+            Err(e) => warn_dayfile_error(date, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_plan(&plan)?;
+
+    Ok(())
+}
+
+fn run_cal(
+    month: Option<NaiveDate>,
+    year: Option<i32>,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let today = todays_date();
+
+    let span = match (month, year) {
+        (_, Some(year)) => CalendarSpan::Year(year),
+        (Some(month), None) => CalendarSpan::Month(month),
+        (None, None) => CalendarSpan::Month(today.with_day(1).unwrap_or(today)),
+    };
+
+    let mut calendar = Calendar::new(span, today).ok_or_else(|| TuskError::InvalidInput {
+        message: "That calendar is out of range.".to_string(),
+    })?;
+
+    let (start, end) = (calendar.start(), calendar.end());
+
+    for (date, df) in ctx.store.days().load_range(Some(start), Some(end))? {
+        match df {
+            Ok(df) => calendar.add_day(&df),
+            Err(e) => warn_dayfile_error(date, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    // Due dates can be set on any day's items. Days in the calendar that
+    // couldn't be read were warned about above.
+    for (date, df) in ctx.store.days().load_with_due()? {
+        match df {
+            Ok(df) => calendar.add_due(&df.items),
+            Err(_) if (start..=end).contains(&date) => {}
+            Err(e) => warn_dayfile_error(date, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    calendar.add_due(&load_focus_or_empty(&ctx)?.items);
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_calendar(&calendar)?;
+
+    Ok(())
+}
+
+fn run_stats(range: StatsRange, ctx: CommandContext) -> Result<(), TuskError> {
+    let end = todays_date();
+    let start = range.start(end).ok_or_else(|| TuskError::InvalidInput {
+        message: format!("A range of {range} goes back too far."),
+    })?;
+
+    let mut dayfiles = Vec::new();
+
+    for (date, df) in ctx.store.days().load_range(Some(start), Some(end))? {
+        match df {
+            Ok(df) => dayfiles.push(df),
+            Err(e) => warn_dayfile_error(date, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    let report = StatsReport::new(range, start, end, &dayfiles);
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_stats(&report)?;
+
+    Ok(())
+}
+
+fn run_due(soon: Option<i64>, ctx: CommandContext) -> Result<(), TuskError> {
+    let soon_days = soon.unwrap_or(DUE_SOON_DAYS);
+
+    if soon_days < 0 {
+        return Err(TuskError::InvalidInput {
+            message: "`--soon` can't be negative".to_string(),
+        });
+    }
+
+    let mut report = DueReport::new(Utc::now(), soon_days);
+
+    for (d, df) in ctx.store.days().load_with_due()? {
+        match df {
+            Ok(df) => report.collect(Some(d), &df.items),
+            Err(e) => warn_dayfile_error(d, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    report.collect(None, &load_focus_or_empty(&ctx)?.items);
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_due(&report)?;
+
+    Ok(())
+}
+
+fn run_stale(
+    min_migrations: Option<usize>,
+    action: StaleAction,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let min_migrations = min_migrations.unwrap_or(STALE_MIGRATIONS);
+
+    if min_migrations == 0 {
+        return Err(TuskError::InvalidInput {
+            message: "`--min-migrations` must be at least 1".to_string(),
+        });
+    }
+
+    let mut report = StaleReport::new(todays_date(), min_migrations, action);
+    let mut to_focus = Vec::new();
+    let now = Utc::now();
+
+    for (d, df) in ctx.store.days().load_range(None, None)? {
+        let mut df = match df {
+            Ok(df) => df,
+            Err(e) => {
+                warn_dayfile_error(d, &e, ctx.render_opts.verbose);
+                continue;
+            }
+        };
+
+        if report.collect(d, &df.items) == 0 {
+            continue;
+        }
+
+        match action {
+            StaleAction::List => continue,
+            StaleAction::Drop => df.items.retain(|i| !report.is_stale(i)),
+            StaleAction::Focus => {
+                let (stale, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut df.items)
+                    .into_iter()
+                    .partition(|i| report.is_stale(i));
+                df.items = kept;
+
+                // Noted the same way `promote` notes it.
+                to_focus.extend(stale.into_iter().map(|mut i| {
+                    i.list_moves.push(ListMove::Promoted { date: d, at: now });
+                    i
+                }));
+            }
+            StaleAction::Reprioritise(p) => {
+                for i in df.items.iter_mut().filter(|i| report.is_stale(i)) {
+                    i.priority = p;
+                }
+            }
+        }
+
+        save_dayfile(&ctx, &df)?;
+    }
+
+    if !to_focus.is_empty() {
+        let mut ff = load_focus_or_empty(&ctx)?;
+        ff.items.extend(to_focus);
+        save_focusfile(&ctx, &ff)?;
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_stale(&report)?;
+
+    Ok(())
+}
+
+fn run_search(query: String, scope: ListScope, ctx: CommandContext) -> Result<(), TuskError> {
+    let parsed = Query::parse(&query)?;
+    let mut results = SearchResults::new(query);
+
+    if scope != ListScope::Day {
+        results.collect(&parsed, None, &load_focus_or_empty(&ctx)?.items);
+    }
+
+    if scope != ListScope::Focus {
+        for (d, df) in ctx.store.days().load_range(None, None)? {
+            match df {
+                Ok(df) => {
+                    results.days_searched += 1;
+                    results.collect(&parsed, Some(d), &df.items);
+                }
+                Err(e) => warn_dayfile_error(d, &e, ctx.render_opts.verbose),
+            }
+        }
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_search(&results)?;
+
+    Ok(())
+}
+
+fn run_export(
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    format: ExportFormat,
+    focus: bool,
+    out: Option<PathBuf>,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let from = from_date.unwrap_or(todays_date());
+    let to = to_date.unwrap_or(todays_date());
+
+    if from > to {
+        return Err(TuskError::InvalidInput {
+            message: "`--from` is after `--to`, check your input.".to_string(),
+        });
+    }
+
+    let mut days = Vec::new();
+
+    for (d, df) in ctx.store.days().load_range(Some(from), Some(to))? {
+        match df {
+            Ok(df) if !df.items.is_empty() => days.push(df),
+            Ok(_) => {}
+            Err(e) => warn_dayfile_error(d, &e, ctx.render_opts.verbose),
+        }
+    }
+
+    let export = Export {
+        from,
+        to,
+        days,
+        focus: if focus {
+            Some(load_focus_or_empty(&ctx)?)
+        } else {
+            None
+        },
+    };
+
+    let formats = &ctx.render_opts.formats;
+
+    let Some(path) = out else {
+        let mut stdout = io::stdout().lock();
+        format.write(&mut stdout, &export, formats)?;
+        return Ok(());
+    };
+
+    let mut report = ExportReport {
+        format,
+        path: path.clone(),
+        files: 0,
+        days: export.days.len(),
+        items: export.items(),
+    };
+
+    if path.is_dir() {
+        for (name, part) in export.split() {
+            let file = path.join(name).with_extension(format.extension());
+            write_export_file(&file, format, &part, formats)?;
+            report.files += 1;
+        }
+    } else {
+        write_export_file(&path, format, &export, formats)?;
+        report.files = 1;
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_export(&report)?;
+
+    Ok(())
+}
+
+fn write_export_file(
+    path: &Path,
+    format: ExportFormat,
+    export: &Export,
+    formats: &DateFormats,
+) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+    format.write(&mut file, export, formats)?;
+    file.flush()
+}
+
+fn run_import(
+    path: PathBuf,
+    format: ImportFormat,
+    dry_run: bool,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let input = if path.as_os_str() == "-" {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(&path)?
+    };
+
+    let defaults = ImportDefaults {
+        today: todays_date(),
+        priority: ctx.settings.default_priority(),
+        formats: ctx.render_opts.formats.clone(),
+    };
+
+    let parsed = format
+        .parse(&input, &defaults)
+        .map_err(|message| TuskError::InvalidInput { message })?;
+
+    let mut days: BTreeMap<NaiveDate, DayFile> = BTreeMap::new();
+    let mut focus = None;
+    let mut entries = Vec::new();
+
+    for p in parsed {
+        let (target, item) = match p.result {
+            Ok(found) => found,
+            Err(refusal) => {
+                entries.push(ImportEntry {
+                    entry: p.entry,
+                    text: p.text,
+                    target: None,
+                    outcome: match refusal {
+                        Refusal::Skipped(reason) => ImportOutcome::Skipped(reason),
+                        Refusal::Rejected(reason) => ImportOutcome::Rejected(reason),
+                    },
+                });
+                continue;
+            }
+        };
+
+        let items = match target {
+            Target::Day(date) => match days.entry(date) {
+                Entry::Occupied(e) => &mut e.into_mut().items,
+                Entry::Vacant(e) => &mut e.insert(load_day_or_empty(&ctx, date)?).items,
+            },
+            Target::Focus => {
+                if focus.is_none() {
+                    focus = Some(load_focus_or_empty(&ctx)?);
+                }
+                &mut focus
+                    .as_mut()
+                    .expect("the focus tasks were just loaded")
+                    .items
+            }
+        };
+
+        // Earlier items from the same file count too, so a file can't import twice over.
+        let duplicate = items
+            .iter()
+            .any(|i| i.text.trim().eq_ignore_ascii_case(item.text.trim()));
+
+        let outcome = if duplicate {
+            ImportOutcome::Skipped("already there".to_string())
+        } else {
+            items.push(item);
+            ImportOutcome::Imported
+        };
+
+        entries.push(ImportEntry {
+            entry: p.entry,
+            text: p.text,
+            target: Some(target),
+            outcome,
+        });
+    }
+
+    let report = ImportReport {
+        format,
+        path,
+        dry_run,
+        entries,
+    };
+
+    if !dry_run && report.imported() > 0 {
+        let touched: BTreeSet<Target> = report
+            .entries
+            .iter()
+            .filter(|e| matches!(e.outcome, ImportOutcome::Imported))
+            .filter_map(|e| e.target)
+            .collect();
+
+        for target in touched {
+            match target {
+                Target::Day(date) => save_dayfile(&ctx, &days[&date])?,
+                Target::Focus => {
+                    if let Some(ff) = &focus {
+                        save_focusfile(&ctx, ff)?;
+                    }
+                }
+            }
+        }
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_import(&report)?;
+
+    Ok(())
+}
+
+fn run_history_step(
+    action: HistoryAction,
+    force: bool,
+    mut ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let journal = load_journal(&ctx)?;
+
+    let op = match action {
+        HistoryAction::Undo => journal.next_undo(),
+        HistoryAction::Redo => journal.next_redo(),
+    }
+    .ok_or_else(|| TuskError::InvalidInput {
+        message: format!("Nothing to {}", action.as_command()),
+    })?;
+
+    ctx.operation.kind = match action {
+        HistoryAction::Undo => OperationKind::Undo {
+            target: op.id.clone(),
+        },
+        HistoryAction::Redo => OperationKind::Redo {
+            target: op.id.clone(),
+        },
+    };
+
+    // Each file goes back to how it was before the first write, or forward
+    // to how it was after the last, so a multi-file operation is one step.
+    let steps = op
+        .net_changes()
+        .into_iter()
+        .map(|(file, before, after)| match action {
+            HistoryAction::Undo => (file, after, before),
+            HistoryAction::Redo => (file, before, after),
+        })
+        .collect::<Vec<_>>();
+
+    if !force {
+        for (file, expected, _) in &steps {
+            if file_state(&ctx, file)?.as_ref() != *expected {
+                return Err(TuskError::InvalidInput {
+                    message: format!(
+                        "{file} has changed since `{}` ({}), use --force to {} it anyway",
+                        op.command,
+                        op.id,
+                        action.as_command()
+                    ),
+                });
+            }
+        }
+    }
+
+    for (file, _, state) in &steps {
+        restore_file(&ctx, file, *state)?;
+    }
+
+    let entry = HistoryEntry::new(op, matches!(action, HistoryAction::Undo));
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_history_action(action, &entry)?;
+
+    Ok(())
+}
+
+fn run_log(limit: usize, ctx: CommandContext) -> Result<(), TuskError> {
+    let journal = load_journal(&ctx)?;
+    let history = History::new(&journal, limit);
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_history(&history)?;
+
+    Ok(())
+}
+
+fn run_recur_ls(ctx: CommandContext) -> Result<(), TuskError> {
+    let rf = load_recurring_or_empty(&ctx)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_rules(&rf.rules, todays_date())?;
+
+    Ok(())
+}
+
+fn run_recur_add(
+    text: String,
+    schedule: Schedule,
+    priority: Option<ItemPriority>,
+    start: Option<NaiveDate>,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let rule = RecurrenceRule::new(
+        sanitise_str(&text)?,
+        priority.unwrap_or(ctx.settings.default_priority()),
+        schedule,
+        start.unwrap_or(todays_date()),
+    );
+
+    let mut rf = load_recurring_or_empty(&ctx)?;
+    rf.rules.push(rule);
+    save_recurring(&ctx, &rf)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    if let Some(rule) = rf.rules.last() {
+        renderer.render_rule_action(&TaskRef::Index(rf.rules.len()), RuleAction::Added, rule)?;
+    }
+
+    Ok(())
+}
+
+fn run_recur_rm(reference: TaskRef, ctx: CommandContext) -> Result<(), TuskError> {
+    let mut rf = load_recurring_or_empty(&ctx)?;
+    let ids = rf.rules.iter().map(|r| r.id.as_str()).collect::<Vec<_>>();
+    let pos = reference.position(&ids)?;
+
+    let rule = rf.rules.remove(pos);
+    save_recurring(&ctx, &rf)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_rule_action(&reference.with_id(&rule.id), RuleAction::Removed, &rule)?;
+
+    Ok(())
+}
+
+fn run_recur_pause(reference: TaskRef, paused: bool, ctx: CommandContext) -> Result<(), TuskError> {
+    let mut rf = load_recurring_or_empty(&ctx)?;
+    let ids = rf.rules.iter().map(|r| r.id.as_str()).collect::<Vec<_>>();
+    let pos = reference.position(&ids)?;
+
+    rf.rules[pos].paused = paused;
+    save_recurring(&ctx, &rf)?;
+
+    let action = if paused {
+        RuleAction::Paused
+    } else {
+        RuleAction::Resumed
+    };
+
+    let rule = &rf.rules[pos];
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_rule_action(&reference.with_id(&rule.id), action, rule)?;
+
+    Ok(())
+}
+
+fn run_store_migrate(to: StoreBackend, ctx: CommandContext) -> Result<(), TuskError> {
+    let from = load_vault_config(&ctx)?.store;
+
+    if from == to {
+        return Err(TuskError::InvalidInput {
+            message: format!("This vault already uses the {to} store."),
+        });
+    }
+
+    let migration = migrate_store(&ctx, to)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_store_migrate(&migration)?;
+
+    Ok(())
+}
+
+fn run_config_get(key: String, ctx: CommandContext) -> Result<(), TuskError> {
+    let global = load_settings(ctx.config_path.clone())?;
+    let vault = load_vault_config(&ctx)?.settings;
+
+    let entry = ConfigEntry::resolve(&key, &global, &vault)
+        .map_err(|message| TuskError::InvalidInput { message })?;
+
+    if entry.value.is_none() {
+        return Err(TuskError::InvalidInput {
+            message: format!("'{key}' isn't set"),
+        });
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_config(ConfigAction::Get, &[entry])?;
+
+    Ok(())
+}
+
+/// Sets or clears a key in the global config, or in the vault's `config.json` with `--local`.
+fn run_config_set(
+    key: String,
+    value: Option<String>,
+    local: bool,
+    ctx: CommandContext,
+) -> Result<(), TuskError> {
+    let invalid = |message| TuskError::InvalidInput { message };
+
+    let source = if local {
+        let mut config = load_vault_config(&ctx)?;
+        config
+            .settings
+            .set(&key, value.as_deref())
+            .map_err(invalid)?;
+        save_vault_config(&ctx, &config)?;
+        ConfigSource::Vault
+    } else {
+        let store = FsSettingsStore::new(ctx.config_path.clone())?;
+        let mut settings = load_settings(ctx.config_path.clone())?;
+        settings.set(&key, value.as_deref()).map_err(invalid)?;
+        store.save(&settings)?;
+        ConfigSource::Global
+    };
+
+    let entry = ConfigEntry {
+        key,
+        value: value.map(|v| v.trim().to_string()),
+        source,
+    };
+
+    let action = if entry.value.is_some() {
+        ConfigAction::Set
+    } else {
+        ConfigAction::Unset
+    };
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_config(action, &[entry])?;
+
+    Ok(())
+}
+
+fn run_config_ls(ctx: CommandContext) -> Result<(), TuskError> {
+    let global = load_settings(ctx.config_path.clone())?;
+    let vault = load_vault_config(&ctx)?.settings;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_config(ConfigAction::Ls, &ConfigEntry::all(&global, &vault))?;
+
+    Ok(())
+}
+
+fn run_vault_ls(ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let current = current_vault(registry)?;
+    let mut names = registry.names()?;
+
+    if !names.contains(&current) {
+        names.push(current.clone());
+        names.sort();
+    }
+
+    let mut vaults = Vec::new();
+
+    for name in names {
+        let store = registry.open(&name)?;
+        let mut summary = VaultSummary::new(&name, name == current);
+
+        for date in store.days().dates()? {
+            match store.days().load(date) {
+                Ok(df) => summary.add_day(&df.items),
+                Err(e) => warn_dayfile_error(date, &e, ctx.render_opts.verbose),
+            }
+        }
+
+        match store.focus().load() {
+            Ok(ff) => summary.add_focus(&ff.items),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if let Some(entry) = store.journal().load(1)?.last() {
+            summary.touched_at(entry.at);
+        }
+
+        vaults.push(summary);
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_vaults(&vaults)?;
+
+    Ok(())
+}
+
+fn run_vault_create(name: String, ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let name = checked_vault_name(&name)?;
+    ensure_vault_free(registry, &name)?;
+    registry.create(&name)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_vault_action(VaultAction::Create, &name, None)?;
+
+    Ok(())
+}
+
+fn run_vault_rename(from: String, to: String, ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let (from, to) = (checked_vault_name(&from)?, checked_vault_name(&to)?);
+    ensure_vault_exists(registry, &from)?;
+    ensure_vault_free(registry, &to)?;
+
+    {
+        let _lock = registry.lock(&from)?;
+        registry.rename(&from, &to)?;
+    }
+
+    if current_vault(registry)? == from {
+        registry.set_current(&to)?;
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_vault_action(VaultAction::Rename, &to, Some(&from))?;
+
+    Ok(())
+}
+
+fn run_vault_rm(name: String, force: bool, ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let name = checked_vault_name(&name)?;
+    ensure_vault_exists(registry, &name)?;
+
+    if current_vault(registry)? == name {
+        return Err(TuskError::InvalidInput {
+            message: format!(
+                "'{name}' is the current vault, switch to another with `tusk vault current <name>` first"
+            ),
+        });
+    }
+
+    let _lock = registry.lock(&name)?;
+
+    if !force {
+        let store = registry.open(&name)?;
+        let mut summary = VaultSummary::new(&name, false);
+
+        for date in store.days().dates()? {
+            summary.add_day(&store.days().load(date)?.items);
+        }
+
+        if let Ok(ff) = store.focus().load() {
+            summary.add_focus(&ff.items);
+        }
+
+        if summary.tasks() > 0 {
+            return Err(TuskError::InvalidInput {
+                message: format!(
+                    "'{name}' still has {} task(s), pass --force to delete it anyway",
+                    summary.tasks()
+                ),
+            });
+        }
+    }
+
+    registry.remove(&name)?;
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_vault_action(VaultAction::Remove, &name, None)?;
+
+    Ok(())
+}
+
+fn run_vault_copy(from: String, to: String, ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let (from, to) = (checked_vault_name(&from)?, checked_vault_name(&to)?);
+    ensure_vault_exists(registry, &from)?;
+    ensure_vault_free(registry, &to)?;
+
+    {
+        let _lock = registry.lock(&from)?;
+        registry.copy(&from, &to)?;
+    }
+
+    let renderer = make_renderer(&ctx.render_opts);
+    renderer.render_vault_action(VaultAction::Copy, &to, Some(&from))?;
+
+    Ok(())
+}
+
+fn run_vault_current(name: Option<String>, ctx: CommandContext) -> Result<(), TuskError> {
+    let registry = ctx.store.registry();
+    let renderer = make_renderer(&ctx.render_opts);
+
+    let Some(name) = name else {
+        renderer.render_vault_action(VaultAction::Current, &current_vault(registry)?, None)?;
+        return Ok(());
+    };
+
+    let name = checked_vault_name(&name)?;
+
+    // The default vault is always there to go back to, even before it has any tasks.
+    if name != "default" {
+        ensure_vault_exists(registry, &name)?;
+    }
+
+    registry.set_current(&name)?;
+    renderer.render_vault_action(VaultAction::Switch, &name, None)?;
+
+    Ok(())
+}
+
+/// The vault used without `--vault`.
+fn current_vault(registry: &dyn VaultRegistry) -> Result<String, TuskError> {
+    Ok(registry.current()?.unwrap_or_else(|| "default".to_string()))
+}
+
+fn checked_vault_name(name: &str) -> Result<String, TuskError> {
+    vault_name(name).map_err(|message| TuskError::InvalidInput { message })
+}
+
+fn ensure_vault_exists(registry: &dyn VaultRegistry, name: &str) -> Result<(), TuskError> {
+    if !registry.exists(name) {
+        return Err(TuskError::InvalidInput {
+            message: format!("There's no vault named '{name}', run `tusk vault ls` to see them"),
+        });
+    }
+
+    Ok(())
+}
+
+fn ensure_vault_free(registry: &dyn VaultRegistry, name: &str) -> Result<(), TuskError> {
+    if registry.exists(name) {
+        return Err(TuskError::InvalidInput {
+            message: format!("A vault named '{name}' already exists"),
+        });
+    }
+
+    Ok(())
+}
+
+fn command_name(cmd: Option<&Commands>) -> &'static str {
+    match cmd {
+        Some(Commands::Ls { .. }) => "ls",
+        Some(Commands::Add { .. }) => "add",
+        Some(Commands::Done { .. }) => "done",
+        Some(Commands::Undone { .. }) => "undone",
+        Some(Commands::Rm { .. }) => "rm",
+        Some(Commands::Edit { .. }) => "edit",
+        Some(Commands::Promote { .. }) => "promote",
+        Some(Commands::Show { .. }) => "show",
+        Some(Commands::Start { .. }) => "start",
+        Some(Commands::Stop) => "stop",
+        Some(Commands::Migrate { .. }) => "migrate",
         Some(Commands::Review { .. }) => "review",
+        Some(Commands::Stats { .. }) => "stats",
+        Some(Commands::Week { .. }) => "week",
+        Some(Commands::Month { .. }) => "month",
+        Some(Commands::Cal { .. }) => "cal",
+        Some(Commands::Due { .. }) => "due",
+        Some(Commands::Stale { .. }) => "stale",
+        Some(Commands::Undo { .. }) => "undo",
+        Some(Commands::Redo { .. }) => "redo",
+        Some(Commands::Log { .. }) => "log",
+        Some(Commands::Search { .. }) => "search",
+        Some(Commands::Export { .. }) => "export",
+        Some(Commands::Import { .. }) => "import",
         Some(Commands::Focus(focus_cmd)) => match focus_cmd {
-            FocusCommands::Ls { .. } => "focus ls",
+            FocusCommands::Ls => "focus ls",
             FocusCommands::Add { .. } => "focus add",
             FocusCommands::Done { .. } => "focus done",
             FocusCommands::Undone { .. } => "focus undone",
             FocusCommands::Rm { .. } => "focus rm",
+            FocusCommands::Edit { .. } => "focus edit",
+            FocusCommands::Demote { .. } => "focus demote",
             FocusCommands::Show { .. } => "focus show",
+            FocusCommands::Start { .. } => "focus start",
+            FocusCommands::Pomodoro { .. } => "focus pomodoro",
+        },
+        Some(Commands::Recur(recur_cmd)) => match recur_cmd {
+            RecurCommands::Ls => "recur ls",
+            RecurCommands::Add { .. } => "recur add",
+            RecurCommands::Rm { .. } => "recur rm",
+            RecurCommands::Pause { .. } => "recur pause",
+            RecurCommands::Resume { .. } => "recur resume",
+        },
+        Some(Commands::Store(store_cmd)) => match store_cmd {
+            StoreCommands::Migrate { .. } => "store migrate",
+        },
+        Some(Commands::Ui) => "ui",
+        Some(Commands::Vault(vault_cmd)) => match vault_cmd {
+            VaultCommands::Ls => "vault ls",
+            VaultCommands::Create { .. } => "vault create",
+            VaultCommands::Rename { .. } => "vault rename",
+            VaultCommands::Rm { .. } => "vault rm",
+            VaultCommands::Copy { .. } => "vault copy",
+            VaultCommands::Current { .. } => "vault current",
+        },
+        Some(Commands::Config(config_cmd)) => match config_cmd {
+            ConfigCommands::Get { .. } => "config get",
+            ConfigCommands::Set { .. } => "config set",
+            ConfigCommands::Unset { .. } => "config unset",
+            ConfigCommands::Ls => "config ls",
         },
         None => "ls",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{
+        cli::command::OperationContext,
+        display::terminal::DateFormats,
+        store::{memory::MemoryStore, read_only::ReadOnlyStore, vault_store::VaultStore},
+        utils::{
+            dates::ymd,
+            render::{RenderOpts, RenderOutput},
+            selection::Pick,
+        },
+    };
+
+    fn ctx(store: &Rc<MemoryStore>, command: &'static str) -> CommandContext {
+        CommandContext {
+            config_path: None,
+            settings: Settings::default(),
+            render_opts: RenderOpts {
+                output: RenderOutput::Json,
+                verbose: false,
+                vault_name: None,
+                color: false,
+                formats: DateFormats::default(),
+                checkbox_open: None,
+                checkbox_done: None,
+            },
+            store: Box::new(store.clone()),
+            operation: OperationContext::new(command),
+        }
+    }
+
+    fn add(store: &Rc<MemoryStore>, date: NaiveDate, text: &str) {
+        run_add(
+            Some(date),
+            text.to_string(),
+            AddOptions::default(),
+            ctx(store, "add"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+    }
+
+    fn texts(store: &Rc<MemoryStore>, date: NaiveDate) -> Vec<String> {
+        match store.days().load(date) {
+            Ok(df) => df.items.into_iter().map(|i| i.text).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn done_and_rm_update_the_store() {
+        let store = Rc::new(MemoryStore::default());
+        let date = ymd("2020-01-01");
+
+        add(&store, date, "one");
+        add(&store, date, "two");
+
+        run_done(
+            Some(date),
+            TaskRef::Index(1).into(),
+            true,
+            ctx(&store, "done"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+        run_rm(
+            Some(date),
+            TaskRef::Index(2).into(),
+            ctx(&store, "rm"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+
+        let df = store.days().load(date).unwrap();
+        assert_eq!(df.items.len(), 1);
+        assert!(df.items[0].done_at.is_some());
+    }
+
+    #[test]
+    fn undo_and_redo_migrate_as_one_step() {
+        let store = Rc::new(MemoryStore::default());
+        let (from, to) = (ymd("2020-01-01"), ymd("2020-01-02"));
+
+        add(&store, from, "one");
+        add(&store, from, "two");
+        run_migrate(
+            Some(from),
+            Some(to),
+            Selection {
+                open_only: true,
+                ..Selection::default()
+            },
+            MigrateMode::default(),
+            ctx(&store, "migrate"),
+        )
+        .unwrap();
+
+        assert!(texts(&store, from).is_empty());
+        assert_eq!(texts(&store, to), ["one", "two"]);
+
+        run_history_step(HistoryAction::Undo, false, ctx(&store, "undo")).unwrap();
+
+        assert_eq!(texts(&store, from), ["one", "two"]);
+        assert!(store.days().load(to).is_err());
+
+        run_history_step(HistoryAction::Redo, false, ctx(&store, "redo")).unwrap();
+
+        assert!(texts(&store, from).is_empty());
+        assert_eq!(texts(&store, to), ["one", "two"]);
+    }
+
+    #[test]
+    fn migrate_leaves_picked_done_items_behind() {
+        let store = Rc::new(MemoryStore::default());
+        let (from, to) = (ymd("2020-01-01"), ymd("2020-01-02"));
+
+        add(&store, from, "one");
+        add(&store, from, "two");
+        run_done(
+            Some(from),
+            TaskRef::Index(1).into(),
+            true,
+            ctx(&store, "done"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+
+        run_migrate(
+            Some(from),
+            Some(to),
+            Selection {
+                picks: vec![Pick::Range(1, 2)],
+                open_only: true,
+                ..Selection::default()
+            },
+            MigrateMode::default(),
+            ctx(&store, "migrate"),
+        )
+        .unwrap();
+
+        assert_eq!(texts(&store, from), ["one"]);
+        assert_eq!(texts(&store, to), ["two"]);
+    }
+
+    #[test]
+    fn read_only_store_refuses_changes() {
+        let store = Rc::new(MemoryStore::default());
+        let date = ymd("2020-01-01");
+        add(&store, date, "one");
+
+        let mut read_only = ctx(&store, "rm");
+        read_only.store = Box::new(ReadOnlyStore::new(Box::new(store.clone())));
+
+        assert!(
+            run_rm(
+                Some(date),
+                TaskRef::Index(1).into(),
+                read_only,
+                TaskTarget::Day
+            )
+            .is_err()
+        );
+        assert_eq!(texts(&store, date), ["one"]);
+    }
+
+    #[test]
+    fn done_stops_the_timer_running_on_the_item() {
+        let store = Rc::new(MemoryStore::default());
+        let date = ymd("2020-01-01");
+        add(&store, date, "one");
+
+        run_start(
+            Some(date),
+            TaskRef::Index(1),
+            ctx(&store, "start"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+        run_done(
+            Some(date),
+            TaskRef::Index(1).into(),
+            true,
+            ctx(&store, "done"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+
+        assert!(
+            load_vault_config(&ctx(&store, "ls"))
+                .unwrap()
+                .timer
+                .is_none()
+        );
+
+        let df = store.days().load(date).unwrap();
+        assert!(df.items[0].done_at.is_some());
+        assert_eq!(df.items[0].sessions.len(), 1);
+    }
+
+    #[test]
+    fn recurring_items_are_saved_the_first_time_a_day_is_read() {
+        let store = Rc::new(MemoryStore::default());
+        let future = todays_date() + Days::new(3);
+
+        run_recur_add(
+            "Water plants".to_string(),
+            Schedule::Daily,
+            None,
+            None,
+            ctx(&store, "recur add"),
+        )
+        .unwrap();
+        let journal = load_journal(&ctx(&store, "ls")).unwrap().operations.len();
+
+        // A read-only vault shows them but can't keep them.
+        let mut read_only = ctx(&store, "ls");
+        read_only.store = Box::new(ReadOnlyStore::new(Box::new(store.clone())));
+        let shown = load_day_or_empty(&read_only, future).unwrap();
+        assert_eq!(shown.items.len(), 1);
+        assert!(store.days().dates().unwrap().is_empty());
+
+        run_ls(Some(future), vec![], ctx(&store, "ls"), ListScope::Day).unwrap();
+
+        let df = store.days().load(future).unwrap();
+        assert_eq!(texts(&store, future), ["Water plants"]);
+        assert_eq!(df.items[0].id, shown.items[0].id);
+        assert_eq!(
+            load_journal(&ctx(&store, "ls")).unwrap().operations.len(),
+            journal
+        );
+
+        // Reading it again doesn't add them twice.
+        run_plan(PlanSpan::Month, Some(future), ctx(&store, "month")).unwrap();
+        assert_eq!(texts(&store, future), ["Water plants"]);
+    }
+
+    #[test]
+    fn store_migration_stays_in_memory() {
+        let store = Rc::new(MemoryStore::default());
+        add(&store, ymd("2020-01-01"), "one");
+        add(&store, ymd("2020-01-01"), "two");
+        add(&store, ymd("2020-01-02"), "three");
+
+        let report = migrate_store(&ctx(&store, "store migrate"), StoreBackend::Sqlite).unwrap();
+
+        assert_eq!((report.days, report.items, report.focus_items), (2, 3, 0));
+        assert_eq!(
+            load_vault_config(&ctx(&store, "config")).unwrap().store,
+            StoreBackend::Sqlite
+        );
+        assert!(run_store_migrate(StoreBackend::Sqlite, ctx(&store, "store migrate")).is_err());
+    }
+
+    #[test]
+    fn stale_items_sent_to_focus_are_promoted() {
+        let store = Rc::new(MemoryStore::default());
+        let date = ymd("2020-01-10");
+        add(&store, date, "old");
+        add(&store, date, "new");
+
+        let mut df = store.days().load(date).unwrap();
+        df.items[0].migration_history =
+            vec![ymd("2020-01-07"), ymd("2020-01-08"), ymd("2020-01-09")];
+        store.days().save(&df).unwrap();
+
+        run_stale(None, StaleAction::List, ctx(&store, "stale")).unwrap();
+        assert_eq!(texts(&store, date), ["old", "new"]);
+
+        run_stale(None, StaleAction::Focus, ctx(&store, "stale")).unwrap();
+        assert_eq!(texts(&store, date), ["new"]);
+
+        let ff = store.focus().load().unwrap();
+        assert_eq!(ff.items[0].text, "old");
+        assert!(matches!(
+            ff.items[0].list_moves[..],
+            [ListMove::Promoted { date: d, .. }] if d == date
+        ));
+    }
+
+    #[test]
+    fn vaults_are_created_copied_and_removed() {
+        let store = Rc::new(MemoryStore::default());
+        let registry = store.registry();
+
+        run_vault_create("work".into(), ctx(&store, "vault create")).unwrap();
+        assert!(run_vault_create("work".into(), ctx(&store, "vault create")).is_err());
+
+        let mut df = DayFile::new(ymd("2020-01-01"));
+        df.items.push(Item::new(
+            "ship it".into(),
+            ItemPriority::High,
+            vec![],
+            None,
+        ));
+        registry.open("work").unwrap().days().save(&df).unwrap();
+
+        run_vault_copy("work".into(), "home".into(), ctx(&store, "vault copy")).unwrap();
+        run_vault_rename("home".into(), "house".into(), ctx(&store, "vault rename")).unwrap();
+        assert_eq!(registry.names().unwrap(), ["default", "house", "work"]);
+
+        let copy = registry
+            .open("house")
+            .unwrap()
+            .days()
+            .load(df.date)
+            .unwrap();
+        assert_eq!(copy.items[0].text, "ship it");
+
+        run_vault_current(Some("work".into()), ctx(&store, "vault current")).unwrap();
+        assert_eq!(registry.current().unwrap().as_deref(), Some("work"));
+
+        // The current vault, or one with tasks, needs more than a plain `rm`.
+        assert!(run_vault_rm("work".into(), true, ctx(&store, "vault rm")).is_err());
+        assert!(run_vault_rm("house".into(), false, ctx(&store, "vault rm")).is_err());
+        run_vault_rm("house".into(), true, ctx(&store, "vault rm")).unwrap();
+        assert_eq!(registry.names().unwrap(), ["default", "work"]);
+    }
+
+    #[test]
+    fn rollover_moves_the_last_days_open_items_once() {
+        let store = Rc::new(MemoryStore::default());
+        let today = todays_date();
+        let last = today - Days::new(3);
+
+        add(&store, last, "carried");
+        add(&store, last, "finished");
+        run_done(
+            Some(last),
+            TaskRef::Index(2).into(),
+            true,
+            ctx(&store, "done"),
+            TaskTarget::Day,
+        )
+        .unwrap();
+        store
+            .days()
+            .save(&DayFile::new(today - Days::new(1)))
+            .unwrap();
+
+        // Off by default.
+        run_rollover(&mut ctx(&store, "ls")).unwrap();
+        assert_eq!(texts(&store, last), ["carried", "finished"]);
+
+        let mut on = ctx(&store, "ls");
+        on.settings.rollover = Some(true);
+        run_rollover(&mut on).unwrap();
+
+        assert_eq!(texts(&store, last), ["finished"]);
+        assert_eq!(texts(&store, today), ["carried"]);
+        assert_eq!(
+            store.days().load(today).unwrap().items[0].migrated_from,
+            Some(last)
+        );
+
+        // Only the first command of the day rolls over.
+        add(&store, last, "late");
+        run_rollover(&mut on).unwrap();
+        assert_eq!(texts(&store, last), ["finished", "late"]);
+    }
+
+    #[test]
+    fn promote_and_demote_move_items_between_lists() {
+        let store = Rc::new(MemoryStore::default());
+        let (date, later) = (ymd("2020-01-01"), ymd("2020-01-05"));
+
+        for text in ["one", "two", "three"] {
+            add(&store, date, text);
+        }
+
+        let picks = |picks: Vec<Pick>| Selection {
+            picks,
+            ..Selection::default()
+        };
+
+        run_move(
+            Some(date),
+            picks(vec![Pick::Ref(TaskRef::Index(1)), Pick::Range(3, 3)]),
+            TaskTarget::Day,
+            ctx(&store, "promote"),
+        )
+        .unwrap();
+
+        assert_eq!(texts(&store, date), ["two"]);
+        let ff = store.focus().load().unwrap();
+        assert_eq!(
+            ff.items.iter().map(|i| i.text.as_str()).collect::<Vec<_>>(),
+            ["one", "three"]
+        );
+
+        run_move(
+            Some(later),
+            picks(vec![Pick::Ref(TaskRef::Index(2))]),
+            TaskTarget::Focus,
+            ctx(&store, "demote"),
+        )
+        .unwrap();
+
+        assert_eq!(texts(&store, later), ["three"]);
+        assert!(matches!(
+            store.days().load(later).unwrap().items[0].list_moves[..],
+            [
+                ListMove::Promoted { date: from, .. },
+                ListMove::Demoted { date: to, .. },
+            ] if from == date && to == later
+        ));
+
+        // Picking by id finds the item on the other list, which can't move.
+        let id = store.focus().load().unwrap().items[0].id.clone();
+        assert!(
+            run_move(
+                Some(date),
+                TaskRef::Id(id).into(),
+                TaskTarget::Day,
+                ctx(&store, "promote"),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn pomodoro_logs_a_session_per_round_on_focus_items() {
+        let store = Rc::new(MemoryStore::default());
+        let date = todays_date();
+        add(&store, date, "on the day");
+        run_add(
+            None,
+            "write the report".to_string(),
+            AddOptions::default(),
+            ctx(&store, "focus add"),
+            TaskTarget::Focus,
+        )
+        .unwrap();
+
+        let plan = |rounds| PomodoroPlan {
+            work: chrono::Duration::milliseconds(5),
+            rest: chrono::Duration::zero(),
+            rounds,
+        };
+
+        let id = store.focus().load().unwrap().items[0].id.clone();
+        let completed = pomodoro::run(&ctx(&store, "focus pomodoro"), &id, &plan(2)).unwrap();
+
+        let item = &store.focus().load().unwrap().items[0];
+        assert_eq!(completed, 2);
+        assert_eq!(item.sessions.len(), 2);
+        assert!(item.sessions.iter().all(|s| s.pomodoro && s.end >= s.start));
+
+        // Only focus items, and only with some work to do.
+        let on_day = store.days().load(date).unwrap().items[0].id.clone();
+        for (reference, rounds) in [(TaskRef::Id(on_day), 1), (TaskRef::Id(id), 0)] {
+            assert!(run_pomodoro(reference, plan(rounds), ctx(&store, "focus pomodoro")).is_err());
+        }
+    }
+}
@@ -1,4 +1,9 @@
-pub mod item;
 pub mod dayfile;
 pub mod focus_file;
-pub mod task_stats;
\ No newline at end of file
+pub mod item;
+pub mod journal;
+pub mod recurrence;
+pub mod recurring_file;
+pub mod settings;
+pub mod task_stats;
+pub mod vault_config;
@@ -1,11 +1,20 @@
-use crate::models::{item::Item, task_stats::{HasItems, TaskStats}};
+use crate::models::{
+    item::Item,
+    recurrence::RecurrenceRule,
+    task_stats::{HasItems, TaskStats},
+};
 use chrono::NaiveDate;
+use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DayFile {
     pub date: NaiveDate,
     pub items: Vec<Item>,
+    /// Ids of the recurrence rules already applied to this day, so removed
+    /// items aren't generated again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recurrences_applied: Vec<String>,
 }
 
 impl DayFile {
@@ -13,15 +22,89 @@ impl DayFile {
     pub fn new(date: NaiveDate) -> Self {
         Self {
             date,
-            items: Vec::new()
+            items: Vec::new(),
+            recurrences_applied: Vec::new(),
         }
     }
 
-    pub fn migratable_items(&self) -> Vec<Item> {
-        self.items
+    /// Adds an item for every active rule due on this day that hasn't been
+    /// applied yet, returning whether anything changed.
+    pub fn apply_recurring(&mut self, rules: &[RecurrenceRule]) -> bool {
+        let mut changed = false;
+
+        for rule in rules.iter().filter(|r| r.is_due_on(self.date)) {
+            if self.recurrences_applied.contains(&rule.id) {
+                continue;
+            }
+
+            let present = self
+                .items
+                .iter()
+                .any(|i| i.recurrence_id.as_deref() == Some(rule.id.as_str()));
+
+            if !present {
+                self.items.push(rule.to_item(self.date));
+            }
+
+            self.recurrences_applied.push(rule.id.clone());
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Positions of the items that can be migrated, the open ones.
+    pub fn migratable_positions(&self) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|&pos| self.items[pos].done_at.is_none())
+            .collect()
+    }
+
+    /// Takes the open items out to move to another day, marking where they came
+    /// from. Finished subtasks stay behind in a done copy of their item.
+    pub fn take_migratable(&mut self) -> Vec<Item> {
+        let positions = self.migratable_positions();
+
+        self.take_for_migration(&positions)
+            .into_iter()
+            .map(|(_, i)| i)
+            .collect()
+    }
+
+    /// Like [`DayFile::take_migratable`] for the open items at `positions`,
+    /// returned with the position each had.
+    pub fn take_for_migration(&mut self, positions: &[usize]) -> Vec<(usize, Item)> {
+        let mut moved = Vec::new();
+        let mut kept = Vec::new();
+
+        for (pos, mut i) in std::mem::take(&mut self.items).into_iter().enumerate() {
+            if i.done_at.is_some() || !positions.contains(&pos) {
+                kept.push(i);
+                continue;
+            }
+
+            kept.extend(i.split_finished());
+            i.record_migration(self.date);
+            moved.push((pos, i));
+        }
+
+        self.items = kept;
+        moved
+    }
+
+    /// Copies of the open items at `positions` to add to another day, with new
+    /// ids and only their open subtasks. The originals stay as they are.
+    pub fn copy_for_migration(&self, positions: &[usize]) -> Vec<(usize, Item)> {
+        positions
             .iter()
-            .filter(|i| i.done_at.is_none())
-            .cloned()
+            .filter(|&&pos| self.items[pos].done_at.is_none())
+            .map(|&pos| {
+                let mut i = self.items[pos].clone();
+                i.id = nanoid!(6);
+                i.subtasks.retain(|s| s.done_at.is_none());
+                i.record_migration(self.date);
+                (pos, i)
+            })
             .collect()
     }
 
@@ -39,6 +122,7 @@ impl DayFile {
         DayFile {
             date: self.date,
             items,
+            recurrences_applied: self.recurrences_applied.clone(),
         }
     }
 }
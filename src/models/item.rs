@@ -1,10 +1,12 @@
 use core::fmt;
 
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
 use clap::ValueEnum;
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::helpers::extract_tags;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum ItemPriority {
@@ -24,7 +26,7 @@ impl fmt::Display for ItemPriority {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ItemStatus {
     Open,
@@ -41,6 +43,33 @@ impl fmt::Display for ItemStatus {
     }
 }
 
+/// How many days ahead an item counts as due soon.
+pub const DUE_SOON_DAYS: i64 = 3;
+
+/// How many migrations make an item stale, see `tusk stale`.
+pub const STALE_MIGRATIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DueState {
+    Overdue,
+    Today,
+    Soon,
+    Later,
+}
+
+impl fmt::Display for DueState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            DueState::Overdue => "overdue",
+            DueState::Today => "due today",
+            DueState::Soon => "due soon",
+            DueState::Later => "due",
+        };
+        f.write_str(s)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Item {
     pub id: String,
@@ -52,6 +81,83 @@ pub struct Item {
     pub due: Option<DateTime<Utc>>,
     pub notes: Option<String>,
     pub migrated_from: Option<NaiveDate>,
+    /// Every day the item was migrated from, oldest first. `migrated_from` is the last of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migration_history: Vec<NaiveDate>,
+    /// The recurrence rule that generated this item, if any.
+    #[serde(default)]
+    pub recurrence_id: Option<String>,
+    /// A checklist of steps, addressed as `3.1`, `3.2` and so on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<Subtask>,
+    /// Every move between a day's list and the focus tasks, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub list_moves: Vec<ListMove>,
+    /// How long the item is expected to take, in minutes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    /// Time spent on the item with `tusk start` and `tusk stop`, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<WorkSession>,
+}
+
+/// A stretch of time spent working on an item.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkSession {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Whether this was a completed round of `tusk focus pomodoro`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pomodoro: bool,
+}
+
+impl WorkSession {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// The local day the session started on, which is the day it counts towards.
+    pub fn day(&self) -> NaiveDate {
+        self.start.with_timezone(&Local).date_naive()
+    }
+}
+
+/// A move of an item between a day's list and the focus tasks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum ListMove {
+    /// From the list for `date` to the focus tasks.
+    Promoted { date: NaiveDate, at: DateTime<Utc> },
+    /// From the focus tasks to the list for `date`.
+    Demoted { date: NaiveDate, at: DateTime<Utc> },
+}
+
+/// One step of an item's checklist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Subtask {
+    pub id: String,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    pub done_at: Option<DateTime<Utc>>,
+}
+
+impl Subtask {
+    pub fn new(text: String) -> Self {
+        Subtask {
+            id: nanoid!(6),
+            text,
+            created_at: Utc::now(),
+            done_at: None,
+        }
+    }
+
+    pub fn status(&self) -> ItemStatus {
+        if self.done_at.is_some() {
+            ItemStatus::Done
+        } else {
+            ItemStatus::Open
+        }
+    }
 }
 
 impl Item {
@@ -63,7 +169,7 @@ impl Item {
     ) -> Self {
         Item {
             id: nanoid!(6),
-            text: text,
+            text,
             created_at: Utc::now(),
             done_at: None,
             priority,
@@ -71,6 +177,12 @@ impl Item {
             due: None,
             notes,
             migrated_from: None,
+            migration_history: Vec::new(),
+            recurrence_id: None,
+            subtasks: Vec::new(),
+            list_moves: Vec::new(),
+            estimate_minutes: None,
+            sessions: Vec::new(),
         }
     }
 
@@ -81,4 +193,224 @@ impl Item {
             ItemStatus::Open
         }
     }
+
+    /// Where the item stands against its due date, `None` if it has no due
+    /// date or is already done.
+    pub fn due_state(&self, now: DateTime<Utc>, soon_days: i64) -> Option<DueState> {
+        if self.done_at.is_some() {
+            return None;
+        }
+
+        let due = self.due?;
+        let today = now.with_timezone(&Local).date_naive();
+        let due_day = due.with_timezone(&Local).date_naive();
+
+        Some(if due < now {
+            DueState::Overdue
+        } else if due_day == today {
+            DueState::Today
+        } else if due_day <= today + Duration::days(soon_days) {
+            DueState::Soon
+        } else {
+            DueState::Later
+        })
+    }
+
+    /// Notes that the item was moved on from `from`.
+    pub fn record_migration(&mut self, from: NaiveDate) {
+        // Items migrated before the history was kept only know their last move.
+        if self.migration_history.is_empty()
+            && let Some(last) = self.migrated_from
+        {
+            self.migration_history.push(last);
+        }
+
+        self.migration_history.push(from);
+        self.migrated_from = Some(from);
+    }
+
+    /// How many times the item has been migrated.
+    pub fn migrations(&self) -> usize {
+        self.migration_history
+            .len()
+            .max(usize::from(self.migrated_from.is_some()))
+    }
+
+    /// Whole days since the item was created, in local time.
+    pub fn age_days(&self, today: NaiveDate) -> i64 {
+        (today - self.created_at.with_timezone(&Local).date_naive()).num_days()
+    }
+
+    /// How many pomodoros have been completed on the item.
+    pub fn pomodoros(&self) -> usize {
+        self.sessions.iter().filter(|s| s.pomodoro).count()
+    }
+
+    /// The total time logged against the item.
+    pub fn logged(&self) -> Duration {
+        self.sessions.iter().map(WorkSession::duration).sum()
+    }
+
+    /// How many subtasks are done out of how many, `None` without subtasks.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        if self.subtasks.is_empty() {
+            return None;
+        }
+
+        let done = self.subtasks.iter().filter(|s| s.done_at.is_some()).count();
+        Some((done, self.subtasks.len()))
+    }
+
+    /// Marks the item done, along with any subtasks still open.
+    pub fn mark_done(&mut self, at: DateTime<Utc>) {
+        let at = *self.done_at.get_or_insert(at);
+
+        for subtask in self.subtasks.iter_mut().filter(|s| s.done_at.is_none()) {
+            subtask.done_at = Some(at);
+        }
+    }
+
+    /// Opens the item again. Its subtasks keep their state.
+    pub fn mark_undone(&mut self) {
+        self.done_at = None;
+    }
+
+    /// Keeps the item's done state in step with its subtasks after one of them
+    /// changes: done once they're all done, open again while any is open.
+    pub fn sync_with_subtasks(&mut self) {
+        let Some((done, total)) = self.progress() else {
+            return;
+        };
+
+        if done == total {
+            let last = self.subtasks.iter().filter_map(|s| s.done_at).max();
+            self.done_at = self.done_at.or(last);
+        } else {
+            self.done_at = None;
+        }
+    }
+
+    /// Replaces the text, swapping the `#tags` written in the old text for the
+    /// ones in the new. Tags added any other way are kept.
+    pub fn set_text(&mut self, text: String) {
+        let written = extract_tags(&self.text);
+        self.tags
+            .retain(|t| !written.iter().any(|w| w.eq_ignore_ascii_case(t)));
+
+        for tag in extract_tags(&text) {
+            self.add_tag(&tag);
+        }
+
+        self.text = text;
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim().trim_start_matches('#');
+
+        if !tag.is_empty() && !self.has_tag(tag) {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    /// Removes a tag, and the `#tag` from the text if it's written there.
+    pub fn remove_tag(&mut self, tag: &str) {
+        let tag = tag.trim().trim_start_matches('#');
+
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        self.text = strip_words(&self.text, |w| {
+            w.strip_prefix('#')
+                .is_some_and(|t| t.eq_ignore_ascii_case(tag))
+        });
+    }
+
+    /// Removes every tag, and every `#tag` written in the text.
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+        self.text = strip_words(&self.text, |w| w.starts_with('#'));
+    }
+
+    /// Takes the finished subtasks out of an open item so only the rest moves
+    /// on. They come back as a done copy of the item to leave on the old day,
+    /// or `None` if there were none.
+    pub fn split_finished(&mut self) -> Option<Item> {
+        if self.done_at.is_some() || !self.subtasks.iter().any(|s| s.done_at.is_some()) {
+            return None;
+        }
+
+        let (finished, open): (Vec<Subtask>, Vec<Subtask>) = std::mem::take(&mut self.subtasks)
+            .into_iter()
+            .partition(|s| s.done_at.is_some());
+
+        self.subtasks = open;
+
+        let mut done = self.clone();
+        done.id = nanoid!(6);
+        done.subtasks = finished;
+        done.sync_with_subtasks();
+
+        Some(done)
+    }
+}
+
+fn strip_words(text: &str, strip: impl Fn(&str) -> bool) -> String {
+    text.split_whitespace()
+        .filter(|w| !strip(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str) -> Item {
+        let mut item = Item::new(String::new(), ItemPriority::Low, Vec::new(), None);
+        item.set_text(text.to_string());
+        item
+    }
+
+    #[test]
+    fn set_text_swaps_written_tags() {
+        let mut i = item("call #old");
+        i.add_tag("kept");
+
+        i.set_text("call #new".to_string());
+
+        assert_eq!(i.tags, ["kept", "new"]);
+    }
+
+    #[test]
+    fn mark_done_finishes_open_subtasks() {
+        let mut i = item("parent");
+        i.subtasks.push(Subtask::new("a".to_string()));
+        i.subtasks.push(Subtask::new("b".to_string()));
+
+        let now = Utc::now();
+        i.mark_done(now);
+
+        assert_eq!(i.done_at, Some(now));
+        assert!(i.subtasks.iter().all(|s| s.done_at == Some(now)));
+
+        i.mark_undone();
+        assert!(i.done_at.is_none());
+    }
+
+    #[test]
+    fn subtasks_keep_the_item_in_step() {
+        let mut i = item("parent");
+        i.subtasks.push(Subtask::new("a".to_string()));
+        i.subtasks.push(Subtask::new("b".to_string()));
+
+        i.subtasks[0].done_at = Some(Utc::now());
+        i.sync_with_subtasks();
+        assert!(i.done_at.is_none());
+
+        i.subtasks[1].done_at = Some(Utc::now());
+        i.sync_with_subtasks();
+        assert!(i.done_at.is_some());
+    }
 }
@@ -0,0 +1,173 @@
+use core::fmt;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How many operations the journal keeps. Older ones are dropped as it
+/// grows and can no longer be undone.
+pub const JOURNAL_LIMIT: usize = 200;
+
+/// A file in a vault that the journal tracks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum JournalFile {
+    Day { date: NaiveDate },
+    Focus,
+    Recurring,
+}
+
+impl fmt::Display for JournalFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalFile::Day { date } => write!(f, "{date}"),
+            JournalFile::Focus => f.write_str("focus"),
+            JournalFile::Recurring => f.write_str("recurring"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum OperationKind {
+    Do,
+    Undo { target: String },
+    Redo { target: String },
+}
+
+/// One line of the journal: a single file write made by an operation.
+/// Every write made by one invocation shares the same `op`, so an operation
+/// touching several files is undone and redone as one step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub op: String,
+    pub kind: OperationKind,
+    pub command: String,
+    pub at: DateTime<Utc>,
+    pub file: JournalFile,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub id: String,
+    pub kind: OperationKind,
+    pub command: String,
+    pub at: DateTime<Utc>,
+    pub changes: Vec<JournalEntry>,
+}
+
+impl Operation {
+    pub fn files(&self) -> Vec<&JournalFile> {
+        let mut files: Vec<&JournalFile> = Vec::new();
+
+        for change in &self.changes {
+            if !files.contains(&&change.file) {
+                files.push(&change.file);
+            }
+        }
+
+        files
+    }
+
+    /// Each file the operation touched, with its state before the first
+    /// write and after the last one.
+    pub fn net_changes(&self) -> Vec<(&JournalFile, Option<&Value>, Option<&Value>)> {
+        self.files()
+            .into_iter()
+            .map(|file| {
+                let mut writes = self.changes.iter().filter(|c| c.file == *file);
+                let first = writes.next();
+                let last = writes.next_back().or(first);
+
+                (
+                    file,
+                    first.and_then(|c| c.before.as_ref()),
+                    last.and_then(|c| c.after.as_ref()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// A check for entries read newest first, true until they reach past the
+/// last `limit` operations.
+pub fn within_last(limit: usize) -> impl FnMut(&JournalEntry) -> bool {
+    let mut seen = 0;
+    let mut current: Option<String> = None;
+
+    move |entry| {
+        if current.as_deref() != Some(entry.op.as_str()) {
+            seen += 1;
+            current = Some(entry.op.clone());
+        }
+
+        seen <= limit
+    }
+}
+
+pub struct Journal {
+    pub operations: Vec<Operation>,
+}
+
+impl Journal {
+    pub fn new(entries: Vec<JournalEntry>) -> Self {
+        let mut operations: Vec<Operation> = Vec::new();
+
+        for entry in entries {
+            match operations.last_mut() {
+                Some(op) if op.id == entry.op => op.changes.push(entry),
+                _ => operations.push(Operation {
+                    id: entry.op.clone(),
+                    kind: entry.kind.clone(),
+                    command: entry.command.clone(),
+                    at: entry.at,
+                    changes: vec![entry],
+                }),
+            }
+        }
+
+        Self { operations }
+    }
+
+    /// Replays the history into the stack of applied operations and the
+    /// stack of undone operations, most recent last.
+    fn stacks(&self) -> (Vec<&Operation>, Vec<&Operation>) {
+        let mut applied: Vec<&Operation> = Vec::new();
+        let mut undone: Vec<&Operation> = Vec::new();
+
+        for op in &self.operations {
+            match &op.kind {
+                OperationKind::Do => {
+                    applied.push(op);
+                    undone.clear();
+                }
+                OperationKind::Undo { target } => {
+                    if let Some(pos) = applied.iter().rposition(|o| o.id == *target) {
+                        undone.push(applied.remove(pos));
+                    }
+                }
+                OperationKind::Redo { target } => {
+                    if let Some(pos) = undone.iter().rposition(|o| o.id == *target) {
+                        applied.push(undone.remove(pos));
+                    }
+                }
+            }
+        }
+
+        (applied, undone)
+    }
+
+    pub fn next_undo(&self) -> Option<&Operation> {
+        self.stacks().0.pop()
+    }
+
+    pub fn next_redo(&self) -> Option<&Operation> {
+        self.stacks().1.pop()
+    }
+
+    pub fn is_undone(&self, id: &str) -> bool {
+        self.stacks().1.iter().any(|o| o.id == id)
+    }
+}
@@ -0,0 +1,437 @@
+use core::fmt;
+use std::{num::NonZeroU32, str::FromStr};
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::item::{Item, ItemPriority},
+    utils::{dates::start_of_day, helpers::extract_tags},
+};
+
+/// How far ahead `next_on_or_after` looks before giving up on a schedule.
+const MAX_LOOKAHEAD_DAYS: u64 = 366 * 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Schedule {
+    Daily,
+    Weekdays,
+    /// Zero would never come round, so it's refused when read back too.
+    EveryNDays(NonZeroU32),
+    Weekly(Vec<Weekday>),
+    Monthly(u32),
+    Cron(String),
+}
+
+impl Schedule {
+    pub fn matches(&self, date: NaiveDate, start: NaiveDate) -> bool {
+        if date < start {
+            return false;
+        }
+
+        match self {
+            Schedule::Daily => true,
+            Schedule::Weekdays => !matches!(date.weekday(), Weekday::Sat | Weekday::Sun),
+            Schedule::EveryNDays(n) => (date - start).num_days() % i64::from(n.get()) == 0,
+            Schedule::Weekly(days) => days.contains(&date.weekday()),
+            Schedule::Monthly(day) => {
+                // Days past the end of a short month land on its last day.
+                let last = last_day_of_month(date);
+                date.day() == *day || (*day > last && date.day() == last)
+            }
+            Schedule::Cron(expr) => CronExpr::parse(expr).is_ok_and(|c| c.matches(date)),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Daily => f.write_str("daily"),
+            Schedule::Weekdays => f.write_str("weekdays"),
+            Schedule::EveryNDays(n) => write!(f, "{n}d"),
+            Schedule::Weekly(days) => {
+                let days = days
+                    .iter()
+                    .map(|d| d.to_string().to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                f.write_str(&days)
+            }
+            Schedule::Monthly(day) => write!(f, "monthly:{day}"),
+            Schedule::Cron(expr) => write!(f, "cron:{expr}"),
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let invalid = || {
+            format!(
+                "Invalid schedule '{s}'. Use daily, weekdays, 3d, mon,thu, monthly:15 \
+                 or cron:'<day-of-month> <month> <day-of-week>'"
+            )
+        };
+
+        match s.as_str() {
+            "daily" | "day" => return Ok(Schedule::Daily),
+            "weekdays" | "weekday" => return Ok(Schedule::Weekdays),
+            _ => {}
+        }
+
+        if let Some(expr) = s.strip_prefix("cron:") {
+            let expr = expr.trim();
+            CronExpr::parse(expr)?;
+            return Ok(Schedule::Cron(expr.to_string()));
+        }
+
+        if let Some(day) = s.strip_prefix("monthly:") {
+            return match day.parse::<u32>() {
+                Ok(d) if (1..=31).contains(&d) => Ok(Schedule::Monthly(d)),
+                _ => Err(invalid()),
+            };
+        }
+
+        if let Some(n) = s.strip_suffix('d')
+            && let Ok(n) = n.parse::<u32>()
+        {
+            return NonZeroU32::new(n)
+                .map(Schedule::EveryNDays)
+                .ok_or_else(invalid);
+        }
+
+        let days = s
+            .split(',')
+            .map(|d| d.trim().parse::<Weekday>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+
+        Ok(Schedule::Weekly(days))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub id: String,
+    pub text: String,
+    pub priority: ItemPriority,
+    pub tags: Vec<String>,
+    pub schedule: Schedule,
+    pub start: NaiveDate,
+    pub paused: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecurrenceRule {
+    pub fn new(text: String, priority: ItemPriority, schedule: Schedule, start: NaiveDate) -> Self {
+        Self {
+            id: nanoid!(6),
+            tags: extract_tags(&text),
+            text,
+            priority,
+            schedule,
+            start,
+            paused: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_due_on(&self, date: NaiveDate) -> bool {
+        !self.paused && self.schedule.matches(date, self.start)
+    }
+
+    pub fn next_on_or_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let from = date.max(self.start);
+
+        from.iter_days()
+            .take_while(|d| *d <= from + Days::new(MAX_LOOKAHEAD_DAYS))
+            .find(|d| self.schedule.matches(*d, self.start))
+    }
+
+    /// The item this rule places into the day file for `date`. It's worked out
+    /// from the two alone, so a read-only vault, which can't save the day,
+    /// shows the same item every time.
+    pub fn to_item(&self, date: NaiveDate) -> Item {
+        let mut item = Item::new(self.text.clone(), self.priority, self.tags.clone(), None);
+        item.id = item_id(&self.id, date);
+        item.created_at = start_of_day(date);
+        item.recurrence_id = Some(self.id.clone());
+        item
+    }
+}
+
+/// FNV-1a of the rule id and date, which unlike the std hashers gives the
+/// same id from one build to the next.
+fn item_id(rule_id: &str, date: NaiveDate) -> String {
+    const ALPHABET: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_-";
+
+    let hash = format!("{rule_id}@{date}")
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    (0..6)
+        .map(|n| ALPHABET[(hash >> (n * 6)) as usize & 63] as char)
+        .collect()
+}
+
+/// A date-only cron expression: `<day-of-month> <month> <day-of-week>`.
+/// A full five-field expression is accepted too, its minute and hour are ignored.
+struct CronExpr<'a> {
+    day_of_month: &'a str,
+    month: &'a str,
+    day_of_week: &'a str,
+}
+
+impl<'a> CronExpr<'a> {
+    fn parse(expr: &'a str) -> Result<Self, String> {
+        let fields = expr.split_whitespace().collect::<Vec<_>>();
+
+        let (dom, month, dow) = match fields.as_slice() {
+            [dom, month, dow] | [_, _, dom, month, dow] => (*dom, *month, *dow),
+            _ => {
+                return Err(format!(
+                    "Invalid cron expression '{expr}', expected '<day-of-month> <month> <day-of-week>'"
+                ));
+            }
+        };
+
+        let cron = Self {
+            day_of_month: dom,
+            month,
+            day_of_week: dow,
+        };
+
+        // Validate every field once up front so matching can't fail later.
+        field_matches(cron.day_of_month, 1, 1, 31)?;
+        field_matches(cron.month, 1, 1, 12)?;
+        field_matches(cron.day_of_week, 0, 0, 7)?;
+
+        Ok(cron)
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        let dow = date.weekday().num_days_from_sunday();
+        let month_ok = field_matches(self.month, date.month(), 1, 12).unwrap_or(false);
+        let dom_ok = field_matches(self.day_of_month, date.day(), 1, 31).unwrap_or(false);
+        let dow_ok = field_matches(self.day_of_week, dow, 0, 7).unwrap_or(false)
+            || (dow == 0 && field_matches(self.day_of_week, 7, 0, 7).unwrap_or(false));
+
+        // Like cron, when both day fields are restricted either may match.
+        let day_ok = match (self.day_of_month == "*", self.day_of_week == "*") {
+            (false, false) => dom_ok || dow_ok,
+            _ => dom_ok && dow_ok,
+        };
+
+        month_ok && day_ok
+    }
+}
+
+fn field_matches(field: &str, value: u32, min: u32, max: u32) -> Result<bool, String> {
+    let invalid = || format!("Invalid cron field '{field}'");
+    let mut matched = false;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+
+        if step == 0 {
+            return Err(invalid());
+        }
+
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (
+                a.parse::<u32>().map_err(|_| invalid())?,
+                b.parse::<u32>().map_err(|_| invalid())?,
+            )
+        } else {
+            let n = range.parse::<u32>().map_err(|_| invalid())?;
+            (n, if part.contains('/') { max } else { n })
+        };
+
+        if lo < min || hi > max || lo > hi {
+            return Err(invalid());
+        }
+
+        if (lo..=hi).contains(&value) && (value - lo).is_multiple_of(step) {
+            matched = true;
+        }
+    }
+
+    Ok(matched)
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    let (y, m) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+
+    NaiveDate::from_ymd_opt(y, m, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dates::ymd;
+
+    fn schedule(s: &str) -> Schedule {
+        s.parse().unwrap()
+    }
+
+    /// The days in `from..=to` the schedule falls on, starting at `from`.
+    fn days(s: &str, from: &str, to: &str) -> Vec<u32> {
+        let (from, to) = (ymd(from), ymd(to));
+        let schedule = schedule(s);
+
+        from.iter_days()
+            .take_while(|d| *d <= to)
+            .filter(|d| schedule.matches(*d, from))
+            .map(|d| d.day())
+            .collect()
+    }
+
+    #[test]
+    fn parses_schedules() {
+        assert_eq!(schedule("Daily"), Schedule::Daily);
+        assert_eq!(
+            schedule("3d"),
+            Schedule::EveryNDays(NonZeroU32::new(3).unwrap())
+        );
+        assert_eq!(
+            schedule("mon, thu"),
+            Schedule::Weekly(vec![Weekday::Mon, Weekday::Thu])
+        );
+        assert_eq!(schedule("monthly:31"), Schedule::Monthly(31));
+        assert_eq!(
+            schedule("cron: 1,15 * *"),
+            Schedule::Cron("1,15 * *".into())
+        );
+
+        for bad in [
+            "0d",
+            "monthly:32",
+            "fortnightly",
+            "cron:* *",
+            "cron:32 * *",
+            "cron:*/0 * *",
+        ] {
+            assert!(bad.parse::<Schedule>().is_err(), "{bad} should be refused");
+        }
+    }
+
+    #[test]
+    fn schedules_display_as_they_parse() {
+        for s in [
+            "daily",
+            "weekdays",
+            "3d",
+            "mon,thu",
+            "monthly:15",
+            "cron:1 */2 1-5",
+        ] {
+            assert_eq!(schedule(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn matches_days_from_the_start() {
+        // 2025-09-01 is a Monday.
+        assert_eq!(days("weekdays", "2025-09-05", "2025-09-09"), [5, 8, 9]);
+        assert_eq!(days("3d", "2025-09-01", "2025-09-10"), [1, 4, 7, 10]);
+        assert_eq!(days("tue,sat", "2025-09-01", "2025-09-10"), [2, 6, 9]);
+        assert!(!schedule("daily").matches(ymd("2025-08-31"), ymd("2025-09-01")));
+    }
+
+    #[test]
+    fn monthly_days_past_the_end_land_on_the_last_day() {
+        assert_eq!(days("monthly:31", "2025-02-01", "2025-02-28"), [28]);
+        assert_eq!(days("monthly:30", "2024-02-01", "2024-02-29"), [29]);
+        assert_eq!(days("monthly:15", "2025-02-01", "2025-02-28"), [15]);
+    }
+
+    #[test]
+    fn cron_days() {
+        // Either day field may match once both are restricted.
+        assert_eq!(
+            days("cron:13 * 5", "2025-06-01", "2025-06-30"),
+            [6, 13, 20, 27]
+        );
+        assert_eq!(
+            days("cron:1-10/3 * *", "2025-06-01", "2025-06-30"),
+            [1, 4, 7, 10]
+        );
+        // Both 0 and 7 are Sunday, and the minute and hour of a full expression are ignored.
+        assert_eq!(days("cron:* * 7", "2025-06-01", "2025-06-10"), [1, 8]);
+        assert_eq!(days("cron:30 9 * * 0", "2025-06-01", "2025-06-10"), [1, 8]);
+        assert!(days("cron:* 7 *", "2025-06-01", "2025-06-30").is_empty());
+    }
+
+    #[test]
+    fn next_on_or_after_skips_to_the_schedule() {
+        let mut rule = RecurrenceRule::new(
+            "Pay rent".into(),
+            ItemPriority::High,
+            schedule("monthly:1"),
+            ymd("2025-09-15"),
+        );
+
+        assert_eq!(
+            rule.next_on_or_after(ymd("2025-09-01")),
+            Some(ymd("2025-10-01"))
+        );
+        assert!(rule.is_due_on(ymd("2025-10-01")));
+
+        rule.paused = true;
+        assert!(!rule.is_due_on(ymd("2025-10-01")));
+
+        rule.schedule = schedule("cron:31 2 *");
+        assert_eq!(rule.next_on_or_after(ymd("2025-09-01")), None);
+    }
+
+    #[test]
+    fn items_keep_their_id_for_a_day() {
+        let mut rule = RecurrenceRule::new(
+            "Water #plants".into(),
+            ItemPriority::Low,
+            Schedule::Daily,
+            ymd("2025-09-01"),
+        );
+        rule.id = "water1".into();
+        let item = rule.to_item(ymd("2025-09-02"));
+
+        assert_eq!(item.id, "7bZfmx");
+        assert_eq!(item.id, rule.to_item(ymd("2025-09-02")).id);
+        assert_ne!(item.id, rule.to_item(ymd("2025-09-03")).id);
+        assert_eq!(item.created_at, start_of_day(ymd("2025-09-02")));
+        assert_eq!(item.recurrence_id.as_deref(), Some(rule.id.as_str()));
+        assert_eq!(item.tags, ["plants"]);
+    }
+
+    #[test]
+    fn zero_day_schedules_are_refused_when_read() {
+        let read = |n: u32| {
+            serde_json::from_str::<Schedule>(&format!(r#"{{"kind":"every_n_days","value":{n}}}"#))
+        };
+
+        assert_eq!(
+            read(2).unwrap(),
+            Schedule::EveryNDays(NonZeroU32::new(2).unwrap())
+        );
+        assert!(read(0).is_err());
+    }
+}
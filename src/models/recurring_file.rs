@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::recurrence::RecurrenceRule;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RecurringFile {
+    pub rules: Vec<RecurrenceRule>,
+}
+
+impl RecurringFile {
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+
+use chrono::format::{Item, StrftimeItems};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    display::terminal::{DATE_FORMAT, DATE_WITH_TIME_FORMAT, DUE_FORMAT, DUE_WITH_TIME_FORMAT},
+    models::item::ItemPriority,
+    utils::{list_scope::ListScope, render::RenderOutput},
+};
+
+/// The longest `review-days` can be set to, matching `tusk review`'s own cap.
+pub const MAX_REVIEW_DAYS: u64 = 365;
+
+/// Every key `tusk config` knows about, besides `alias.<name>`.
+pub const SETTING_KEYS: &[&str] = &[
+    "default-priority",
+    "output",
+    "list-scope",
+    "review-days",
+    "editor",
+    "date-format",
+    "date-time-format",
+    "due-format",
+    "due-time-format",
+    "colour",
+    "checkbox-open",
+    "checkbox-done",
+    "rollover",
+    "pomodoro-hook",
+];
+
+/// User defaults, read from the global config file and from each vault's
+/// `config.json`. Anything left unset falls back to the built-in default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_priority: Option<ItemPriority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<RenderOutput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_scope: Option<ListScope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_days: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_time_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_time_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colour: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkbox_open: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkbox_done: Option<String>,
+    /// Move open tasks forward to today on the first run of the day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollover: Option<bool>,
+    /// A command run at each pomodoro transition, e.g. `notify-send tusk`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pomodoro_hook: Option<String>,
+    /// Extra command names, e.g. `today = "ls --scope all"`.
+    #[serde(default, rename = "alias", skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl Settings {
+    /// These settings with anything unset filled in from `base`.
+    pub fn or(&self, base: &Settings) -> Settings {
+        let mut aliases = base.aliases.clone();
+        aliases.extend(self.aliases.clone());
+
+        Settings {
+            default_priority: self.default_priority.or(base.default_priority),
+            output: self.output.or(base.output),
+            list_scope: self.list_scope.or(base.list_scope),
+            review_days: self.review_days.or(base.review_days),
+            editor: self.editor.clone().or(base.editor.clone()),
+            date_format: self.date_format.clone().or(base.date_format.clone()),
+            date_time_format: self
+                .date_time_format
+                .clone()
+                .or(base.date_time_format.clone()),
+            due_format: self.due_format.clone().or(base.due_format.clone()),
+            due_time_format: self
+                .due_time_format
+                .clone()
+                .or(base.due_time_format.clone()),
+            colour: self.colour.or(base.colour),
+            checkbox_open: self.checkbox_open.clone().or(base.checkbox_open.clone()),
+            checkbox_done: self.checkbox_done.clone().or(base.checkbox_done.clone()),
+            rollover: self.rollover.or(base.rollover),
+            pomodoro_hook: self.pomodoro_hook.clone().or(base.pomodoro_hook.clone()),
+            aliases,
+        }
+    }
+
+    pub fn default_priority(&self) -> ItemPriority {
+        self.default_priority.unwrap_or(ItemPriority::Low)
+    }
+
+    pub fn list_scope(&self) -> ListScope {
+        self.list_scope.unwrap_or(ListScope::Day)
+    }
+
+    pub fn review_days(&self) -> u64 {
+        self.review_days.unwrap_or(1)
+    }
+
+    pub fn rollover(&self) -> bool {
+        self.rollover.unwrap_or(false)
+    }
+
+    /// Every key set here plus the built-in ones, in display order.
+    pub fn keys(&self) -> Vec<String> {
+        SETTING_KEYS
+            .iter()
+            .map(|k| k.to_string())
+            .chain(self.aliases.keys().map(|a| format!("alias.{a}")))
+            .collect()
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>, String> {
+        if let Some(name) = key.strip_prefix("alias.") {
+            return Ok(self.aliases.get(name).cloned());
+        }
+
+        let value = match key {
+            "default-priority" => self.default_priority.map(|p| p.to_string()),
+            "output" => self.output.map(value_name),
+            "list-scope" => self.list_scope.map(value_name),
+            "review-days" => self.review_days.map(|d| d.to_string()),
+            "editor" => self.editor.clone(),
+            "date-format" => self.date_format.clone(),
+            "date-time-format" => self.date_time_format.clone(),
+            "due-format" => self.due_format.clone(),
+            "due-time-format" => self.due_time_format.clone(),
+            "colour" => self.colour.map(|c| c.to_string()),
+            "checkbox-open" => self.checkbox_open.clone(),
+            "checkbox-done" => self.checkbox_done.clone(),
+            "rollover" => self.rollover.map(|r| r.to_string()),
+            "pomodoro-hook" => self.pomodoro_hook.clone(),
+            _ => return Err(unknown_key(key)),
+        };
+
+        Ok(value)
+    }
+
+    /// Sets `key` from its text form, or clears it when `value` is `None`.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), String> {
+        if let Some(name) = key.strip_prefix("alias.") {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(format!("Invalid alias name '{name}'"));
+            }
+
+            match value {
+                Some(v) if !v.trim().is_empty() => {
+                    self.aliases.insert(name.to_string(), v.trim().to_string())
+                }
+                _ => self.aliases.remove(name),
+            };
+
+            return Ok(());
+        }
+
+        match key {
+            "default-priority" => self.default_priority = value.map(parse_enum).transpose()?,
+            "output" => self.output = value.map(parse_enum).transpose()?,
+            "list-scope" => self.list_scope = value.map(parse_enum).transpose()?,
+            "review-days" => self.review_days = value.map(parse_review_days).transpose()?,
+            "editor" => self.editor = value.map(|v| v.to_string()),
+            "date-format" => self.date_format = value.map(parse_format).transpose()?,
+            "date-time-format" => self.date_time_format = value.map(parse_format).transpose()?,
+            "due-format" => self.due_format = value.map(parse_format).transpose()?,
+            "due-time-format" => self.due_time_format = value.map(parse_format).transpose()?,
+            "colour" => self.colour = value.map(parse_bool).transpose()?,
+            "checkbox-open" => self.checkbox_open = value.map(|v| v.to_string()),
+            "checkbox-done" => self.checkbox_done = value.map(|v| v.to_string()),
+            "rollover" => self.rollover = value.map(parse_bool).transpose()?,
+            "pomodoro-hook" => self.pomodoro_hook = value.map(|v| v.to_string()),
+            _ => return Err(unknown_key(key)),
+        }
+
+        Ok(())
+    }
+
+    /// What a key falls back to when nothing sets it.
+    pub fn builtin(key: &str) -> Option<&'static str> {
+        match key {
+            "default-priority" => Some("low"),
+            "output" => Some("terminal"),
+            "list-scope" => Some("day"),
+            "review-days" => Some("1"),
+            "editor" => Some("$EDITOR, $VISUAL or nano"),
+            "date-format" => Some(DATE_FORMAT),
+            "date-time-format" => Some(DATE_WITH_TIME_FORMAT),
+            "due-format" => Some(DUE_FORMAT),
+            "due-time-format" => Some(DUE_WITH_TIME_FORMAT),
+            "colour" => Some("true"),
+            "checkbox-open" => Some("☐ or [ ] without colour"),
+            "checkbox-done" => Some("☑ or [x] without colour"),
+            "rollover" => Some("false"),
+            "pomodoro-hook" => Some("none, the terminal bell only"),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a chrono format string only uses specifiers chrono understands,
+/// formatting with an unknown one would otherwise panic.
+pub fn is_valid_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|i| matches!(i, Item::Error))
+}
+
+fn unknown_key(key: &str) -> String {
+    format!(
+        "Unknown config key '{key}'. Use one of {} or alias.<name>",
+        SETTING_KEYS.join(", ")
+    )
+}
+
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn parse_enum<T: ValueEnum>(value: &str) -> Result<T, String> {
+    T::from_str(value.trim(), true).map_err(|_| {
+        let allowed = T::value_variants()
+            .iter()
+            .filter_map(|v| v.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("Invalid value '{value}', expected one of {allowed}")
+    })
+}
+
+fn parse_review_days(value: &str) -> Result<u64, String> {
+    match value.trim().parse::<u64>() {
+        Ok(d) if (1..=MAX_REVIEW_DAYS).contains(&d) => Ok(d),
+        _ => Err(format!(
+            "Invalid value '{value}', expected a number of days from 1 to {MAX_REVIEW_DAYS}"
+        )),
+    }
+}
+
+fn parse_format(value: &str) -> Result<String, String> {
+    if is_valid_format(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "Invalid date format '{value}', see chrono's strftime specifiers"
+        ))
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(format!("Invalid value '{value}', expected true or false")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_a_toml_config() {
+        let settings: Settings = toml::from_str(
+            r#"
+            default-priority = "high"
+            review-days = 7
+            rollover = true
+
+            [alias]
+            today = "ls --scope all"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.default_priority(), ItemPriority::High);
+        assert_eq!(settings.review_days(), 7);
+        assert!(settings.rollover());
+        assert_eq!(settings.aliases["today"], "ls --scope all");
+        assert_eq!(settings.list_scope(), ListScope::Day);
+    }
+
+    #[test]
+    fn vault_settings_win_and_aliases_merge() {
+        let mut global = Settings::default();
+        global.set("review-days", Some("7")).unwrap();
+        global.set("alias.today", Some("ls")).unwrap();
+        global.set("alias.wk", Some("week")).unwrap();
+
+        let mut vault = Settings::default();
+        vault.set("review-days", Some("3")).unwrap();
+        vault.set("alias.today", Some("ls --scope all")).unwrap();
+
+        let merged = vault.or(&global);
+        assert_eq!(merged.review_days(), 3);
+        assert_eq!(merged.aliases["today"], "ls --scope all");
+        assert_eq!(merged.aliases["wk"], "week");
+    }
+
+    #[test]
+    fn set_checks_values_and_get_reads_them_back() {
+        let mut settings = Settings::default();
+
+        settings.set("output", Some("JSON")).unwrap();
+        settings.set("colour", Some("off")).unwrap();
+        assert_eq!(settings.get("output").unwrap().as_deref(), Some("json"));
+        assert_eq!(settings.get("colour").unwrap().as_deref(), Some("false"));
+
+        assert!(settings.set("review-days", Some("0")).is_err());
+        assert!(settings.set("review-days", Some("366")).is_err());
+        assert!(settings.set("date-format", Some("%Q")).is_err());
+        assert!(settings.set("colour", Some("maybe")).is_err());
+        assert!(settings.set("nope", Some("1")).is_err());
+        assert!(settings.get("nope").is_err());
+
+        settings.set("output", None).unwrap();
+        assert_eq!(settings.get("output").unwrap(), None);
+    }
+
+    #[test]
+    fn aliases_need_a_name_and_clear_when_blank() {
+        let mut settings = Settings::default();
+
+        assert!(settings.set("alias.", Some("ls")).is_err());
+        assert!(settings.set("alias.to day", Some("ls")).is_err());
+
+        settings.set("alias.today", Some("  ls  ")).unwrap();
+        assert_eq!(settings.get("alias.today").unwrap().as_deref(), Some("ls"));
+        assert!(settings.keys().contains(&"alias.today".to_string()));
+
+        settings.set("alias.today", Some(" ")).unwrap();
+        assert!(settings.aliases.is_empty());
+    }
+}
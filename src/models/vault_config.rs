@@ -0,0 +1,67 @@
+use core::fmt;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{item::WorkSession, settings::Settings},
+    utils::task_target::TaskTarget,
+};
+
+/// Where a vault keeps its days and focus tasks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    /// One JSON file per day, the default.
+    #[default]
+    Json,
+    /// A single SQLite database, needs tusk built with the `sqlite` feature.
+    Sqlite,
+}
+
+impl fmt::Display for StoreBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreBackend::Json => f.write_str("json"),
+            StoreBackend::Sqlite => f.write_str("sqlite"),
+        }
+    }
+}
+
+/// Settings stored alongside a vault's data.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultConfig {
+    #[serde(default)]
+    pub store: StoreBackend,
+    /// The last day open items were rolled over into, so it only happens once a day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rolled_over_on: Option<NaiveDate>,
+    /// The timer started with `tusk start`, a vault has at most one running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timer: Option<ActiveTimer>,
+    /// Overrides the global config for this vault only.
+    #[serde(flatten)]
+    pub settings: Settings,
+}
+
+/// A running timer on an item, logged as a session when it's stopped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveTimer {
+    pub item_id: String,
+    /// The list the item was on when the timer started.
+    pub target: TaskTarget,
+    pub date: NaiveDate,
+    pub started_at: DateTime<Utc>,
+}
+
+impl ActiveTimer {
+    /// The session to log on the item for a timer stopped at `now`.
+    pub fn session(&self, now: DateTime<Utc>) -> WorkSession {
+        WorkSession {
+            start: self.started_at,
+            end: now,
+            pomodoro: false,
+        }
+    }
+}
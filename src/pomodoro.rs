@@ -0,0 +1,210 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    process::Command,
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+
+use crate::{
+    CommandContext,
+    models::item::{Item, WorkSession},
+    utils::{
+        dates::format_minutes,
+        files::{load_focus_or_empty, save_focusfile},
+        tusk_error::TuskError,
+    },
+    view::pomodoro::PomodoroPlan,
+};
+
+/// A transition between pomodoro phases, passed to the hook as `TUSK_POMODORO_EVENT`.
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    Work,
+    Break,
+    Finished,
+}
+
+impl Event {
+    fn as_str(self) -> &'static str {
+        match self {
+            Event::Work => "work",
+            Event::Break => "break",
+            Event::Finished => "finished",
+        }
+    }
+}
+
+/// Counts down each round in the foreground, logging every finished work
+/// phase on the focus item with `id` as it goes, so stopping early with
+/// Ctrl-C keeps the rounds already done. Returns how many rounds finished.
+///
+/// The vault is only locked while a round is being saved, so other tusk
+/// commands can run in between.
+pub fn run(ctx: &CommandContext, id: &str, plan: &PomodoroPlan) -> Result<u32, TuskError> {
+    let mut completed = 0;
+
+    for round in 1..=plan.rounds {
+        let text = current(ctx, id)?.text;
+        let label = format!("Round {round}/{}", plan.rounds);
+
+        let message = format!(
+            "{label}: work on '{text}' for {}",
+            format_minutes(plan.work.num_minutes())
+        );
+        notify(ctx, Event::Work, round, plan, &text, &message)?;
+
+        let start = Utc::now();
+        countdown(&format!("🍅 {label} · work"), plan.work)?;
+
+        let session = WorkSession {
+            start,
+            end: Utc::now(),
+            pomodoro: true,
+        };
+        log_round(ctx, id, session)?;
+        completed += 1;
+
+        if round == plan.rounds {
+            let message = format!("All {} round(s) on '{text}' done", plan.rounds);
+            notify(ctx, Event::Finished, round, plan, &text, &message)?;
+            break;
+        }
+
+        let message = format!(
+            "{label} done, take a {} break",
+            format_minutes(plan.rest.num_minutes())
+        );
+        notify(ctx, Event::Break, round, plan, &text, &message)?;
+        countdown(&format!("☕ {label} · break"), plan.rest)?;
+    }
+
+    Ok(completed)
+}
+
+/// Asks on stderr whether to mark the item done, only when someone is there
+/// to answer.
+pub fn ask_done(item: &Item) -> Result<bool, TuskError> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    let mut err = io::stderr().lock();
+    write!(err, "Mark '{}' done? [y/N] ", item.text)?;
+    err.flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn current(ctx: &CommandContext, id: &str) -> Result<Item, TuskError> {
+    let _lock = ctx.lock()?;
+
+    load_focus_or_empty(ctx)?
+        .items
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| TuskError::ItemNotFound {
+            reference: format!("@{id}"),
+        })
+}
+
+fn log_round(ctx: &CommandContext, id: &str, session: WorkSession) -> Result<(), TuskError> {
+    let _lock = ctx.lock()?;
+    let mut ff = load_focus_or_empty(ctx)?;
+
+    let item = ff
+        .items
+        .iter_mut()
+        .find(|i| i.id == id)
+        .ok_or_else(|| TuskError::ItemNotFound {
+            reference: format!("@{id}"),
+        })?;
+
+    item.sessions.push(session);
+    save_focusfile(ctx, &ff)?;
+
+    Ok(())
+}
+
+/// Shows the time left on one line of stderr, or nothing when it isn't a terminal.
+fn countdown(label: &str, length: chrono::Duration) -> io::Result<()> {
+    let length = length.to_std().unwrap_or_default();
+    let end = Instant::now() + length;
+    let mut err = io::stderr();
+    let live = err.is_terminal();
+
+    loop {
+        let left = end.saturating_duration_since(Instant::now());
+
+        if live {
+            let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+            write!(
+                err,
+                "\r\x1b[2K{label} · {:02}:{:02} left",
+                secs / 60,
+                secs % 60
+            )?;
+            err.flush()?;
+        }
+
+        if left.is_zero() {
+            break;
+        }
+
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+
+    if live {
+        writeln!(err)?;
+    }
+
+    Ok(())
+}
+
+/// Rings the terminal bell and runs the `pomodoro-hook`, if one is set, with
+/// the message as its last argument. A hook that fails only warns.
+fn notify(
+    ctx: &CommandContext,
+    event: Event,
+    round: u32,
+    plan: &PomodoroPlan,
+    text: &str,
+    message: &str,
+) -> io::Result<()> {
+    let mut err = io::stderr();
+
+    if err.is_terminal() {
+        write!(err, "\x07")?;
+    }
+    writeln!(err, "{message}")?;
+
+    let Some(hook) = ctx.settings.pomodoro_hook.as_deref() else {
+        return Ok(());
+    };
+
+    let mut words = hook.split_whitespace();
+    let Some(program) = words.next() else {
+        return Ok(());
+    };
+
+    let status = Command::new(program)
+        .args(words)
+        .arg(message)
+        .env("TUSK_POMODORO_EVENT", event.as_str())
+        .env("TUSK_POMODORO_ROUND", round.to_string())
+        .env("TUSK_POMODORO_ROUNDS", plan.rounds.to_string())
+        .env("TUSK_ITEM_TEXT", text)
+        // Keeps stdout for the command's own output, e.g. JSON.
+        .stdout(io::stderr())
+        .status();
+
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(s) => writeln!(err, "warn: pomodoro-hook exited with {s}"),
+        Err(e) => writeln!(err, "warn: couldn't run pomodoro-hook '{hook}': {e}"),
+    }
+}
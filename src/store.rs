@@ -1,3 +1,14 @@
 pub mod day_store;
 pub mod focus_store;
-pub mod fs;
\ No newline at end of file
+pub mod fs;
+pub mod journal_store;
+#[cfg(test)]
+pub mod memory;
+pub mod read_only;
+pub mod recurring_store;
+pub mod settings_store;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod vault_config_store;
+pub mod vault_registry;
+pub mod vault_store;
@@ -1,9 +1,44 @@
-use std::io::Error;
 use chrono::NaiveDate;
+use std::io::Error;
 
 use crate::models::dayfile::DayFile;
 
+/// A day read by one of the cross-day queries. A day that couldn't be read
+/// comes back with its error, so the others still can be.
+pub type LoadedDay = (NaiveDate, Result<DayFile, Error>);
+
 pub trait DayStore {
     fn load(&self, date: NaiveDate) -> Result<DayFile, Error>;
     fn save(&self, df: &DayFile) -> Result<(), Error>;
+    fn delete(&self, date: NaiveDate) -> Result<(), Error>;
+    /// Every date that has a stored day file, oldest first.
+    fn dates(&self) -> Result<Vec<NaiveDate>, Error>;
+
+    /// The stored days from `from` to `to`, inclusive, oldest first. Either end
+    /// is left open when `None`. Reads each day in turn unless the backend can
+    /// do better.
+    fn load_range(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<LoadedDay>, Error> {
+        Ok(self
+            .dates()?
+            .into_iter()
+            .filter(|d| from.is_none_or(|f| *d >= f) && to.is_none_or(|t| *d <= t))
+            .map(|d| (d, self.load(d)))
+            .collect())
+    }
+
+    /// The stored days with at least one item that has a due date, oldest first.
+    fn load_with_due(&self) -> Result<Vec<LoadedDay>, Error> {
+        Ok(self
+            .load_range(None, None)?
+            .into_iter()
+            .filter(|(_, df)| {
+                df.as_ref()
+                    .map_or(true, |df| df.items.iter().any(|i| i.due.is_some()))
+            })
+            .collect())
+    }
 }
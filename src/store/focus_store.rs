@@ -5,4 +5,5 @@ use crate::models::focus_file::FocusFile;
 pub trait FocusStore {
     fn load(&self) -> Result<FocusFile, Error>;
     fn save(&self, ff: &FocusFile) -> Result<(), Error>;
+    fn delete(&self) -> Result<(), Error>;
 }
\ No newline at end of file
@@ -1,3 +1,9 @@
 pub mod day_store;
 pub mod focus_store;
-mod shared;
\ No newline at end of file
+pub mod journal_store;
+pub mod lock;
+pub mod recurring_store;
+pub mod settings_store;
+pub(crate) mod shared;
+pub mod vault_config_store;
+pub mod vault_registry;
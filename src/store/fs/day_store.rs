@@ -1,4 +1,7 @@
-use std::{io, path::PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 use chrono::{Datelike, NaiveDate};
 
@@ -6,7 +9,7 @@ use crate::{
     models::dayfile::DayFile,
     store::{
         day_store::DayStore,
-        fs::shared::{normalise_or_default, read_json, save_to_json, tusk_data_root},
+        fs::shared::{normalise_or_default, read_json, remove_json, save_to_json, tusk_data_root},
     },
 };
 
@@ -23,13 +26,17 @@ impl FsDayStore {
         })
     }
 
+    fn vault_dir(&self) -> PathBuf {
+        self.base_dir
+            .join("vaults")
+            .join(normalise_or_default(self.vault.as_deref()))
+    }
+
     pub fn dayfile_path(&self, date: &NaiveDate) -> PathBuf {
         let year = date.year();
         let month = date.month();
 
-        self.base_dir
-            .join("vaults")
-            .join(normalise_or_default(self.vault.as_deref()))
+        self.vault_dir()
             .join(format!("{:04}", year))
             .join(format!("{:02}", month))
             .join(format!("{}.json", date))
@@ -46,4 +53,49 @@ impl DayStore for FsDayStore {
         let path = self.dayfile_path(&df.date);
         save_to_json(&path, df)
     }
+
+    fn delete(&self, date: NaiveDate) -> Result<(), std::io::Error> {
+        let path = self.dayfile_path(&date);
+        remove_json(&path)
+    }
+
+    fn dates(&self) -> Result<Vec<NaiveDate>, std::io::Error> {
+        let mut dates = Vec::new();
+
+        for year in sub_dirs(&self.vault_dir())? {
+            for month in sub_dirs(&year)? {
+                for entry in fs::read_dir(&month)? {
+                    let path = entry?.path();
+
+                    if path.extension().is_some_and(|e| e == "json")
+                        && let Some(date) = path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                    {
+                        dates.push(date);
+                    }
+                }
+            }
+        }
+
+        dates.sort();
+        Ok(dates)
+    }
+}
+
+fn sub_dirs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+
+    Ok(dirs)
 }
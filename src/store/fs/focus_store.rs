@@ -7,7 +7,7 @@ use crate::{
     models::focus_file::FocusFile,
     store::{
         focus_store::FocusStore,
-        fs::shared::{normalise_or_default, read_json, save_to_json, tusk_data_root},
+        fs::shared::{normalise_or_default, read_json, remove_json, save_to_json, tusk_data_root},
     },
 };
 
@@ -42,4 +42,9 @@ impl FocusStore for FsFocusStore {
         let path = self.focusfile_path();
         save_to_json(&path, ff)
     }
+
+    fn delete(&self) -> Result<(), Error> {
+        let path = self.focusfile_path();
+        remove_json(&path)
+    }
 }
@@ -0,0 +1,172 @@
+use std::{
+    fs,
+    io::{self, Error},
+    path::PathBuf,
+};
+
+use crate::{
+    models::journal::{JOURNAL_LIMIT, JournalEntry, within_last},
+    store::{
+        fs::shared::{
+            append_json_line, normalise_or_default, read_json_lines_rev, save_json_lines,
+            tusk_data_root,
+        },
+        journal_store::JournalStore,
+    },
+};
+
+/// Past this size the journal is cut back to its last `JOURNAL_LIMIT`
+/// operations when it's next written to.
+const COMPACT_AT: u64 = 4 * 1024 * 1024;
+
+pub struct FsJournalStore {
+    pub base_dir: PathBuf,
+    pub vault: Option<String>,
+}
+
+impl FsJournalStore {
+    pub fn new(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            base_dir: base_dir.unwrap_or(tusk_data_root()?),
+            vault: vault.map(|v| v.to_string()),
+        })
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.base_dir
+            .join("vaults")
+            .join(normalise_or_default(self.vault.as_deref()))
+            .join("journal.jsonl")
+    }
+
+    /// The entries of the last `limit` operations, oldest first, and whether
+    /// there were older ones.
+    fn load_tail(&self, limit: usize) -> Result<(Vec<JournalEntry>, bool), Error> {
+        let mut keep = within_last(limit);
+        let mut entries = Vec::new();
+
+        let older = read_json_lines_rev(&self.journal_path(), |entry: JournalEntry| {
+            let kept = keep(&entry);
+            if kept {
+                entries.push(entry);
+            }
+            kept
+        })?;
+
+        entries.reverse();
+        Ok((entries, older))
+    }
+
+    /// Cuts the journal back to its last `JOURNAL_LIMIT` operations, and
+    /// further to half of `COMPACT_AT` so the next write doesn't compact it
+    /// again. The newest operation is always kept.
+    fn compact(&self) -> Result<(), Error> {
+        let (entries, older) = self.load_tail(JOURNAL_LIMIT)?;
+        let Some(newest) = entries.last().map(|e| e.op.clone()) else {
+            return Ok(());
+        };
+
+        let sizes = entries
+            .iter()
+            .map(|e| serde_json::to_vec(e).map(|line| line.len() as u64 + 1))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut size = sizes.iter().sum::<u64>();
+        let mut cut = 0;
+
+        while size > COMPACT_AT / 2 && entries[cut].op != newest {
+            let op = entries[cut].op.clone();
+
+            while entries[cut].op == op {
+                size -= sizes[cut];
+                cut += 1;
+            }
+        }
+
+        if older || cut > 0 {
+            save_json_lines(&self.journal_path(), &entries[cut..])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl JournalStore for FsJournalStore {
+    fn load(&self, limit: usize) -> Result<Vec<JournalEntry>, Error> {
+        Ok(self.load_tail(limit)?.0)
+    }
+
+    fn append(&self, entry: &JournalEntry) -> Result<(), Error> {
+        let path = self.journal_path();
+        append_json_line(&path, entry)?;
+
+        if fs::metadata(&path)?.len() > COMPACT_AT {
+            self.compact()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::models::journal::{JournalFile, OperationKind};
+
+    fn entry(op: usize, padding: &str) -> JournalEntry {
+        JournalEntry {
+            op: format!("op{op}"),
+            kind: OperationKind::Do,
+            command: "add".to_string(),
+            at: Utc::now(),
+            file: JournalFile::Focus,
+            before: None,
+            after: Some(Value::String(padding.to_string())),
+        }
+    }
+
+    #[test]
+    fn long_journals_keep_their_last_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsJournalStore::new(Some(dir.path().to_path_buf()), None).unwrap();
+        let padding = "x".repeat(10 * 1024);
+
+        // Two writes per operation, enough to go past `COMPACT_AT`.
+        for op in 0..300 {
+            store.append(&entry(op, &padding)).unwrap();
+            store.append(&entry(op, &padding)).unwrap();
+        }
+
+        let (entries, older) = store.load_tail(usize::MAX).unwrap();
+        assert!(!older);
+        assert!(entries.len() < 600);
+        assert!(fs::metadata(store.journal_path()).unwrap().len() <= COMPACT_AT);
+        assert_eq!(entries.last().unwrap().op, "op299");
+
+        // Operations are kept or dropped whole.
+        let kept = store.load(JOURNAL_LIMIT).unwrap();
+        assert_eq!(kept.len() % 2, 0);
+        assert_eq!(kept[1].op, kept[0].op);
+
+        let last = store.load(1).unwrap();
+        assert_eq!(last.len(), 2);
+        assert!(last.iter().all(|e| e.op == "op299"));
+    }
+
+    #[test]
+    fn loading_reads_only_the_last_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsJournalStore::new(Some(dir.path().to_path_buf()), None).unwrap();
+
+        for op in 0..JOURNAL_LIMIT + 50 {
+            store.append(&entry(op, "")).unwrap();
+        }
+
+        let kept = store.load(JOURNAL_LIMIT).unwrap();
+        assert_eq!(kept.len(), JOURNAL_LIMIT);
+        assert_eq!(kept[0].op, "op50");
+        assert_eq!(kept.last().unwrap().op, format!("op{}", JOURNAL_LIMIT + 49));
+    }
+}
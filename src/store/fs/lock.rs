@@ -0,0 +1,36 @@
+use std::{
+    fs::{File, OpenOptions, create_dir_all},
+    io,
+    path::PathBuf,
+};
+
+use crate::store::fs::shared::{normalise_or_default, tusk_data_root};
+
+/// An exclusive advisory lock on a vault, released when dropped.
+///
+/// Commands hold it across their whole read-modify-write cycle so two
+/// invocations running at once can't overwrite each other's changes.
+pub struct VaultLock {
+    _file: File,
+}
+
+impl VaultLock {
+    /// Blocks until no other tusk process holds the vault's lock.
+    pub fn acquire(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        let vault_dir = base_dir
+            .unwrap_or(tusk_data_root()?)
+            .join("vaults")
+            .join(normalise_or_default(vault));
+
+        create_dir_all(&vault_dir)?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(vault_dir.join(".lock"))?;
+        file.lock()?;
+
+        Ok(Self { _file: file })
+    }
+}
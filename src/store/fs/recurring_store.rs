@@ -0,0 +1,50 @@
+use std::{
+    io::{self, Error},
+    path::PathBuf,
+};
+
+use crate::{
+    models::recurring_file::RecurringFile,
+    store::{
+        fs::shared::{normalise_or_default, read_json, remove_json, save_to_json, tusk_data_root},
+        recurring_store::RecurringStore,
+    },
+};
+
+pub struct FsRecurringStore {
+    pub base_dir: PathBuf,
+    pub vault: Option<String>,
+}
+
+impl FsRecurringStore {
+    pub fn new(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            base_dir: base_dir.unwrap_or(tusk_data_root()?),
+            vault: vault.map(|v| v.to_string()),
+        })
+    }
+
+    fn recurring_path(&self) -> PathBuf {
+        self.base_dir
+            .join("vaults")
+            .join(normalise_or_default(self.vault.as_deref()))
+            .join("recurring.json")
+    }
+}
+
+impl RecurringStore for FsRecurringStore {
+    fn load(&self) -> Result<RecurringFile, Error> {
+        let path = self.recurring_path();
+        read_json(&path)
+    }
+
+    fn save(&self, rf: &RecurringFile) -> Result<(), Error> {
+        let path = self.recurring_path();
+        save_to_json(&path, rf)
+    }
+
+    fn delete(&self) -> Result<(), Error> {
+        let path = self.recurring_path();
+        remove_json(&path)
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    io::{self, Error, ErrorKind},
+    path::PathBuf,
+};
+
+use directories::ProjectDirs;
+
+use crate::{
+    models::settings::Settings,
+    store::{
+        fs::shared::{read_json, save_to_json, write_atomically},
+        settings_store::SettingsStore,
+    },
+};
+
+/// A TOML config file, or JSON if its name ends in `.json`.
+pub struct FsSettingsStore {
+    pub path: PathBuf,
+}
+
+impl FsSettingsStore {
+    /// Uses `path` if given, otherwise `config.toml` in the platform config dir.
+    pub fn new(path: Option<PathBuf>) -> io::Result<Self> {
+        let path = match path {
+            Some(p) => p,
+            None => ProjectDirs::from("io", "jonnothebonno", "tusk")
+                .map(|d| d.config_dir().join("config.toml"))
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::NotFound,
+                        "could not determine platform config directory.",
+                    )
+                })?,
+        };
+
+        Ok(Self { path })
+    }
+
+    fn is_json(&self) -> bool {
+        self.path.extension().is_some_and(|e| e == "json")
+    }
+}
+
+impl SettingsStore for FsSettingsStore {
+    fn load(&self) -> Result<Settings, Error> {
+        if self.is_json() {
+            return read_json(&self.path);
+        }
+
+        if !self.path.exists() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("failed to load config at {}", self.path.display()),
+            ));
+        }
+
+        let text = fs::read_to_string(&self.path)?;
+        toml::from_str(&text).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("failed to parse config in {}: {}", self.path.display(), e),
+            )
+        })
+    }
+
+    fn save(&self, settings: &Settings) -> Result<(), Error> {
+        if self.is_json() {
+            return save_to_json(&self.path, settings);
+        }
+
+        let text = toml::to_string_pretty(settings).map_err(Error::other)?;
+        write_atomically(&self.path, text.as_bytes())
+    }
+}
@@ -1,13 +1,14 @@
 use std::{
-    fs::{File, create_dir_all},
-    io::{self, BufReader, BufWriter, Error, ErrorKind, Write},
+    fs::{File, OpenOptions, create_dir_all, remove_file},
+    io::{self, BufReader, Error, ErrorKind, Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
 };
 
 use directories::ProjectDirs;
 use serde::{Serialize, de::DeserializeOwned};
+use tempfile::NamedTempFile;
 
-pub(super) fn normalise_or_default(vault: Option<&str>) -> String {
+pub(crate) fn normalise_or_default(vault: Option<&str>) -> String {
     match vault {
         None => "default".to_string(),
         Some(s) => {
@@ -27,7 +28,29 @@ pub(super) fn normalise_or_default(vault: Option<&str>) -> String {
     }
 }
 
-pub(super) fn tusk_data_root() -> io::Result<PathBuf> {
+/// Checks a vault name given on the command line. Names are case-insensitive,
+/// anything else `normalise_or_default` would strip is refused so two different
+/// names can't quietly end up in the same vault.
+pub(crate) fn vault_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim().to_ascii_lowercase();
+    let filtered = normalise_or_default(Some(&trimmed));
+
+    if trimmed.is_empty() || (filtered == "default" && trimmed != "default") {
+        return Err(format!(
+            "'{name}' isn't a valid vault name, use letters, numbers, '-' and '_'"
+        ));
+    }
+
+    if filtered != trimmed {
+        return Err(format!(
+            "'{name}' isn't a valid vault name, only letters, numbers, '-' and '_' are allowed. Did you mean '{filtered}'?"
+        ));
+    }
+
+    Ok(filtered)
+}
+
+pub(crate) fn tusk_data_root() -> io::Result<PathBuf> {
     let root = match ProjectDirs::from("io", "jonnothebonno", "tusk") {
         Some(proj_dir) => proj_dir.data_dir().to_owned(),
         None => {
@@ -52,7 +75,7 @@ where
         ));
     }
 
-    let file = File::open(&path)?;
+    let file = File::open(path)?;
     let reader = BufReader::new(file);
 
     serde_json::from_reader(reader).map_err(|e| {
@@ -63,7 +86,132 @@ where
     })
 }
 
+/// Writes `data` to a temporary file next to `path`, syncs it and renames it
+/// into place, so a crash mid-write never leaves a truncated file behind.
 pub(super) fn save_to_json<T>(path: &Path, data: &T) -> Result<(), std::io::Error>
+where
+    T: Serialize,
+{
+    let mut bytes = serde_json::to_vec_pretty(data)?;
+    bytes.push(b'\n');
+
+    write_atomically(path, &bytes)
+}
+
+pub(super) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), std::io::Error> {
+    let parent_path = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    if !parent_path.exists() {
+        create_dir_all(parent_path)?;
+    }
+
+    let mut tmp = NamedTempFile::new_in(parent_path)?;
+    tmp.write_all(bytes)?;
+    tmp.as_file().sync_all()?;
+    tmp.persist(path).map_err(|e| e.error)?;
+
+    sync_dir(parent_path)
+}
+
+/// Makes a rename in `dir` durable. Directories can't be opened for syncing
+/// on every platform, so this is best effort outside of unix.
+fn sync_dir(dir: &Path) -> Result<(), std::io::Error> {
+    if cfg!(unix) {
+        File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn remove_json(path: &Path) -> Result<(), std::io::Error> {
+    match remove_file(path) {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        other => other,
+    }
+}
+
+/// Reads a JSON lines file from its end, newest line first, handing each
+/// value to `more` until it returns false. Returns whether lines were left
+/// unread, so a long file is only read as far back as needed.
+pub(super) fn read_json_lines_rev<T>(
+    path: &Path,
+    mut more: impl FnMut(T) -> bool,
+) -> Result<bool, std::io::Error>
+where
+    T: DeserializeOwned,
+{
+    const CHUNK: u64 = 64 * 1024;
+
+    let mut file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    let mut pos = file.metadata()?.len();
+    // The start of a line whose beginning is in an earlier chunk.
+    let mut partial = Vec::new();
+
+    while pos > 0 {
+        let start = pos.saturating_sub(CHUNK);
+        let mut chunk = vec![0; (pos - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.append(&mut partial);
+        pos = start;
+
+        let complete = match chunk.iter().position(|b| *b == b'\n') {
+            _ if pos == 0 => 0,
+            Some(i) => i + 1,
+            None => {
+                partial = chunk;
+                continue;
+            }
+        };
+
+        for line in chunk[complete..].split(|b| *b == b'\n').rev() {
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+
+            let value = serde_json::from_slice(line).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("failed to parse a line of {}: {}", path.display(), e),
+                )
+            })?;
+
+            if !more(value) {
+                return Ok(true);
+            }
+        }
+
+        chunk.truncate(complete);
+        partial = chunk;
+    }
+
+    Ok(false)
+}
+
+/// Replaces a JSON lines file with `values`, atomically like `save_to_json`.
+pub(super) fn save_json_lines<T>(path: &Path, values: &[T]) -> Result<(), std::io::Error>
+where
+    T: Serialize,
+{
+    let mut bytes = Vec::new();
+
+    for value in values {
+        serde_json::to_writer(&mut bytes, value)?;
+        bytes.push(b'\n');
+    }
+
+    write_atomically(path, &bytes)
+}
+
+pub(super) fn append_json_line<T>(path: &Path, data: &T) -> Result<(), std::io::Error>
 where
     T: Serialize,
 {
@@ -73,11 +221,13 @@ where
         create_dir_all(parent_path)?;
     }
 
-    let file = File::create(path)?;
-    let mut writer = BufWriter::new(file);
-    serde_json::to_writer_pretty(&mut writer, data)?;
-    writer.write_all(b"\n")?;
-    writer.flush()?;
+    let mut line = serde_json::to_vec(data)?;
+    line.push(b'\n');
+
+    // One write per line so an interrupted append can't interleave entries.
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&line)?;
+    file.flush()?;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,45 @@
+use std::{
+    io::{self, Error},
+    path::PathBuf,
+};
+
+use crate::{
+    models::vault_config::VaultConfig,
+    store::{
+        fs::shared::{normalise_or_default, read_json, save_to_json, tusk_data_root},
+        vault_config_store::VaultConfigStore,
+    },
+};
+
+pub struct FsVaultConfigStore {
+    pub base_dir: PathBuf,
+    pub vault: Option<String>,
+}
+
+impl FsVaultConfigStore {
+    pub fn new(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            base_dir: base_dir.unwrap_or(tusk_data_root()?),
+            vault: vault.map(|v| v.to_string()),
+        })
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.base_dir
+            .join("vaults")
+            .join(normalise_or_default(self.vault.as_deref()))
+            .join("config.json")
+    }
+}
+
+impl VaultConfigStore for FsVaultConfigStore {
+    fn load(&self) -> Result<VaultConfig, Error> {
+        let path = self.config_path();
+        read_json(&path)
+    }
+
+    fn save(&self, config: &VaultConfig) -> Result<(), Error> {
+        let path = self.config_path();
+        save_to_json(&path, config)
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    io::{self, Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::{
+    fs::{
+        lock::VaultLock,
+        shared::{read_json, save_to_json, tusk_data_root, vault_name},
+    },
+    vault_registry::VaultRegistry,
+    vault_store::{DiskStore, VaultStore},
+};
+
+/// Kept in the data directory, so each `--data-dir` remembers its own vault.
+#[derive(Debug, Serialize, Deserialize)]
+struct CurrentVault {
+    vault: String,
+}
+
+pub struct FsVaultRegistry {
+    pub base_dir: PathBuf,
+}
+
+impl FsVaultRegistry {
+    pub fn new(base_dir: Option<PathBuf>) -> io::Result<Self> {
+        Ok(Self {
+            base_dir: base_dir.unwrap_or(tusk_data_root()?),
+        })
+    }
+
+    fn vault_path(&self, name: &str) -> PathBuf {
+        self.base_dir.join("vaults").join(name)
+    }
+
+    fn current_path(&self) -> PathBuf {
+        self.base_dir.join("current.json")
+    }
+
+    fn ensure_free(&self, name: &str) -> Result<(), Error> {
+        if self.exists(name) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("a vault named '{name}' already exists"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_exists(&self, name: &str) -> Result<(), Error> {
+        if !self.exists(name) {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("there's no vault named '{name}'"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl VaultRegistry for FsVaultRegistry {
+    fn names(&self) -> Result<Vec<String>, Error> {
+        let root = self.base_dir.join("vaults");
+
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = Vec::new();
+
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+
+            // A folder made by hand that tusk couldn't address is left out.
+            if let Some(name) = entry.file_name().to_str()
+                && vault_name(name).is_ok_and(|n| n == name)
+            {
+                names.push(name.to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.vault_path(name).is_dir()
+    }
+
+    fn create(&self, name: &str) -> Result<(), Error> {
+        self.ensure_free(name)?;
+        fs::create_dir_all(self.vault_path(name))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error> {
+        self.ensure_exists(from)?;
+        self.ensure_free(to)?;
+        fs::rename(self.vault_path(from), self.vault_path(to))
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error> {
+        self.ensure_exists(from)?;
+        self.ensure_free(to)?;
+        copy_dir(&self.vault_path(from), &self.vault_path(to))
+    }
+
+    fn remove(&self, name: &str) -> Result<(), Error> {
+        self.ensure_exists(name)?;
+        fs::remove_dir_all(self.vault_path(name))
+    }
+
+    fn current(&self) -> Result<Option<String>, Error> {
+        match read_json::<CurrentVault>(&self.current_path()) {
+            Ok(current) => Ok(Some(current.vault)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn set_current(&self, name: &str) -> Result<(), Error> {
+        save_to_json(
+            &self.current_path(),
+            &CurrentVault {
+                vault: name.to_string(),
+            },
+        )
+    }
+    fn open(&self, name: &str) -> Result<Box<dyn VaultStore>, Error> {
+        Ok(Box::new(DiskStore::open(
+            Some(self.base_dir.clone()),
+            Some(name),
+        )?))
+    }
+
+    fn lock(&self, name: &str) -> Result<Option<VaultLock>, Error> {
+        VaultLock::acquire(Some(self.base_dir.clone()), Some(name)).map(Some)
+    }
+}
+
+/// Copies a vault folder, leaving out its lock file.
+fn copy_dir(from: &Path, to: &Path) -> Result<(), Error> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if entry.file_name() != ".lock" {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use std::io::Error;
+
+use crate::models::journal::JournalEntry;
+
+pub trait JournalStore {
+    /// The entries of the last `limit` operations, oldest first.
+    fn load(&self, limit: usize) -> Result<Vec<JournalEntry>, Error>;
+    fn append(&self, entry: &JournalEntry) -> Result<(), Error>;
+}
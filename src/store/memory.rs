@@ -0,0 +1,330 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::{self, Error},
+    rc::Rc,
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    models::{
+        dayfile::DayFile,
+        focus_file::FocusFile,
+        journal::{JournalEntry, within_last},
+        recurring_file::RecurringFile,
+        vault_config::{StoreBackend, VaultConfig},
+    },
+    store::{
+        day_store::DayStore, focus_store::FocusStore, fs::lock::VaultLock,
+        journal_store::JournalStore, recurring_store::RecurringStore,
+        vault_config_store::VaultConfigStore, vault_registry::VaultRegistry,
+        vault_store::VaultStore,
+    },
+};
+
+/// A vault that only lives in memory, so commands can be exercised without
+/// touching the real data directory. The vaults next to it, and the copy a
+/// store migration makes, are kept in memory too.
+pub struct MemoryStore {
+    vault: Rc<MemoryVault>,
+    registry: Rc<MemoryRegistry>,
+}
+
+/// The files of one vault.
+#[derive(Default, Clone)]
+pub struct MemoryVault {
+    days: RefCell<BTreeMap<NaiveDate, DayFile>>,
+    focus: RefCell<Option<FocusFile>>,
+    recurring: RefCell<Option<RecurringFile>>,
+    journal: RefCell<Vec<JournalEntry>>,
+    config: RefCell<Option<VaultConfig>>,
+}
+
+/// The vaults of an in-memory data directory, starting with just `default`.
+#[derive(Default)]
+pub struct MemoryRegistry {
+    vaults: RefCell<BTreeMap<String, Rc<MemoryVault>>>,
+    current: RefCell<Option<String>>,
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        let vault = Rc::new(MemoryVault::default());
+        let registry = MemoryRegistry::default();
+        registry
+            .vaults
+            .borrow_mut()
+            .insert("default".to_string(), vault.clone());
+
+        Self {
+            vault,
+            registry: Rc::new(registry),
+        }
+    }
+}
+
+fn not_found(what: &str) -> Error {
+    Error::new(io::ErrorKind::NotFound, format!("no {what} in memory"))
+}
+
+impl VaultStore for MemoryStore {
+    fn days(&self) -> &dyn DayStore {
+        self.vault.as_ref()
+    }
+
+    fn focus(&self) -> &dyn FocusStore {
+        self.vault.as_ref()
+    }
+
+    fn recurring(&self) -> &dyn RecurringStore {
+        self.vault.as_ref()
+    }
+
+    fn journal(&self) -> &dyn JournalStore {
+        self.vault.as_ref()
+    }
+
+    fn config(&self) -> &dyn VaultConfigStore {
+        self.vault.as_ref()
+    }
+
+    fn registry(&self) -> &dyn VaultRegistry {
+        &self.registry
+    }
+
+    /// An empty vault of its own, standing in for the other backend's files.
+    fn open_backend(&self, _backend: StoreBackend) -> io::Result<Box<dyn VaultStore>> {
+        Ok(Box::new(Self {
+            vault: Rc::default(),
+            registry: self.registry.clone(),
+        }))
+    }
+
+    fn lock(&self) -> io::Result<Option<VaultLock>> {
+        Ok(None)
+    }
+}
+
+impl MemoryRegistry {
+    fn get(&self, name: &str) -> Result<Rc<MemoryVault>, Error> {
+        self.vaults
+            .borrow()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| not_found(&format!("vault named '{name}'")))
+    }
+
+    fn ensure_free(&self, name: &str) -> Result<(), Error> {
+        if self.vaults.borrow().contains_key(name) {
+            return Err(Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("a vault named '{name}' already exists"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// On the `Rc` so the vaults it opens share it.
+impl VaultRegistry for Rc<MemoryRegistry> {
+    fn names(&self) -> Result<Vec<String>, Error> {
+        Ok(self.vaults.borrow().keys().cloned().collect())
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.vaults.borrow().contains_key(name)
+    }
+
+    fn create(&self, name: &str) -> Result<(), Error> {
+        self.ensure_free(name)?;
+        self.vaults
+            .borrow_mut()
+            .insert(name.to_string(), Rc::default());
+        Ok(())
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error> {
+        let vault = self.get(from)?;
+        self.ensure_free(to)?;
+
+        let mut vaults = self.vaults.borrow_mut();
+        vaults.remove(from);
+        vaults.insert(to.to_string(), vault);
+        Ok(())
+    }
+
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error> {
+        let copy = self.get(from)?.as_ref().clone();
+        self.ensure_free(to)?;
+
+        self.vaults
+            .borrow_mut()
+            .insert(to.to_string(), Rc::new(copy));
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<(), Error> {
+        self.get(name)?;
+        self.vaults.borrow_mut().remove(name);
+        Ok(())
+    }
+
+    fn current(&self) -> Result<Option<String>, Error> {
+        Ok(self.current.borrow().clone())
+    }
+
+    fn set_current(&self, name: &str) -> Result<(), Error> {
+        *self.current.borrow_mut() = Some(name.to_string());
+        Ok(())
+    }
+
+    fn open(&self, name: &str) -> Result<Box<dyn VaultStore>, Error> {
+        Ok(Box::new(MemoryStore {
+            vault: self.get(name)?,
+            registry: self.clone(),
+        }))
+    }
+
+    fn lock(&self, _name: &str) -> Result<Option<VaultLock>, Error> {
+        Ok(None)
+    }
+}
+
+impl DayStore for MemoryVault {
+    fn load(&self, date: NaiveDate) -> Result<DayFile, Error> {
+        self.days
+            .borrow()
+            .get(&date)
+            .cloned()
+            .ok_or_else(|| not_found("day file"))
+    }
+
+    fn save(&self, df: &DayFile) -> Result<(), Error> {
+        self.days.borrow_mut().insert(df.date, df.clone());
+        Ok(())
+    }
+
+    fn delete(&self, date: NaiveDate) -> Result<(), Error> {
+        self.days.borrow_mut().remove(&date);
+        Ok(())
+    }
+
+    fn dates(&self) -> Result<Vec<NaiveDate>, Error> {
+        Ok(self.days.borrow().keys().copied().collect())
+    }
+}
+
+impl FocusStore for MemoryVault {
+    fn load(&self) -> Result<FocusFile, Error> {
+        self.focus
+            .borrow()
+            .clone()
+            .ok_or_else(|| not_found("focus file"))
+    }
+
+    fn save(&self, ff: &FocusFile) -> Result<(), Error> {
+        *self.focus.borrow_mut() = Some(ff.clone());
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<(), Error> {
+        *self.focus.borrow_mut() = None;
+        Ok(())
+    }
+}
+
+impl RecurringStore for MemoryVault {
+    fn load(&self) -> Result<RecurringFile, Error> {
+        self.recurring
+            .borrow()
+            .clone()
+            .ok_or_else(|| not_found("recurring file"))
+    }
+
+    fn save(&self, rf: &RecurringFile) -> Result<(), Error> {
+        *self.recurring.borrow_mut() = Some(rf.clone());
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<(), Error> {
+        *self.recurring.borrow_mut() = None;
+        Ok(())
+    }
+}
+
+impl JournalStore for MemoryVault {
+    fn load(&self, limit: usize) -> Result<Vec<JournalEntry>, Error> {
+        let mut keep = within_last(limit);
+        let mut entries = self
+            .journal
+            .borrow()
+            .iter()
+            .rev()
+            .take_while(|e| keep(e))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        entries.reverse();
+        Ok(entries)
+    }
+
+    fn append(&self, entry: &JournalEntry) -> Result<(), Error> {
+        self.journal.borrow_mut().push(entry.clone());
+        Ok(())
+    }
+}
+
+impl VaultConfigStore for MemoryVault {
+    fn load(&self) -> Result<VaultConfig, Error> {
+        self.config
+            .borrow()
+            .clone()
+            .ok_or_else(|| not_found("vault config"))
+    }
+
+    fn save(&self, config: &VaultConfig) -> Result<(), Error> {
+        *self.config.borrow_mut() = Some(config.clone());
+        Ok(())
+    }
+}
+
+/// Lets a test keep hold of the store while commands consume their context.
+impl<S: VaultStore> VaultStore for Rc<S> {
+    fn days(&self) -> &dyn DayStore {
+        self.as_ref().days()
+    }
+
+    fn focus(&self) -> &dyn FocusStore {
+        self.as_ref().focus()
+    }
+
+    fn recurring(&self) -> &dyn RecurringStore {
+        self.as_ref().recurring()
+    }
+
+    fn journal(&self) -> &dyn JournalStore {
+        self.as_ref().journal()
+    }
+
+    fn config(&self) -> &dyn VaultConfigStore {
+        self.as_ref().config()
+    }
+
+    fn registry(&self) -> &dyn VaultRegistry {
+        self.as_ref().registry()
+    }
+
+    fn open_backend(&self, backend: StoreBackend) -> io::Result<Box<dyn VaultStore>> {
+        self.as_ref().open_backend(backend)
+    }
+
+    fn lock(&self) -> io::Result<Option<VaultLock>> {
+        self.as_ref().lock()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.as_ref().is_read_only()
+    }
+}
@@ -0,0 +1,159 @@
+use std::io::{self, Error};
+
+use chrono::NaiveDate;
+
+use crate::{
+    models::{
+        dayfile::DayFile,
+        focus_file::FocusFile,
+        journal::JournalEntry,
+        recurring_file::RecurringFile,
+        vault_config::{StoreBackend, VaultConfig},
+    },
+    store::{
+        day_store::{DayStore, LoadedDay},
+        focus_store::FocusStore,
+        fs::lock::VaultLock,
+        journal_store::JournalStore,
+        recurring_store::RecurringStore,
+        vault_config_store::VaultConfigStore,
+        vault_registry::VaultRegistry,
+        vault_store::VaultStore,
+    },
+};
+
+/// Wraps another store and refuses every write, for browsing an archive
+/// without any risk of changing it.
+pub struct ReadOnlyStore {
+    inner: Box<dyn VaultStore>,
+}
+
+impl ReadOnlyStore {
+    pub fn new(inner: Box<dyn VaultStore>) -> Self {
+        Self { inner }
+    }
+}
+
+pub(crate) fn refused() -> Error {
+    Error::new(
+        io::ErrorKind::PermissionDenied,
+        "the vault is open read-only, drop --read-only to make changes",
+    )
+}
+
+impl VaultStore for ReadOnlyStore {
+    fn days(&self) -> &dyn DayStore {
+        self
+    }
+
+    fn focus(&self) -> &dyn FocusStore {
+        self
+    }
+
+    fn recurring(&self) -> &dyn RecurringStore {
+        self
+    }
+
+    fn journal(&self) -> &dyn JournalStore {
+        self
+    }
+
+    fn config(&self) -> &dyn VaultConfigStore {
+        self
+    }
+
+    fn registry(&self) -> &dyn VaultRegistry {
+        self.inner.registry()
+    }
+
+    fn open_backend(&self, backend: StoreBackend) -> io::Result<Box<dyn VaultStore>> {
+        Ok(Box::new(Self::new(self.inner.open_backend(backend)?)))
+    }
+
+    /// Nothing is written, so there's nothing to lock.
+    fn lock(&self) -> io::Result<Option<VaultLock>> {
+        Ok(None)
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+}
+
+impl DayStore for ReadOnlyStore {
+    fn load(&self, date: NaiveDate) -> Result<DayFile, Error> {
+        self.inner.days().load(date)
+    }
+
+    fn save(&self, _df: &DayFile) -> Result<(), Error> {
+        Err(refused())
+    }
+
+    fn delete(&self, _date: NaiveDate) -> Result<(), Error> {
+        Err(refused())
+    }
+
+    fn dates(&self) -> Result<Vec<NaiveDate>, Error> {
+        self.inner.days().dates()
+    }
+
+    fn load_range(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<LoadedDay>, Error> {
+        self.inner.days().load_range(from, to)
+    }
+
+    fn load_with_due(&self) -> Result<Vec<LoadedDay>, Error> {
+        self.inner.days().load_with_due()
+    }
+}
+
+impl FocusStore for ReadOnlyStore {
+    fn load(&self) -> Result<FocusFile, Error> {
+        self.inner.focus().load()
+    }
+
+    fn save(&self, _ff: &FocusFile) -> Result<(), Error> {
+        Err(refused())
+    }
+
+    fn delete(&self) -> Result<(), Error> {
+        Err(refused())
+    }
+}
+
+impl RecurringStore for ReadOnlyStore {
+    fn load(&self) -> Result<RecurringFile, Error> {
+        self.inner.recurring().load()
+    }
+
+    fn save(&self, _rf: &RecurringFile) -> Result<(), Error> {
+        Err(refused())
+    }
+
+    fn delete(&self) -> Result<(), Error> {
+        Err(refused())
+    }
+}
+
+impl JournalStore for ReadOnlyStore {
+    fn load(&self, limit: usize) -> Result<Vec<JournalEntry>, Error> {
+        self.inner.journal().load(limit)
+    }
+
+    fn append(&self, _entry: &JournalEntry) -> Result<(), Error> {
+        Err(refused())
+    }
+}
+
+impl VaultConfigStore for ReadOnlyStore {
+    fn load(&self) -> Result<VaultConfig, Error> {
+        self.inner.config().load()
+    }
+
+    fn save(&self, _config: &VaultConfig) -> Result<(), Error> {
+        Err(refused())
+    }
+}
@@ -0,0 +1,9 @@
+use std::io::Error;
+
+use crate::models::recurring_file::RecurringFile;
+
+pub trait RecurringStore {
+    fn load(&self) -> Result<RecurringFile, Error>;
+    fn save(&self, rf: &RecurringFile) -> Result<(), Error>;
+    fn delete(&self) -> Result<(), Error>;
+}
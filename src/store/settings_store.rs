@@ -0,0 +1,9 @@
+use std::io::Error;
+
+use crate::models::settings::Settings;
+
+/// The global config file, shared by every vault.
+pub trait SettingsStore {
+    fn load(&self) -> Result<Settings, Error>;
+    fn save(&self, settings: &Settings) -> Result<(), Error>;
+}
@@ -0,0 +1,3 @@
+pub mod day_store;
+pub mod focus_store;
+mod shared;
@@ -0,0 +1,264 @@
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+use chrono::NaiveDate;
+use rusqlite::{Connection, params};
+
+use crate::{
+    models::dayfile::DayFile,
+    store::{
+        day_store::{DayStore, LoadedDay},
+        sqlite::shared::{
+            List, delete_items, from_json, load_items, load_row, open, query_pairs, replace_items,
+            sql_error, to_json,
+        },
+    },
+};
+
+pub struct SqliteDayStore {
+    conn: Connection,
+}
+
+impl SqliteDayStore {
+    pub fn new(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            conn: open(base_dir, vault)?,
+        })
+    }
+}
+
+impl DayStore for SqliteDayStore {
+    fn load(&self, date: NaiveDate) -> Result<DayFile, io::Error> {
+        let key = date.to_string();
+
+        let mut df: DayFile = load_row(
+            &self.conn,
+            "SELECT data FROM days WHERE date = ?1",
+            Some(&key),
+            &format!("day file for {key}"),
+        )?;
+        df.items = load_items(&self.conn, List::Day, Some(&key))?;
+
+        Ok(df)
+    }
+
+    fn save(&self, df: &DayFile) -> Result<(), io::Error> {
+        let key = df.date.to_string();
+
+        // The day row holds everything but the items, which get their own table.
+        let mut meta = df.clone();
+        meta.items.clear();
+
+        let tx = self.conn.unchecked_transaction().map_err(sql_error)?;
+        tx.execute(
+            "INSERT INTO days (date, data) VALUES (?1, ?2)
+             ON CONFLICT (date) DO UPDATE SET data = excluded.data",
+            params![key, to_json(&meta)?],
+        )
+        .map_err(sql_error)?;
+        replace_items(&tx, List::Day, Some(&key), &df.items)?;
+        tx.commit().map_err(sql_error)
+    }
+
+    fn delete(&self, date: NaiveDate) -> Result<(), io::Error> {
+        let key = date.to_string();
+
+        let tx = self.conn.unchecked_transaction().map_err(sql_error)?;
+        tx.execute("DELETE FROM days WHERE date = ?1", params![key])
+            .map_err(sql_error)?;
+        delete_items(&tx, List::Day, Some(&key))?;
+        tx.commit().map_err(sql_error)
+    }
+
+    fn dates(&self) -> Result<Vec<NaiveDate>, io::Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT date FROM days ORDER BY date")
+            .map_err(sql_error)?;
+
+        let rows = stmt
+            .query_map([], |r| r.get::<_, String>(0))
+            .map_err(sql_error)?;
+
+        let mut dates = Vec::new();
+        for row in rows {
+            if let Ok(date) = NaiveDate::parse_from_str(&row.map_err(sql_error)?, "%Y-%m-%d") {
+                dates.push(date);
+            }
+        }
+
+        Ok(dates)
+    }
+
+    /// Reads the days and their items with a query each, instead of one per day.
+    fn load_range(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<LoadedDay>, io::Error> {
+        let bounds = params![from.map(|d| d.to_string()), to.map(|d| d.to_string())];
+        let range = "(?1 IS NULL OR date >= ?1) AND (?2 IS NULL OR date <= ?2)";
+
+        let mut days = BTreeMap::new();
+
+        for (key, data) in query_pairs(
+            &self.conn,
+            &format!("SELECT date, data FROM days WHERE {range}"),
+            bounds,
+        )? {
+            if let Ok(date) = NaiveDate::parse_from_str(&key, "%Y-%m-%d") {
+                days.insert(key, (date, from_json::<DayFile>(&data)));
+            }
+        }
+
+        for (key, data) in query_pairs(
+            &self.conn,
+            &format!(
+                "SELECT date, data FROM items WHERE list = 'day' AND {range} ORDER BY position"
+            ),
+            bounds,
+        )? {
+            let Some((_, day)) = days.get_mut(&key) else {
+                continue;
+            };
+
+            if let Ok(df) = day {
+                match from_json(&data) {
+                    Ok(item) => df.items.push(item),
+                    Err(e) => *day = Err(e),
+                }
+            }
+        }
+
+        Ok(days.into_values().collect())
+    }
+
+    /// Finds the days through the index on `due`, then reads just those.
+    fn load_with_due(&self) -> Result<Vec<LoadedDay>, io::Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT DISTINCT date FROM items
+                 WHERE list = 'day' AND due IS NOT NULL ORDER BY date",
+            )
+            .map_err(sql_error)?;
+
+        let keys = stmt
+            .query_map([], |r| r.get::<_, String>(0))
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| NaiveDate::parse_from_str(key, "%Y-%m-%d").ok())
+            .map(|date| (date, self.load(date)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::item::{Item, ItemPriority},
+        store::fs::day_store::FsDayStore,
+        utils::dates::{end_of_day, ymd},
+    };
+
+    fn day(date: &str, texts: &[&str]) -> DayFile {
+        let mut df = DayFile::new(ymd(date));
+        df.recurrences_applied = vec!["rule1".into()];
+        df.items = texts
+            .iter()
+            .map(|t| Item::new(t.to_string(), ItemPriority::Medium, vec![], None))
+            .collect();
+        df
+    }
+
+    fn texts(days: Vec<LoadedDay>) -> Vec<(NaiveDate, Vec<String>)> {
+        days.into_iter()
+            .map(|(date, df)| {
+                (
+                    date,
+                    df.unwrap().items.into_iter().map(|i| i.text).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn days_read_back_as_they_were_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteDayStore::new(Some(dir.path().to_path_buf()), None).unwrap();
+        let df = day("2025-09-01", &["one", "two", "three"]);
+
+        store.save(&df).unwrap();
+        store.save(&day("2025-09-02", &[])).unwrap();
+
+        let store = SqliteDayStore::new(Some(dir.path().to_path_buf()), None).unwrap();
+        let loaded = store.load(df.date).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&df).unwrap()
+        );
+        assert_eq!(
+            store.dates().unwrap(),
+            [ymd("2025-09-01"), ymd("2025-09-02")]
+        );
+
+        store.delete(df.date).unwrap();
+        assert_eq!(
+            store.load(df.date).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(store.dates().unwrap(), [ymd("2025-09-02")]);
+    }
+
+    #[test]
+    fn cross_day_queries_match_the_json_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = Some(dir.path().to_path_buf());
+        let sqlite = SqliteDayStore::new(base.clone(), None).unwrap();
+        let json = FsDayStore::new(base, None).unwrap();
+
+        let mut due = day("2025-09-03", &["a", "b"]);
+        due.items[1].due = Some(end_of_day(ymd("2025-09-05")));
+
+        for df in [
+            day("2025-09-01", &["x", "y"]),
+            day("2025-09-02", &[]),
+            due,
+            day("2025-09-04", &["z"]),
+        ] {
+            sqlite.save(&df).unwrap();
+            json.save(&df).unwrap();
+        }
+
+        let stores: [&dyn DayStore; 2] = [&sqlite, &json];
+
+        for store in stores {
+            assert_eq!(
+                texts(
+                    store
+                        .load_range(Some(ymd("2025-09-02")), Some(ymd("2025-09-03")))
+                        .unwrap()
+                ),
+                [
+                    (ymd("2025-09-02"), vec![]),
+                    (ymd("2025-09-03"), vec!["a".to_string(), "b".to_string()]),
+                ]
+            );
+            assert_eq!(store.load_range(None, None).unwrap().len(), 4);
+            assert_eq!(
+                store.load_range(Some(ymd("2025-09-04")), None).unwrap()[0].0,
+                ymd("2025-09-04")
+            );
+
+            let with_due = store.load_with_due().unwrap();
+            assert_eq!(
+                texts(with_due),
+                [(ymd("2025-09-03"), vec!["a".into(), "b".into()])]
+            );
+        }
+    }
+}
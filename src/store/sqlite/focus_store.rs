@@ -0,0 +1,62 @@
+use std::{io, path::PathBuf};
+
+use rusqlite::{Connection, params};
+
+use crate::{
+    models::focus_file::FocusFile,
+    store::{
+        focus_store::FocusStore,
+        sqlite::shared::{
+            List, delete_items, load_items, load_row, open, replace_items, sql_error, to_json,
+        },
+    },
+};
+
+pub struct SqliteFocusStore {
+    conn: Connection,
+}
+
+impl SqliteFocusStore {
+    pub fn new(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            conn: open(base_dir, vault)?,
+        })
+    }
+}
+
+impl FocusStore for SqliteFocusStore {
+    fn load(&self) -> Result<FocusFile, io::Error> {
+        let mut ff: FocusFile = load_row(
+            &self.conn,
+            "SELECT data FROM focus WHERE id = 1",
+            None,
+            "focus file",
+        )?;
+        ff.items = load_items(&self.conn, List::Focus, None)?;
+
+        Ok(ff)
+    }
+
+    fn save(&self, ff: &FocusFile) -> Result<(), io::Error> {
+        let mut meta = ff.clone();
+        meta.items.clear();
+
+        let tx = self.conn.unchecked_transaction().map_err(sql_error)?;
+        tx.execute(
+            "INSERT INTO focus (id, data) VALUES (1, ?1)
+             ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+            params![to_json(&meta)?],
+        )
+        .map_err(sql_error)?;
+        replace_items(&tx, List::Focus, None, &ff.items)?;
+        tx.commit().map_err(sql_error)
+    }
+
+    fn delete(&self) -> Result<(), io::Error> {
+        let tx = self.conn.unchecked_transaction().map_err(sql_error)?;
+        tx.execute("DELETE FROM focus WHERE id = 1", [])
+            .map_err(sql_error)?;
+        delete_items(&tx, List::Focus, None)?;
+        tx.commit().map_err(sql_error)
+    }
+}
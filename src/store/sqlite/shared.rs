@@ -0,0 +1,193 @@
+use std::{
+    fs::create_dir_all,
+    io::{self, Error, ErrorKind},
+    path::PathBuf,
+};
+
+use rusqlite::{Connection, OptionalExtension, Params, Transaction, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    models::item::Item,
+    store::fs::shared::{normalise_or_default, tusk_data_root},
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS days (
+        date TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS focus (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        data TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS items (
+        list TEXT NOT NULL,
+        date TEXT,
+        position INTEGER NOT NULL,
+        id TEXT NOT NULL,
+        text TEXT NOT NULL,
+        priority TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        done_at TEXT,
+        due TEXT,
+        data TEXT NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS items_by_list ON items (list, date, position);
+    CREATE INDEX IF NOT EXISTS items_by_id ON items (id);
+    CREATE INDEX IF NOT EXISTS items_by_due ON items (due) WHERE due IS NOT NULL;
+    CREATE INDEX IF NOT EXISTS items_by_done ON items (done_at);
+";
+
+/// Which list a row in `items` belongs to.
+#[derive(Clone, Copy)]
+pub(super) enum List {
+    Day,
+    Focus,
+}
+
+impl List {
+    fn as_str(&self) -> &'static str {
+        match self {
+            List::Day => "day",
+            List::Focus => "focus",
+        }
+    }
+}
+
+/// Opens the vault's database, creating it and its tables if needed.
+pub(super) fn open(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Connection> {
+    let vault_dir = base_dir
+        .unwrap_or(tusk_data_root()?)
+        .join("vaults")
+        .join(normalise_or_default(vault));
+
+    create_dir_all(&vault_dir)?;
+
+    let conn = Connection::open(vault_dir.join("tusk.db")).map_err(sql_error)?;
+    conn.execute_batch(SCHEMA).map_err(sql_error)?;
+
+    Ok(conn)
+}
+
+pub(super) fn sql_error(e: rusqlite::Error) -> Error {
+    Error::other(format!("sqlite: {e}"))
+}
+
+pub(super) fn to_json<T: Serialize>(value: &T) -> io::Result<String> {
+    serde_json::to_string(value).map_err(Error::from)
+}
+
+pub(super) fn from_json<T: DeserializeOwned>(data: &str) -> io::Result<T> {
+    serde_json::from_str(data).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to parse JSON stored in sqlite: {e}"),
+        )
+    })
+}
+
+/// Loads the single `data` column matching `sql`, or `NotFound` if there's no row.
+pub(super) fn load_row<T: DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    param: Option<&str>,
+    what: &str,
+) -> io::Result<T> {
+    let data: Option<String> = match param {
+        Some(p) => conn.query_row(sql, params![p], |r| r.get(0)),
+        None => conn.query_row(sql, [], |r| r.get(0)),
+    }
+    .optional()
+    .map_err(sql_error)?;
+
+    match data {
+        Some(d) => from_json(&d),
+        None => Err(Error::new(ErrorKind::NotFound, format!("no {what} stored"))),
+    }
+}
+
+/// The `(date, data)` rows `sql` returns, in order.
+pub(super) fn query_pairs(
+    conn: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> io::Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(sql).map_err(sql_error)?;
+
+    let rows = stmt
+        .query_map(params, |r| Ok((r.get(0)?, r.get(1)?)))
+        .map_err(sql_error)?;
+
+    rows.collect::<Result<_, _>>().map_err(sql_error)
+}
+
+pub(super) fn load_items(
+    conn: &Connection,
+    list: List,
+    date: Option<&str>,
+) -> io::Result<Vec<Item>> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM items WHERE list = ?1 AND date IS ?2 ORDER BY position")
+        .map_err(sql_error)?;
+
+    let rows = stmt
+        .query_map(params![list.as_str(), date], |r| r.get::<_, String>(0))
+        .map_err(sql_error)?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(from_json(&row.map_err(sql_error)?)?);
+    }
+
+    Ok(items)
+}
+
+pub(super) fn delete_items(tx: &Transaction, list: List, date: Option<&str>) -> io::Result<()> {
+    tx.execute(
+        "DELETE FROM items WHERE list = ?1 AND date IS ?2",
+        params![list.as_str(), date],
+    )
+    .map_err(sql_error)?;
+
+    Ok(())
+}
+
+/// Replaces every item in a list. Searchable fields get their own columns,
+/// the whole item is kept in `data` so nothing is lost on the way back out.
+pub(super) fn replace_items(
+    tx: &Transaction,
+    list: List,
+    date: Option<&str>,
+    items: &[Item],
+) -> io::Result<()> {
+    delete_items(tx, list, date)?;
+
+    let mut stmt = tx
+        .prepare(
+            "INSERT INTO items (list, date, position, id, text, priority, created_at, done_at, due, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        )
+        .map_err(sql_error)?;
+
+    for (position, item) in items.iter().enumerate() {
+        stmt.execute(params![
+            list.as_str(),
+            date,
+            position as i64,
+            item.id,
+            item.text,
+            item.priority.to_string(),
+            item.created_at.to_rfc3339(),
+            item.done_at.map(|d| d.to_rfc3339()),
+            item.due.map(|d| d.to_rfc3339()),
+            to_json(item)?,
+        ])
+        .map_err(sql_error)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use std::io::Error;
+
+use crate::models::vault_config::VaultConfig;
+
+pub trait VaultConfigStore {
+    fn load(&self) -> Result<VaultConfig, Error>;
+    fn save(&self, config: &VaultConfig) -> Result<(), Error>;
+}
@@ -0,0 +1,22 @@
+use std::io::Error;
+
+use crate::store::{fs::lock::VaultLock, vault_store::VaultStore};
+
+/// The vaults under a data directory, and which one commands use by default.
+pub trait VaultRegistry {
+    /// Every vault with a valid name, sorted.
+    fn names(&self) -> Result<Vec<String>, Error>;
+    fn exists(&self, name: &str) -> bool;
+    fn create(&self, name: &str) -> Result<(), Error>;
+    fn rename(&self, from: &str, to: &str) -> Result<(), Error>;
+    fn copy(&self, from: &str, to: &str) -> Result<(), Error>;
+    fn remove(&self, name: &str) -> Result<(), Error>;
+    /// The vault picked with `tusk vault current`, if any.
+    fn current(&self) -> Result<Option<String>, Error>;
+    fn set_current(&self, name: &str) -> Result<(), Error>;
+    /// Opens another vault to read, with the backend from its config.
+    fn open(&self, name: &str) -> Result<Box<dyn VaultStore>, Error>;
+    /// Takes a vault's lock, released when dropped, `None` where there's
+    /// nothing to lock.
+    fn lock(&self, name: &str) -> Result<Option<VaultLock>, Error>;
+}
@@ -0,0 +1,149 @@
+use std::{io, path::PathBuf};
+
+use crate::{
+    models::vault_config::StoreBackend,
+    store::{
+        day_store::DayStore,
+        focus_store::FocusStore,
+        fs::{
+            day_store::FsDayStore, focus_store::FsFocusStore, journal_store::FsJournalStore,
+            lock::VaultLock, recurring_store::FsRecurringStore,
+            vault_config_store::FsVaultConfigStore, vault_registry::FsVaultRegistry,
+        },
+        journal_store::JournalStore,
+        recurring_store::RecurringStore,
+        vault_config_store::VaultConfigStore,
+        vault_registry::VaultRegistry,
+    },
+};
+
+#[cfg(feature = "sqlite")]
+use crate::store::sqlite::{day_store::SqliteDayStore, focus_store::SqliteFocusStore};
+
+/// Everything a command reads and writes for one vault. A single instance is
+/// opened at startup and handed to every command through `CommandContext`.
+pub trait VaultStore {
+    fn days(&self) -> &dyn DayStore;
+    fn focus(&self) -> &dyn FocusStore;
+    fn recurring(&self) -> &dyn RecurringStore;
+    fn journal(&self) -> &dyn JournalStore;
+    fn config(&self) -> &dyn VaultConfigStore;
+
+    /// The vaults under the same data directory as this one.
+    fn registry(&self) -> &dyn VaultRegistry;
+
+    /// Opens this vault with a specific backend, whatever its config says, so
+    /// its tasks can be copied across.
+    fn open_backend(&self, backend: StoreBackend) -> io::Result<Box<dyn VaultStore>>;
+
+    /// Takes the vault's lock, released when dropped. `None` if there's
+    /// nothing to lock.
+    fn lock(&self) -> io::Result<Option<VaultLock>>;
+
+    /// Whether every write will be refused.
+    fn is_read_only(&self) -> bool {
+        false
+    }
+}
+
+/// A vault on disk. Days and focus tasks use the backend from the vault's
+/// config, everything else is kept in JSON files.
+pub struct DiskStore {
+    base_dir: Option<PathBuf>,
+    vault: Option<String>,
+    days: Box<dyn DayStore>,
+    focus: Box<dyn FocusStore>,
+    recurring: FsRecurringStore,
+    journal: FsJournalStore,
+    config: FsVaultConfigStore,
+    registry: FsVaultRegistry,
+}
+
+impl DiskStore {
+    pub fn open(base_dir: Option<PathBuf>, vault: Option<&str>) -> io::Result<Self> {
+        let config = FsVaultConfigStore::new(base_dir.clone(), vault)?;
+
+        let backend = match config.load() {
+            Ok(c) => c.store,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => StoreBackend::default(),
+            Err(e) => return Err(e),
+        };
+
+        Self::open_backend(base_dir, vault, backend)
+    }
+
+    /// Opens the vault with a specific backend, whatever its config says.
+    pub fn open_backend(
+        base_dir: Option<PathBuf>,
+        vault: Option<&str>,
+        backend: StoreBackend,
+    ) -> io::Result<Self> {
+        let (days, focus): (Box<dyn DayStore>, Box<dyn FocusStore>) = match backend {
+            StoreBackend::Json => (
+                Box::new(FsDayStore::new(base_dir.clone(), vault)?),
+                Box::new(FsFocusStore::new(base_dir.clone(), vault)?),
+            ),
+            #[cfg(feature = "sqlite")]
+            StoreBackend::Sqlite => (
+                Box::new(SqliteDayStore::new(base_dir.clone(), vault)?),
+                Box::new(SqliteFocusStore::new(base_dir.clone(), vault)?),
+            ),
+            #[cfg(not(feature = "sqlite"))]
+            StoreBackend::Sqlite => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "the sqlite store needs tusk built with `--features sqlite`",
+                ));
+            }
+        };
+
+        Ok(Self {
+            days,
+            focus,
+            recurring: FsRecurringStore::new(base_dir.clone(), vault)?,
+            journal: FsJournalStore::new(base_dir.clone(), vault)?,
+            config: FsVaultConfigStore::new(base_dir.clone(), vault)?,
+            registry: FsVaultRegistry::new(base_dir.clone())?,
+            base_dir,
+            vault: vault.map(|v| v.to_string()),
+        })
+    }
+}
+
+impl VaultStore for DiskStore {
+    fn days(&self) -> &dyn DayStore {
+        self.days.as_ref()
+    }
+
+    fn focus(&self) -> &dyn FocusStore {
+        self.focus.as_ref()
+    }
+
+    fn recurring(&self) -> &dyn RecurringStore {
+        &self.recurring
+    }
+
+    fn journal(&self) -> &dyn JournalStore {
+        &self.journal
+    }
+
+    fn config(&self) -> &dyn VaultConfigStore {
+        &self.config
+    }
+
+    fn registry(&self) -> &dyn VaultRegistry {
+        &self.registry
+    }
+
+    fn open_backend(&self, backend: StoreBackend) -> io::Result<Box<dyn VaultStore>> {
+        Ok(Box::new(Self::open_backend(
+            self.base_dir.clone(),
+            self.vault.as_deref(),
+            backend,
+        )?))
+    }
+
+    fn lock(&self) -> io::Result<Option<VaultLock>> {
+        VaultLock::acquire(self.base_dir.clone(), self.vault.as_deref()).map(Some)
+    }
+}
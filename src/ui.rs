@@ -0,0 +1,87 @@
+use std::io::{self, IsTerminal, Stdout, Write};
+
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{self, Event, KeyEventKind},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+
+use crate::{
+    CommandContext,
+    ui::app::App,
+    utils::{dates::todays_date, theme::Theme, tusk_error::TuskError},
+};
+
+pub mod app;
+mod draw;
+
+/// Runs `tusk ui` until the user quits.
+pub fn run(mut ctx: CommandContext) -> Result<(), TuskError> {
+    if !io::stdout().is_terminal() {
+        return Err(TuskError::InvalidInput {
+            message: "`tusk ui` needs an interactive terminal".to_string(),
+        });
+    }
+
+    let opts = ctx.render_opts.clone();
+    let theme = Theme::new(opts.color).with_checkboxes(opts.checkbox_open, opts.checkbox_done);
+
+    let mut app = App::new(todays_date());
+    app.reload(&ctx)?;
+
+    let mut screen = Screen::enter()?;
+
+    while !app.quit {
+        draw::draw(
+            &mut screen.out,
+            &app,
+            &theme,
+            &opts.formats,
+            opts.vault_name.as_deref(),
+        )?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.on_key(key, &mut ctx, &mut screen);
+        }
+    }
+
+    Ok(())
+}
+
+/// The alternate screen in raw mode, put back however the UI exits.
+pub struct Screen {
+    out: Stdout,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        let mut out = io::stdout();
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Self { out })
+    }
+
+    /// Hands the terminal back for the length of `f`, e.g. to run an editor.
+    pub fn suspended<T>(&mut self, f: impl FnOnce() -> T) -> io::Result<T> {
+        self.leave()?;
+        let result = f();
+        enable_raw_mode()?;
+        execute!(self.out, EnterAlternateScreen, Hide)?;
+        Ok(result)
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        execute!(self.out, Show, LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+        self.out.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = self.leave();
+    }
+}
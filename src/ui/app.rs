@@ -0,0 +1,329 @@
+use chrono::{Days, NaiveDate, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    CommandContext,
+    cli::command::OperationContext,
+    models::item::{Item, ItemPriority},
+    ui::Screen,
+    utils::{
+        dates::todays_date,
+        editor::edit_in_editor,
+        files::{
+            load_day_or_empty, load_focus_or_empty, save_dayfile, save_focusfile, stop_timer_on,
+        },
+        helpers::sanitise_str,
+        task_target::TaskTarget,
+        tusk_error::TuskError,
+    },
+    view::agenda::Agenda,
+};
+
+pub enum Mode {
+    Normal,
+    /// Typing a new description for the selected item.
+    Editing {
+        buffer: String,
+    },
+}
+
+pub struct App {
+    pub date: NaiveDate,
+    pub agenda: Agenda,
+    pub pane: TaskTarget,
+    pub focus_cursor: usize,
+    pub day_cursor: usize,
+    pub mode: Mode,
+    /// The outcome of the last key, shown under the lists.
+    pub status: Option<String>,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            agenda: Agenda::new(date, None, None),
+            pane: TaskTarget::Day,
+            focus_cursor: 0,
+            day_cursor: 0,
+            mode: Mode::Normal,
+            status: None,
+            quit: false,
+        }
+    }
+
+    pub fn items(&self, pane: TaskTarget) -> &[Item] {
+        let items = match pane {
+            TaskTarget::Day => self.agenda.dayfile.as_ref().map(|df| &df.items),
+            TaskTarget::Focus => self.agenda.focusfile.as_ref().map(|ff| &ff.items),
+        };
+
+        items.map(|i| i.as_slice()).unwrap_or_default()
+    }
+
+    pub fn cursor(&self, pane: TaskTarget) -> usize {
+        match pane {
+            TaskTarget::Day => self.day_cursor,
+            TaskTarget::Focus => self.focus_cursor,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Item> {
+        self.items(self.pane).get(self.cursor(self.pane))
+    }
+
+    /// Reads the day and focus tasks again, keeping the cursors in range.
+    pub fn reload(&mut self, ctx: &CommandContext) -> Result<(), TuskError> {
+        let _lock = ctx.lock()?;
+
+        self.agenda = Agenda::new(
+            self.date,
+            Some(load_day_or_empty(ctx, self.date)?),
+            Some(load_focus_or_empty(ctx)?),
+        );
+
+        self.day_cursor = self
+            .day_cursor
+            .min(self.items(TaskTarget::Day).len().saturating_sub(1));
+        self.focus_cursor = self
+            .focus_cursor
+            .min(self.items(TaskTarget::Focus).len().saturating_sub(1));
+
+        Ok(())
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent, ctx: &mut CommandContext, screen: &mut Screen) {
+        let result = match &mut self.mode {
+            Mode::Normal => self.on_normal_key(key, ctx, screen),
+            Mode::Editing { buffer } => match key.code {
+                KeyCode::Enter => {
+                    let text = std::mem::take(buffer);
+                    self.mode = Mode::Normal;
+                    self.edit_text(text, ctx)
+                }
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    Ok(())
+                }
+                KeyCode::Backspace => {
+                    buffer.pop();
+                    Ok(())
+                }
+                KeyCode::Char(c) => {
+                    buffer.push(c);
+                    Ok(())
+                }
+                _ => Ok(()),
+            },
+        };
+
+        if let Err(e) = result {
+            self.status = Some(e.to_string());
+        }
+    }
+
+    fn on_normal_key(
+        &mut self,
+        key: KeyEvent,
+        ctx: &mut CommandContext,
+        screen: &mut Screen,
+    ) -> Result<(), TuskError> {
+        self.status = None;
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Left | KeyCode::Char('h') => self.pane = TaskTarget::Focus,
+            KeyCode::Right | KeyCode::Char('l') => self.pane = TaskTarget::Day,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.pane = match self.pane {
+                    TaskTarget::Day => TaskTarget::Focus,
+                    TaskTarget::Focus => TaskTarget::Day,
+                }
+            }
+            KeyCode::Char(' ') => self.toggle_done(ctx)?,
+            KeyCode::Char('e') => {
+                if let Some(item) = self.selected() {
+                    self.mode = Mode::Editing {
+                        buffer: item.text.clone(),
+                    };
+                }
+            }
+            KeyCode::Char('n') => self.edit_notes(ctx, screen)?,
+            KeyCode::Char('p') => self.cycle_priority(ctx)?,
+            KeyCode::Char('m') => self.migrate(ctx)?,
+            KeyCode::Char('[') => self.go_to(self.date - Days::new(1), ctx)?,
+            KeyCode::Char(']') => self.go_to(self.date + Days::new(1), ctx)?,
+            KeyCode::Char('t') => self.go_to(todays_date(), ctx)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn move_cursor(&mut self, by: isize) {
+        let len = self.items(self.pane).len();
+        let cursor = match self.pane {
+            TaskTarget::Day => &mut self.day_cursor,
+            TaskTarget::Focus => &mut self.focus_cursor,
+        };
+
+        *cursor = cursor.saturating_add_signed(by).min(len.saturating_sub(1));
+    }
+
+    fn go_to(&mut self, date: NaiveDate, ctx: &CommandContext) -> Result<(), TuskError> {
+        self.date = date;
+        self.day_cursor = 0;
+        self.reload(ctx)
+    }
+
+    fn toggle_done(&mut self, ctx: &mut CommandContext) -> Result<(), TuskError> {
+        let done = self.selected().is_some_and(|i| i.done_at.is_some());
+        let command = if done { "ui undone" } else { "ui done" };
+
+        self.update(ctx, command, |ctx, item| {
+            if done {
+                item.mark_undone();
+            } else {
+                let now = Utc::now();
+                item.mark_done(now);
+                stop_timer_on(ctx, item, now)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn edit_text(&mut self, text: String, ctx: &mut CommandContext) -> Result<(), TuskError> {
+        self.update(ctx, "ui edit", |_, item| {
+            item.set_text(sanitise_str(&text)?);
+            Ok(())
+        })
+    }
+
+    fn edit_notes(
+        &mut self,
+        ctx: &mut CommandContext,
+        screen: &mut Screen,
+    ) -> Result<(), TuskError> {
+        let Some(item) = self.selected() else {
+            return Ok(());
+        };
+
+        let template = item.notes.clone().unwrap_or_default();
+        let editor = ctx.settings.editor.clone();
+        let notes = screen.suspended(|| edit_in_editor(&template, editor.as_deref()))??;
+
+        self.update(ctx, "ui notes", |_, item| {
+            item.notes = Some(notes);
+            Ok(())
+        })
+    }
+
+    fn cycle_priority(&mut self, ctx: &mut CommandContext) -> Result<(), TuskError> {
+        self.update(ctx, "ui priority", |_, item| {
+            item.priority = match item.priority {
+                ItemPriority::Low => ItemPriority::Medium,
+                ItemPriority::Medium => ItemPriority::High,
+                ItemPriority::High => ItemPriority::Low,
+            };
+            Ok(())
+        })
+    }
+
+    /// Moves the selected daily task on: to today from a past day, otherwise to the next day.
+    fn migrate(&mut self, ctx: &mut CommandContext) -> Result<(), TuskError> {
+        let Some(item) = self.selected() else {
+            return Ok(());
+        };
+
+        if self.pane == TaskTarget::Focus {
+            return Err(TuskError::InvalidInput {
+                message: "Focus tasks stay put, only daily tasks can be migrated".to_string(),
+            });
+        }
+
+        if item.done_at.is_some() {
+            return Err(TuskError::InvalidInput {
+                message: "Only open tasks can be migrated".to_string(),
+            });
+        }
+
+        let id = item.id.clone();
+        let to_date = if self.date < todays_date() {
+            todays_date()
+        } else {
+            self.date + Days::new(1)
+        };
+
+        let guard = ctx.lock()?;
+        ctx.operation = OperationContext::new("ui migrate");
+
+        let mut from_df = load_day_or_empty(ctx, self.date)?;
+        let mut to_df = load_day_or_empty(ctx, to_date)?;
+
+        let Some(pos) = from_df.items.iter().position(|i| i.id == id) else {
+            return Err(TuskError::ItemNotFound {
+                reference: format!("@{id}"),
+            });
+        };
+
+        let mut item = from_df.items.remove(pos);
+        if let Some(done) = item.split_finished() {
+            from_df.items.insert(pos, done);
+        }
+        item.record_migration(self.date);
+        to_df.items.push(item);
+
+        save_dayfile(ctx, &from_df)?;
+        save_dayfile(ctx, &to_df)?;
+        drop(guard);
+
+        self.status = Some(format!("Migrated to {to_date}"));
+        self.reload(ctx)
+    }
+
+    /// Applies `change` to the selected item as its own journalled operation.
+    /// The item is found again by id so changes made elsewhere aren't lost.
+    fn update(
+        &mut self,
+        ctx: &mut CommandContext,
+        command: &'static str,
+        change: impl FnOnce(&CommandContext, &mut Item) -> Result<(), TuskError>,
+    ) -> Result<(), TuskError> {
+        let Some(id) = self.selected().map(|i| i.id.clone()) else {
+            return Ok(());
+        };
+
+        {
+            let _lock = ctx.lock()?;
+            ctx.operation = OperationContext::new(command);
+
+            match self.pane {
+                TaskTarget::Day => {
+                    let mut df = load_day_or_empty(ctx, self.date)?;
+                    change(ctx, find(&mut df.items, &id)?)?;
+                    save_dayfile(ctx, &df)?;
+                }
+                TaskTarget::Focus => {
+                    let mut ff = load_focus_or_empty(ctx)?;
+                    change(ctx, find(&mut ff.items, &id)?)?;
+                    save_focusfile(ctx, &ff)?;
+                }
+            }
+        }
+
+        self.reload(ctx)
+    }
+}
+
+fn find<'a>(items: &'a mut [Item], id: &str) -> Result<&'a mut Item, TuskError> {
+    items
+        .iter_mut()
+        .find(|i| i.id == id)
+        .ok_or_else(|| TuskError::ItemNotFound {
+            reference: format!("@{id}"),
+        })
+}
@@ -0,0 +1,150 @@
+use std::io::{self, Write};
+
+use chrono::Utc;
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+};
+
+use crate::{
+    display::terminal::DateFormats,
+    models::item::{DUE_SOON_DAYS, Item},
+    ui::app::{App, Mode},
+    utils::{task_target::TaskTarget, theme::Theme},
+};
+
+const HELP: &str = "↑↓/jk move · tab pane · space done · e edit · n notes · p priority · m migrate · [ ] day · t today · q quit";
+
+pub fn draw(
+    out: &mut impl Write,
+    app: &App,
+    theme: &Theme,
+    formats: &DateFormats,
+    vault: Option<&str>,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+
+    let mut title = format!("Tusk • {}", app.date.format(&formats.date));
+    if let Some(v) = vault {
+        title.push_str(&format!(" • vault: {v}"));
+    }
+
+    let mut lines = vec![theme.title(&title).to_string(), String::new()];
+    let mut selected_line = 0;
+
+    for (pane, name) in [
+        (TaskTarget::Focus, "Focus Tasks"),
+        (TaskTarget::Day, "Daily Tasks"),
+    ] {
+        let items = app.items(pane);
+        let active = app.pane == pane;
+        let header = format!("{name} ({})", items.len());
+
+        lines.push(if active {
+            theme.subtitle(header).to_string()
+        } else {
+            theme.dim(header).to_string()
+        });
+
+        if items.is_empty() {
+            lines.push(format!("  {}", theme.dim("Nothing here.")));
+        }
+
+        for (idx, item) in items.iter().enumerate() {
+            let selected = active && idx == app.cursor(pane);
+
+            if selected {
+                selected_line = lines.len();
+            }
+
+            lines.push(item_line(idx, item, selected, width, theme, formats));
+        }
+
+        lines.push(String::new());
+    }
+
+    // Two rows are kept for the status and help lines.
+    let body = height.saturating_sub(2).max(1);
+    let scroll = (selected_line + 1).saturating_sub(body);
+
+    queue!(out, Clear(ClearType::All))?;
+
+    for (row, line) in lines.iter().skip(scroll).take(body).enumerate() {
+        queue!(out, MoveTo(0, row as u16), Print(line))?;
+    }
+
+    let status = match &app.mode {
+        Mode::Editing { buffer } => format!("{} {buffer}█", theme.hint("Edit:")),
+        Mode::Normal => app
+            .status
+            .as_deref()
+            .map(|s| theme.warn(s).to_string())
+            .unwrap_or_default(),
+    };
+
+    queue!(
+        out,
+        MoveTo(0, height.saturating_sub(2) as u16),
+        Print(status),
+        MoveTo(0, height.saturating_sub(1) as u16),
+        Print(theme.dim(truncate(HELP, width)))
+    )?;
+
+    out.flush()
+}
+
+fn item_line(
+    idx: usize,
+    item: &Item,
+    selected: bool,
+    width: usize,
+    theme: &Theme,
+    formats: &DateFormats,
+) -> String {
+    let marker = if selected { "›" } else { " " };
+    let text = truncate(&item.text, width.saturating_sub(24).max(10));
+    let text = if selected {
+        theme.title(&text)
+    } else if item.done_at.is_some() {
+        theme.dim(&text)
+    } else {
+        theme.plain(&text)
+    };
+
+    let mut line = format!(
+        "{} {:>2}. {} {} {}",
+        theme.ok(marker),
+        idx + 1,
+        theme.checkbox(item.done_at.is_some()),
+        text,
+        theme.priority(&item.priority)
+    );
+
+    if let Some((done, total)) = item.progress() {
+        line.push_str(&format!(" {}", theme.dim(format!("{done}/{total}"))));
+    }
+
+    if let Some(due) = item.due {
+        let state = item.due_state(Utc::now(), DUE_SOON_DAYS);
+        line.push_str(&format!(" {}", theme.due(formats.format_due(&due), state)));
+    }
+
+    if item.notes.as_deref().is_some_and(|n| !n.trim().is_empty()) {
+        line.push_str(&format!(" {}", theme.dim("✎")));
+    }
+
+    line
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+
+    let mut cut: String = s.chars().take(max.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
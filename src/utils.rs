@@ -1,9 +1,15 @@
-pub mod files;
+pub mod alias;
 pub mod dates;
-pub mod render;
+pub mod day_ref;
 pub mod editor;
+pub mod files;
 pub mod helpers;
+pub mod list_scope;
+pub mod query;
+pub mod render;
+pub mod selection;
+pub mod stats_range;
+pub mod task_ref;
+pub mod task_target;
 pub mod theme;
 pub mod tusk_error;
-pub mod list_scope;
-pub mod task_target;
\ No newline at end of file
@@ -0,0 +1,202 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use clap::CommandFactory;
+
+use crate::{
+    cli::command::{Cli, load_settings, resolve_vault},
+    store::{fs::vault_config_store::FsVaultConfigStore, vault_config_store::VaultConfigStore},
+};
+
+/// Global options that take a value, so the word after them isn't the command.
+const VALUE_OPTIONS: &[&str] = &["--data-dir", "--config", "-o", "--output", "-v", "--vault"];
+
+/// Swaps a configured alias in the command position for the words it stands
+/// for, e.g. `tusk today` for `tusk ls --scope all`. Built-in commands always
+/// win over an alias, and an alias can't expand into another alias.
+pub fn expand_aliases(args: Vec<OsString>) -> Vec<OsString> {
+    let Some(pos) = command_position(&args) else {
+        return args;
+    };
+
+    let name = args[pos].to_string_lossy().to_string();
+
+    if name == "help" || Cli::command().find_subcommand(&name).is_some() {
+        return args;
+    }
+
+    // Anything wrong with the config is reported once the command runs.
+    let Ok(global) = load_settings(option_value(&args[..pos], "--config").map(PathBuf::from))
+    else {
+        return args;
+    };
+
+    let data_dir = option_value(&args[..pos], "--data-dir").map(PathBuf::from);
+    let vault = option_value(&args[..pos], "--vault").or(option_value(&args[..pos], "-v"));
+    let Ok(vault) = resolve_vault(data_dir.clone(), vault.as_deref()) else {
+        return args;
+    };
+
+    let settings = FsVaultConfigStore::new(data_dir, vault.as_deref())
+        .and_then(|store| store.load())
+        .map(|config| config.settings.or(&global))
+        .unwrap_or(global);
+
+    let Some(expansion) = settings.aliases.get(&name) else {
+        return args;
+    };
+
+    let mut expanded = args[..pos].to_vec();
+    expanded.extend(split_words(expansion).into_iter().map(OsString::from));
+    expanded.extend(args[pos + 1..].iter().cloned());
+    expanded
+}
+
+/// Where the subcommand sits, skipping the program name and global options.
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let mut i = 1;
+
+    while let Some(arg) = args.get(i) {
+        let arg = arg.to_string_lossy();
+
+        if arg == "--" {
+            return None;
+        } else if VALUE_OPTIONS.contains(&arg.as_ref()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+/// The value given to a global option, as `--name value` or `--name=value`.
+fn option_value(args: &[OsString], name: &str) -> Option<String> {
+    let mut found = None;
+
+    for (i, arg) in args.iter().enumerate() {
+        let arg = arg.to_string_lossy();
+
+        if arg == name {
+            found = args.get(i + 1).map(|v| v.to_string_lossy().to_string());
+        } else if let Some(value) = arg.strip_prefix(name).and_then(|r| r.strip_prefix('=')) {
+            found = Some(value.to_string());
+        }
+    }
+
+    found
+}
+
+/// Splits an alias on whitespace, keeping quoted words together.
+fn split_words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => word.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn args(words: &[&str]) -> Vec<OsString> {
+        words.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn finds_the_command_after_global_options() {
+        assert_eq!(command_position(&args(&["tusk", "today"])), Some(1));
+        assert_eq!(
+            command_position(&args(&["tusk", "-o", "json", "today"])),
+            Some(3)
+        );
+        assert_eq!(
+            command_position(&args(&["tusk", "--no-colour", "today"])),
+            Some(2)
+        );
+        assert_eq!(
+            command_position(&args(&["tusk", "--output=json", "today"])),
+            Some(2)
+        );
+        assert_eq!(command_position(&args(&["tusk", "--", "today"])), None);
+        assert_eq!(command_position(&args(&["tusk", "-v", "work"])), None);
+    }
+
+    #[test]
+    fn reads_option_values_either_way() {
+        let given = args(&["tusk", "--config", "a.toml", "--vault=work"]);
+
+        assert_eq!(option_value(&given, "--config").as_deref(), Some("a.toml"));
+        assert_eq!(option_value(&given, "--vault").as_deref(), Some("work"));
+        assert_eq!(option_value(&given, "--data-dir"), None);
+    }
+
+    #[test]
+    fn splits_words_keeping_quotes_together() {
+        assert_eq!(split_words("ls  --scope all"), ["ls", "--scope", "all"]);
+        assert_eq!(
+            split_words(r#"add "buy milk" --tag 'to do'"#),
+            ["add", "buy milk", "--tag", "to do"]
+        );
+        assert_eq!(split_words(r#"search """#), ["search", ""]);
+        assert!(split_words("   ").is_empty());
+    }
+
+    #[test]
+    fn expands_an_alias_in_the_command_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        fs::write(
+            &config,
+            "[alias]\ntoday = \"ls --scope all\"\nls = \"week\"\n",
+        )
+        .unwrap();
+
+        let config = config.to_string_lossy().to_string();
+        let data_dir = dir.path().join("data").to_string_lossy().to_string();
+        let run = |words: &[&str]| {
+            let mut given = args(&["tusk", "--config", &config, "--data-dir", &data_dir]);
+            given.extend(args(words));
+            expand_aliases(given)[5..].to_vec()
+        };
+
+        assert_eq!(
+            run(&["today", "-d", "mon"]),
+            args(&["ls", "--scope", "all", "-d", "mon"])
+        );
+        // Built-in commands can't be shadowed.
+        assert_eq!(run(&["ls"]), args(&["ls"]));
+        assert_eq!(run(&["unknown"]), args(&["unknown"]));
+    }
+}
@@ -1,4 +1,6 @@
-use chrono::{Duration, NaiveDate};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
 
 pub fn todays_date() -> NaiveDate {
     chrono::Local::now().date_naive()
@@ -13,3 +15,263 @@ pub fn parse_ymd(d: &str) -> Result<NaiveDate, String> {
             .map_err(|_| format!("Invalid date '{d}'. Use YYYY-MM-DD, e.g. 2025-09-14")),
     }
 }
+
+/// A date for tests, e.g. `ymd("2025-09-14")`.
+#[cfg(test)]
+pub fn ymd(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("tests use YYYY-MM-DD dates")
+}
+
+/// Parses a month given as `YYYY-MM` into its first day.
+pub fn parse_month(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", s.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month '{s}'. Use YYYY-MM, e.g. 2025-09"))
+}
+
+/// Parses a due date such as `tomorrow`, `2025-10-20 14:00`, `fri 9am`, `+3d`
+/// or `17:30`. Dates without a time are due at the end of that day.
+pub fn parse_due(s: &str) -> Result<DateTime<Utc>, String> {
+    let s = s.trim().to_ascii_lowercase();
+    let invalid = || {
+        format!(
+            "Invalid due date '{s}'. Use YYYY-MM-DD, today, tomorrow, +3d, fri, \
+             optionally followed by a time, e.g. 'fri 17:00'"
+        )
+    };
+
+    let tokens = s.split_whitespace().collect::<Vec<_>>();
+
+    let (date, time) = match tokens.as_slice() {
+        [single] => match parse_due_date(single) {
+            Some(date) => (date, None),
+            None => (todays_date(), Some(parse_time(single).ok_or_else(invalid)?)),
+        },
+        [date, time] => (
+            parse_due_date(date).ok_or_else(invalid)?,
+            Some(parse_time(time).ok_or_else(invalid)?),
+        ),
+        _ => return Err(invalid()),
+    };
+
+    match time {
+        Some(t) => to_utc(date, t).ok_or_else(invalid),
+        None => Ok(end_of_day(date)),
+    }
+}
+
+/// The last second of `date` in local time, used for dues without a time.
+pub fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    let eod = NaiveTime::from_hms_opt(23, 59, 59).expect("23:59:59 is a valid time");
+    to_utc(date, eod).unwrap_or_else(|| Utc.from_utc_datetime(&date.and_time(eod)))
+}
+
+/// Midnight at the start of `date` in local time, for timestamps only known to the day.
+pub fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    to_utc(date, NaiveTime::MIN)
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)))
+}
+
+/// Whether a due was given as a plain date rather than a date and time.
+pub fn is_all_day(due: &DateTime<Utc>) -> bool {
+    let local = due.with_timezone(&Local);
+    local.hour() == 23 && local.minute() == 59 && local.second() == 59
+}
+
+fn parse_due_date(s: &str) -> Option<NaiveDate> {
+    if let Ok(date) = parse_ymd(s) {
+        return Some(date);
+    }
+
+    if let Some(offset) = s.strip_prefix('+') {
+        let days = if let Some(n) = offset.strip_suffix('d') {
+            n.parse::<i64>().ok()?
+        } else if let Some(n) = offset.strip_suffix('w') {
+            n.parse::<i64>().ok()?.checked_mul(7)?
+        } else {
+            return None;
+        };
+
+        return todays_date().checked_add_signed(Duration::try_days(days)?);
+    }
+
+    let weekday = s.parse::<Weekday>().ok()?;
+    let today = todays_date();
+    let ahead = (7 + weekday.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        % 7;
+
+    today.checked_add_signed(Duration::days(ahead))
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    if let Ok(t) = NaiveTime::parse_from_str(s, "%H:%M") {
+        return Some(t);
+    }
+
+    let (clock, pm) = if let Some(c) = s.strip_suffix("am") {
+        (c, false)
+    } else if let Some(c) = s.strip_suffix("pm") {
+        (c, true)
+    } else {
+        return None;
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+
+    let hour = match (hour, pm) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (h, false) => h,
+        (h, true) => h + 12,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn to_utc(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Parses a length of time such as `45m`, `2h`, `1h 30m`, `90s` or `1.5h`.
+/// A bare number is taken as minutes.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s
+        .split_whitespace()
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let invalid = || format!("Invalid duration '{s}'. Use minutes or hours, e.g. 45m, 2h or 1h30m");
+
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    if let Ok(m) = s.parse::<u32>() {
+        return Ok(Duration::minutes(m.into()));
+    }
+
+    let mut seconds = 0.0;
+    let mut rest = s.as_str();
+
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+        let (n, tail) = rest.split_at(split);
+        let n = n.parse::<f64>().map_err(|_| invalid())?;
+
+        let (unit, tail) = match tail.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => tail.split_at(i),
+            None => (tail, ""),
+        };
+
+        seconds += match unit {
+            "h" | "hr" | "hrs" | "hour" | "hours" => n * 3600.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => n * 60.0,
+            "s" | "sec" | "secs" | "second" | "seconds" => n,
+            _ => return Err(invalid()),
+        };
+        rest = tail;
+    }
+
+    if seconds > u32::MAX as f64 {
+        return Err(invalid());
+    }
+
+    Ok(Duration::seconds(seconds.round() as i64))
+}
+
+/// Like [`parse_duration`], rounded to whole minutes.
+pub fn parse_minutes(s: &str) -> Result<u32, String> {
+    let d = parse_duration(s)?;
+    u32::try_from((d.num_seconds() + 30) / 60).map_err(|_| format!("Invalid duration '{s}'"))
+}
+
+/// A length of time for display, e.g. `45m`, `2h` or `1h 30m`.
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today_plus(days: i64) -> NaiveDate {
+        todays_date() + Duration::days(days)
+    }
+
+    #[test]
+    fn due_dates_take_offsets_and_weekdays() {
+        assert_eq!(parse_due_date("+3d"), Some(today_plus(3)));
+        assert_eq!(parse_due_date("+2w"), Some(today_plus(14)));
+        assert_eq!(parse_due_date("tomorrow"), Some(today_plus(1)));
+        assert_eq!(
+            parse_due_date("2025-10-20"),
+            NaiveDate::from_ymd_opt(2025, 10, 20)
+        );
+
+        let fri = parse_due_date("fri").unwrap();
+        assert_eq!(fri.weekday(), Weekday::Fri);
+        assert!((0..7).contains(&(fri - todays_date()).num_days()));
+    }
+
+    #[test]
+    fn due_dates_reject_bad_units_and_non_ascii() {
+        assert_eq!(parse_due_date("+3m"), None);
+        assert_eq!(parse_due_date("+d"), None);
+        assert_eq!(parse_due_date("+"), None);
+        assert_eq!(parse_due_date("+é"), None);
+        assert_eq!(parse_due_date("+3é"), None);
+        assert_eq!(parse_due_date("+99999999999999w"), None);
+        assert_eq!(parse_due_date("someday"), None);
+        assert!(parse_due("+é").is_err());
+        assert!(parse_due("fri 25:00").is_err());
+    }
+
+    #[test]
+    fn due_times_default_to_the_end_of_the_day() {
+        let due = parse_due("+1d").unwrap();
+        assert!(is_all_day(&due));
+        assert_eq!(due.with_timezone(&Local).date_naive(), today_plus(1));
+
+        let due = parse_due("fri 9am").unwrap().with_timezone(&Local);
+        assert_eq!((due.hour(), due.minute()), (9, 0));
+    }
+
+    #[test]
+    fn durations_take_units_and_bare_minutes() {
+        assert_eq!(parse_duration("45"), Ok(Duration::minutes(45)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::hours(2)));
+        assert_eq!(parse_duration("1h 30m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("90s"), Ok(Duration::seconds(90)));
+        assert_eq!(parse_minutes("1h29m40s"), Ok(90));
+    }
+
+    #[test]
+    fn durations_reject_bad_units_and_non_ascii() {
+        for bad in ["", "h", "3x", "2 days", "1..5h", "é", "5é", "1hé"] {
+            assert!(parse_duration(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn minutes_format_as_hours_and_minutes() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(120), "2h");
+        assert_eq!(format_minutes(90), "1h 30m");
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::utils::{
+    dates::todays_date,
+    selection::{Pick, PickList, parse_pick, parse_pick_list},
+    task_ref::{TaskRef, parse_task_ref},
+    tusk_error::TuskError,
+};
+
+/// The day part of a reference such as `mon:3` or `14:3`, naming a day the
+/// way `tusk week` and `tusk month` label them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayRef {
+    Weekday(Weekday),
+    MonthDay(u32),
+}
+
+impl DayRef {
+    /// The date it names in the week, or month, around `anchor`.
+    pub fn date(&self, anchor: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            DayRef::Weekday(w) => anchor
+                .week(Weekday::Mon)
+                .first_day()
+                .checked_add_days(Days::new(w.num_days_from_monday() as u64)),
+            DayRef::MonthDay(d) => anchor.with_day(*d),
+        }
+    }
+}
+
+impl fmt::Display for DayRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DayRef::Weekday(w) => write!(f, "{}", w.to_string().to_lowercase()),
+            DayRef::MonthDay(d) => write!(f, "{d}"),
+        }
+    }
+}
+
+/// A reference with the day it points into, if it names one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnDay<T> {
+    pub day: Option<DayRef>,
+    pub value: T,
+}
+
+impl<T> OnDay<T> {
+    /// Like [`resolve_day`] for a command with a single reference.
+    pub fn resolve(self, date: Option<NaiveDate>) -> Result<(Option<NaiveDate>, T), TuskError> {
+        let (date, mut values) = resolve_day(date, vec![self])?;
+        Ok((date, values.remove(0)))
+    }
+}
+
+/// Finds the day a command's references point into. A named day is looked up
+/// in the week or month around `date` (today if omitted) and references
+/// without one go to the same day. If none names a day, `date` is kept.
+pub fn resolve_day<T>(
+    date: Option<NaiveDate>,
+    refs: Vec<OnDay<T>>,
+) -> Result<(Option<NaiveDate>, Vec<T>), TuskError> {
+    let anchor = date.unwrap_or(todays_date());
+    let mut resolved = None;
+    let mut values = Vec::new();
+
+    for r in refs {
+        if let Some(day) = r.day {
+            let on = day.date(anchor).ok_or_else(|| TuskError::InvalidInput {
+                message: format!("{} has no day {day}.", anchor.format("%B %Y")),
+            })?;
+
+            if resolved.is_some_and(|d| d != on) {
+                return Err(TuskError::InvalidInput {
+                    message:
+                        "The references point into different days, pick from one day at a time."
+                            .to_string(),
+                });
+            }
+
+            resolved = Some(on);
+        }
+
+        values.push(r.value);
+    }
+
+    Ok((resolved.or(date), values))
+}
+
+fn split_day(s: &str) -> Result<(Option<DayRef>, &str), String> {
+    let s = s.trim();
+
+    let Some((day, rest)) = s.split_once(':') else {
+        return Ok((None, s));
+    };
+
+    let day = day.trim();
+
+    let parsed = match day.parse::<u32>() {
+        Ok(d @ 1..=31) => Some(DayRef::MonthDay(d)),
+        Ok(_) => None,
+        Err(_) => day.parse::<Weekday>().ok().map(DayRef::Weekday),
+    };
+
+    match parsed {
+        Some(d) => Ok((Some(d), rest)),
+        None => Err(format!(
+            "Invalid day '{day}' in '{s}'. Use a weekday or a day of the month, e.g. mon:3 or 14:3"
+        )),
+    }
+}
+
+pub fn parse_day_pick(s: &str) -> Result<OnDay<Pick>, String> {
+    let (day, rest) = split_day(s)?;
+    Ok(OnDay {
+        day,
+        value: parse_pick(rest)?,
+    })
+}
+
+/// Parses a pick list, where one day prefix covers the whole list, e.g. `mon:1,3`.
+pub fn parse_day_pick_list(s: &str) -> Result<OnDay<PickList>, String> {
+    let (day, rest) = split_day(s)?;
+    Ok(OnDay {
+        day,
+        value: parse_pick_list(rest)?,
+    })
+}
+
+pub fn parse_day_task_ref(s: &str) -> Result<OnDay<TaskRef>, String> {
+    let (day, rest) = split_day(s)?;
+    Ok(OnDay {
+        day,
+        value: parse_task_ref(rest)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dates::ymd;
+
+    #[test]
+    fn one_day_prefix_covers_a_pick_list() {
+        let picked = parse_day_pick_list("mon:1,3-4").unwrap();
+
+        assert_eq!(picked.day, Some(DayRef::Weekday(Weekday::Mon)));
+        assert_eq!(
+            picked.value.0,
+            [Pick::Ref(TaskRef::Index(1)), Pick::Range(3, 4)]
+        );
+        assert_eq!(
+            parse_day_pick("14:2").unwrap().day,
+            Some(DayRef::MonthDay(14))
+        );
+        assert_eq!(parse_day_pick("2").unwrap().day, None);
+
+        assert!(parse_day_pick("32:1").is_err());
+        assert!(parse_day_pick("someday:1").is_err());
+    }
+
+    #[test]
+    fn days_resolve_around_the_anchor() {
+        // 2025-09-10 is a Wednesday.
+        let anchor = ymd("2025-09-10");
+
+        assert_eq!(
+            DayRef::Weekday(Weekday::Mon).date(anchor),
+            Some(ymd("2025-09-08"))
+        );
+        assert_eq!(
+            DayRef::Weekday(Weekday::Sun).date(anchor),
+            Some(ymd("2025-09-14"))
+        );
+        assert_eq!(DayRef::MonthDay(30).date(anchor), Some(ymd("2025-09-30")));
+        assert_eq!(DayRef::MonthDay(31).date(anchor), None);
+    }
+
+    #[test]
+    fn references_have_to_share_a_day() {
+        let anchor = Some(ymd("2025-09-10"));
+        let refs = |s: &[&str]| s.iter().map(|r| parse_day_pick(r).unwrap()).collect();
+
+        let (date, _) = resolve_day(anchor, refs(&["tue:1", "2"])).unwrap();
+        assert_eq!(date, Some(ymd("2025-09-09")));
+
+        let (date, _) = resolve_day(anchor, refs(&["1", "2"])).unwrap();
+        assert_eq!(date, anchor);
+
+        assert!(resolve_day(anchor, refs(&["tue:1", "wed:2"])).is_err());
+        assert!(resolve_day(anchor, refs(&["31:1"])).is_err());
+    }
+}
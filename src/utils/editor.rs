@@ -2,15 +2,23 @@ use std::{env, io, io::Write, process::Command};
 
 use tempfile::NamedTempFile;
 
-pub fn edit_in_editor(initial: &str) -> io::Result<String> {
+/// Opens `initial` in the configured editor, then `$EDITOR`, `$VISUAL` or
+/// nano. The editor command can carry its own arguments, e.g. `code --wait`.
+pub fn edit_in_editor(initial: &str, editor: Option<&str>) -> io::Result<String> {
     let mut tmp = NamedTempFile::new()?;
     writeln!(tmp, "{}", initial)?;
 
-    let editor = env::var("EDITOR")
-        .or_else(|_| env::var("VISUAL"))
-        .unwrap_or_else(|_| String::from("nano"));
+    let editor = editor
+        .map(|e| e.to_string())
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| env::var("EDITOR").ok())
+        .or_else(|| env::var("VISUAL").ok())
+        .unwrap_or_else(|| String::from("nano"));
 
-    Command::new(editor).arg(tmp.path()).status()?;
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("nano");
+
+    Command::new(program).args(words).arg(tmp.path()).status()?;
 
     let contents = std::fs::read_to_string(tmp.path())?;
     Ok(contents)
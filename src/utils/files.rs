@@ -1,47 +1,244 @@
 use std::io::{self, Error};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::{
     CommandContext,
-    models::{dayfile::DayFile, focus_file::FocusFile},
-    store::{
-        day_store::DayStore,
-        focus_store::FocusStore,
-        fs::{day_store::FsDayStore, focus_store::FsFocusStore},
-    }
+    models::{
+        dayfile::DayFile,
+        focus_file::FocusFile,
+        item::Item,
+        journal::{JOURNAL_LIMIT, Journal, JournalEntry, JournalFile, OperationKind},
+        recurring_file::RecurringFile,
+        vault_config::{StoreBackend, VaultConfig},
+    },
+    utils::dates::todays_date,
+    view::store_migration::StoreMigration,
 };
 
+pub fn load_vault_config(ctx: &CommandContext) -> Result<VaultConfig, Error> {
+    Ok(existing(ctx.store.config().load())?.unwrap_or_default())
+}
+
+pub fn save_vault_config(ctx: &CommandContext, config: &VaultConfig) -> Result<(), Error> {
+    ctx.store.config().save(config)
+}
+
+/// Stops the running timer if it's on `item`, logging the time on the item,
+/// so an item marked done doesn't keep collecting time.
+pub fn stop_timer_on(
+    ctx: &CommandContext,
+    item: &mut Item,
+    now: DateTime<Utc>,
+) -> Result<(), Error> {
+    let mut config = load_vault_config(ctx)?;
+
+    if let Some(timer) = config.timer.take_if(|t| t.item_id == item.id) {
+        item.sessions.push(timer.session(now));
+        save_vault_config(ctx, &config)?;
+    }
 
-// TODO:
-// Can we have a make store and then pass that around instead of making it each time?
+    Ok(())
+}
+
+/// Copies every day and the focus list from the vault's current backend to
+/// `to`, checks each copy reads back identically, then switches the vault over.
+/// The old backend's data is left where it is.
+pub fn migrate_store(ctx: &CommandContext, to: StoreBackend) -> Result<StoreMigration, Error> {
+    let mut config = load_vault_config(ctx)?;
+    let mut report = StoreMigration::new(config.store, to);
+
+    let target = ctx.store.open_backend(to)?;
+    let (source_days, target_days) = (ctx.store.days(), target.days());
+    let dates = source_days.dates()?;
+
+    // Anything left over from an earlier migration would otherwise reappear.
+    for stale in target_days.dates()? {
+        if !dates.contains(&stale) {
+            target_days.delete(stale)?;
+        }
+    }
+
+    for date in dates {
+        let df = source_days.load(date)?;
+        target_days.save(&df)?;
+        ensure_copied(&df, &target_days.load(date)?, &date.to_string())?;
+
+        report.days += 1;
+        report.items += df.items.len();
+    }
+
+    let (source_focus, target_focus) = (ctx.store.focus(), target.focus());
+
+    match existing(source_focus.load())? {
+        Some(ff) => {
+            target_focus.save(&ff)?;
+            ensure_copied(&ff, &target_focus.load()?, "focus")?;
+            report.focus_items = ff.items.len();
+        }
+        None => target_focus.delete()?,
+    }
+
+    config.store = to;
+    save_vault_config(ctx, &config)?;
+
+    Ok(report)
+}
+
+fn ensure_copied<T: Serialize>(original: &T, copy: &T, what: &str) -> Result<(), Error> {
+    if serde_json::to_value(original)? != serde_json::to_value(copy)? {
+        return Err(Error::other(format!(
+            "{what} didn't read back the same after copying, the vault was left as it was"
+        )));
+    }
+
+    Ok(())
+}
 
 pub fn load_day_or_empty(ctx: &CommandContext, date: NaiveDate) -> Result<DayFile, Error> {
-    let store = FsDayStore::new(ctx.data_dir.clone(), ctx.vault.as_deref())?;
+    let store = ctx.store.days();
+
+    let mut df = match store.load(date) {
+        Ok(df) => df,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => DayFile::new(date),
+        Err(e) => return Err(e),
+    };
 
-    match store.load(date) {
-        Ok(df) => Ok(df),
-        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(DayFile::new(date)),
-        Err(e) => Err(e.into()),
+    // Recurring items only fill in today and future days, past days are left
+    // as they were. Every command holds the vault lock while it reads, so the
+    // day is saved straight away and the items stay once the date has passed.
+    // They aren't journalled, undo has nothing to take back.
+    if date >= todays_date() {
+        let rf = load_recurring_or_empty(ctx)?;
+
+        if df.apply_recurring(&rf.rules) && !ctx.store.is_read_only() {
+            store.save(&df)?;
+        }
     }
+
+    Ok(df)
+}
+
+pub fn list_day_dates(ctx: &CommandContext) -> Result<Vec<NaiveDate>, Error> {
+    let store = ctx.store.days();
+    store.dates()
 }
 
 pub fn load_focus_or_empty(ctx: &CommandContext) -> Result<FocusFile, Error> {
-    let store = FsFocusStore::new(ctx.data_dir.clone(), ctx.vault.as_deref())?;
+    let store = ctx.store.focus();
 
     match store.load() {
         Ok(ff) => Ok(ff),
         Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(FocusFile::new()),
-        Err(e) => Err(e.into()),
+        Err(e) => Err(e),
     }
 }
 
 pub fn save_dayfile(ctx: &CommandContext, df: &DayFile) -> Result<(), Error> {
-    let store = FsDayStore::new(ctx.data_dir.clone(), ctx.vault.as_deref())?;
-    store.save(df)
+    let store = ctx.store.days();
+    let before = existing(store.load(df.date))?;
+    store.save(df)?;
+    record_change(ctx, JournalFile::Day { date: df.date }, before, Some(df))
 }
 
 pub fn save_focusfile(ctx: &CommandContext, ff: &FocusFile) -> Result<(), Error> {
-    let store = FsFocusStore::new(ctx.data_dir.clone(), ctx.vault.as_deref())?;
-    store.save(ff)
-}
\ No newline at end of file
+    let store = ctx.store.focus();
+    let before = existing(store.load())?;
+    store.save(ff)?;
+    record_change(ctx, JournalFile::Focus, before, Some(ff))
+}
+
+pub fn load_recurring_or_empty(ctx: &CommandContext) -> Result<RecurringFile, Error> {
+    let store = ctx.store.recurring();
+
+    match store.load() {
+        Ok(rf) => Ok(rf),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(RecurringFile::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save_recurring(ctx: &CommandContext, rf: &RecurringFile) -> Result<(), Error> {
+    let store = ctx.store.recurring();
+    let before = existing(store.load())?;
+    store.save(rf)?;
+    record_change(ctx, JournalFile::Recurring, before, Some(rf))
+}
+
+pub fn load_journal(ctx: &CommandContext) -> Result<Journal, Error> {
+    Ok(Journal::new(ctx.store.journal().load(JOURNAL_LIMIT)?))
+}
+
+/// The stored state of a journalled file, `None` if it doesn't exist.
+pub fn file_state(ctx: &CommandContext, file: &JournalFile) -> Result<Option<Value>, Error> {
+    let state = match file {
+        JournalFile::Day { date } => {
+            existing(ctx.store.days().load(*date))?.map(serde_json::to_value)
+        }
+        JournalFile::Focus => existing(ctx.store.focus().load())?.map(serde_json::to_value),
+        JournalFile::Recurring => existing(ctx.store.recurring().load())?.map(serde_json::to_value),
+    };
+
+    Ok(state.transpose()?)
+}
+
+/// Puts a journalled file back to `state`, removing it when `state` is `None`.
+/// The write is journalled like any other under the current operation.
+pub fn restore_file(
+    ctx: &CommandContext,
+    file: &JournalFile,
+    state: Option<&Value>,
+) -> Result<(), Error> {
+    if let Some(state) = state {
+        return match file {
+            JournalFile::Day { .. } => save_dayfile(ctx, &DayFile::deserialize(state)?),
+            JournalFile::Focus => save_focusfile(ctx, &FocusFile::deserialize(state)?),
+            JournalFile::Recurring => save_recurring(ctx, &RecurringFile::deserialize(state)?),
+        };
+    }
+
+    let before = file_state(ctx, file)?;
+
+    match file {
+        JournalFile::Day { date } => ctx.store.days().delete(*date)?,
+        JournalFile::Focus => ctx.store.focus().delete()?,
+        JournalFile::Recurring => ctx.store.recurring().delete()?,
+    }
+
+    record_change::<Value>(ctx, file.clone(), before, None)
+}
+
+fn existing<T>(loaded: Result<T, Error>) -> Result<Option<T>, Error> {
+    match loaded {
+        Ok(v) => Ok(Some(v)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn record_change<T: Serialize>(
+    ctx: &CommandContext,
+    file: JournalFile,
+    before: Option<T>,
+    after: Option<&T>,
+) -> Result<(), Error> {
+    let before = before.map(serde_json::to_value).transpose()?;
+    let after = after.map(serde_json::to_value).transpose()?;
+
+    // Undo and redo always record their writes so the history stays in step.
+    if before == after && ctx.operation.kind == OperationKind::Do {
+        return Ok(());
+    }
+
+    ctx.store.journal().append(&JournalEntry {
+        op: ctx.operation.id.clone(),
+        kind: ctx.operation.kind.clone(),
+        command: ctx.operation.command.to_string(),
+        at: Utc::now(),
+        file,
+        before,
+        after,
+    })
+}
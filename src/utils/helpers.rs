@@ -72,6 +72,7 @@ pub fn item_count_meta(dayfiles: &[DayFile]) -> ItemCountResult {
     }
 }
 
+#[derive(Default)]
 pub struct SummaryStats {
     pub completed: usize,
     pub total: usize,
@@ -88,16 +89,6 @@ impl SummaryStats {
     }
 }
 
-impl Default for SummaryStats {
-    fn default() -> Self {
-        Self {
-            completed: 0,
-            total: 0,
-            open: 0,
-        }
-    }
-}
-
 impl Add for SummaryStats {
     type Output = SummaryStats;
 
@@ -1,8 +1,10 @@
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, ValueEnum, Copy)]
+#[derive(Debug, Clone, PartialEq, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ListScope {
     Day,
     Focus,
     All,
-}
\ No newline at end of file
+}
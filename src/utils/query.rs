@@ -0,0 +1,385 @@
+use chrono::NaiveDate;
+
+use crate::{
+    models::item::{Item, ItemPriority, ItemStatus},
+    utils::{dates::parse_ymd, tusk_error::TuskError},
+};
+
+/// A parsed search query, e.g. `#work priority:high (standup OR "1:1") -status:done`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    All,
+    Term(Term),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Text(String),
+    Tag(String),
+    Priority(ItemPriority),
+    Status(ItemStatus),
+    Migrated(bool),
+    Before(NaiveDate),
+    After(NaiveDate),
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Query, TuskError> {
+        let tokens = tokenise(input)?;
+
+        if tokens.is_empty() {
+            return Ok(Query::All);
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+
+        match parser.peek() {
+            None => Ok(query),
+            Some(t) => Err(invalid(format!("unexpected '{}'", t.as_str()))),
+        }
+    }
+
+    /// Whether `item` matches, `date` being the day it lives on.
+    pub fn matches(&self, item: &Item, date: NaiveDate) -> bool {
+        match self {
+            Query::All => true,
+            Query::Term(t) => t.matches(item, date),
+            Query::Not(q) => !q.matches(item, date),
+            Query::And(a, b) => a.matches(item, date) && b.matches(item, date),
+            Query::Or(a, b) => a.matches(item, date) || b.matches(item, date),
+        }
+    }
+}
+
+impl Term {
+    fn matches(&self, item: &Item, date: NaiveDate) -> bool {
+        match self {
+            Term::Text(s) => {
+                let s = s.to_lowercase();
+                item.text.to_lowercase().contains(&s)
+                    || item
+                        .notes
+                        .as_deref()
+                        .is_some_and(|n| n.to_lowercase().contains(&s))
+            }
+            Term::Tag(tag) => item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            Term::Priority(p) => item.priority == *p,
+            Term::Status(s) => item.status() == *s,
+            Term::Migrated(m) => item.migrated_from.is_some() == *m,
+            Term::Before(d) => date < *d,
+            Term::After(d) => date > *d,
+        }
+    }
+
+    fn parse(word: &str, quoted: bool) -> Result<Term, TuskError> {
+        if quoted {
+            return Ok(Term::Text(word.to_string()));
+        }
+
+        if let Some(tag) = word.strip_prefix('#')
+            && !tag.is_empty()
+        {
+            return Ok(Term::Tag(tag.to_string()));
+        }
+
+        let Some((key, value)) = word.split_once(':') else {
+            return Ok(Term::Text(word.to_string()));
+        };
+
+        let value = value.to_ascii_lowercase();
+
+        match key.to_ascii_lowercase().as_str() {
+            "priority" | "p" => match value.as_str() {
+                "high" | "h" => Ok(Term::Priority(ItemPriority::High)),
+                "medium" | "med" | "m" => Ok(Term::Priority(ItemPriority::Medium)),
+                "low" | "l" => Ok(Term::Priority(ItemPriority::Low)),
+                _ => Err(invalid(format!("unknown priority '{value}'"))),
+            },
+            "status" => match value.as_str() {
+                "open" => Ok(Term::Status(ItemStatus::Open)),
+                "done" => Ok(Term::Status(ItemStatus::Done)),
+                _ => Err(invalid(format!("unknown status '{value}'"))),
+            },
+            "migrated" => match value.as_str() {
+                "true" | "yes" => Ok(Term::Migrated(true)),
+                "false" | "no" => Ok(Term::Migrated(false)),
+                _ => Err(invalid(format!(
+                    "`migrated:` expects true or false, got '{value}'"
+                ))),
+            },
+            "tag" => Ok(Term::Tag(value)),
+            "before" => parse_ymd(&value).map(Term::Before).map_err(invalid),
+            "after" => parse_ymd(&value).map(Term::After).map_err(invalid),
+            _ => Ok(Term::Text(word.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word { text: String, quoted: bool },
+}
+
+impl Token {
+    fn as_str(&self) -> &str {
+        match self {
+            Token::Open => "(",
+            Token::Close => ")",
+            Token::Word { text, .. } => text,
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word { text, quoted: false } if text == keyword)
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Query, TuskError> {
+        let mut left = self.parse_and()?;
+
+        while self.peek().is_some_and(|t| t.is_keyword("OR")) {
+            self.next();
+            let right = self.parse_and()?;
+            left = Query::Or(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Query, TuskError> {
+        let mut left = self.parse_unary()?;
+
+        loop {
+            match self.peek() {
+                Some(t) if t.is_keyword("AND") => {
+                    self.next();
+                }
+                Some(t) if t.is_keyword("OR") || *t == Token::Close => break,
+                Some(_) => {}
+                None => break,
+            }
+
+            let right = self.parse_unary()?;
+            left = Query::And(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Query, TuskError> {
+        match self.next() {
+            Some(t) if t.is_keyword("NOT") => Ok(Query::Not(Box::new(self.parse_unary()?))),
+            Some(Token::Open) => {
+                let inner = self.parse_or()?;
+
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err(invalid("missing ')'".to_string())),
+                }
+            }
+            Some(Token::Word { text, quoted }) => {
+                if !quoted
+                    && let Some(negated) = text.strip_prefix('-')
+                    && !negated.is_empty()
+                {
+                    let term = Term::parse(negated, false)?;
+                    return Ok(Query::Not(Box::new(Query::Term(term))));
+                }
+
+                Ok(Query::Term(Term::parse(&text, quoted)?))
+            }
+            Some(Token::Close) => Err(invalid("unexpected ')'".to_string())),
+            None => Err(invalid("query ends unexpectedly".to_string())),
+        }
+    }
+}
+
+fn tokenise(input: &str) -> Result<Vec<Token>, TuskError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(invalid("missing closing quote".to_string())),
+                    }
+                }
+                tokens.push(Token::Word { text, quoted: true });
+            }
+            c => {
+                let mut text = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word {
+                    text,
+                    quoted: false,
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn invalid(message: String) -> TuskError {
+    TuskError::InvalidInput {
+        message: format!("bad search query, {message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{dates::ymd, helpers::extract_tags};
+
+    fn term(t: Term) -> Box<Query> {
+        Box::new(Query::Term(t))
+    }
+
+    fn item(text: &str, priority: ItemPriority) -> Item {
+        Item::new(text.to_string(), priority, extract_tags(text), None)
+    }
+
+    #[test]
+    fn parses_terms() {
+        assert_eq!(Query::parse("").unwrap(), Query::All);
+        assert_eq!(
+            Query::parse("#work").unwrap(),
+            Query::Term(Term::Tag("work".into()))
+        );
+        assert_eq!(
+            Query::parse("p:h").unwrap(),
+            Query::Term(Term::Priority(ItemPriority::High))
+        );
+        assert_eq!(
+            Query::parse("after:2025-09-01").unwrap(),
+            Query::Term(Term::After(ymd("2025-09-01")))
+        );
+        // Words with a colon that isn't a key stay text, as do quoted keys.
+        assert_eq!(
+            Query::parse("10:30").unwrap(),
+            Query::Term(Term::Text("10:30".into()))
+        );
+        assert_eq!(
+            Query::parse("\"status:done\"").unwrap(),
+            Query::Term(Term::Text("status:done".into()))
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            Query::parse("a b OR c").unwrap(),
+            Query::Or(
+                Box::new(Query::And(
+                    term(Term::Text("a".into())),
+                    term(Term::Text("b".into()))
+                )),
+                term(Term::Text("c".into())),
+            )
+        );
+        assert_eq!(
+            Query::parse("a AND (b OR c)").unwrap(),
+            Query::And(
+                term(Term::Text("a".into())),
+                Box::new(Query::Or(
+                    term(Term::Text("b".into())),
+                    term(Term::Text("c".into()))
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn negates_with_not_or_a_dash() {
+        let expected = Query::Not(term(Term::Status(ItemStatus::Done)));
+
+        assert_eq!(Query::parse("NOT status:done").unwrap(), expected);
+        assert_eq!(Query::parse("-status:done").unwrap(), expected);
+        assert_eq!(
+            Query::parse("-").unwrap(),
+            Query::Term(Term::Text("-".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_bad_queries() {
+        for bad in [
+            "priority:urgent",
+            "status:maybe",
+            "migrated:sometimes",
+            "before:yesterday-ish",
+            "(a OR b",
+            "a)",
+            "\"open",
+            "a OR",
+            "NOT",
+        ] {
+            assert!(Query::parse(bad).is_err(), "{bad} should be refused");
+        }
+    }
+
+    #[test]
+    fn matches_items() {
+        let date = ymd("2025-09-10");
+        let mut standup = item("Standup #work", ItemPriority::High);
+        standup.notes = Some("Bring the 1:1 notes".into());
+        let mut chores = item("Laundry #home", ItemPriority::Low);
+        chores.done_at = Some(chores.created_at);
+        chores.migrated_from = Some(ymd("2025-09-09"));
+
+        let matching = |q: &str| {
+            let query = Query::parse(q).unwrap();
+            [&standup, &chores]
+                .into_iter()
+                .filter(|i| query.matches(i, date))
+                .map(|i| i.text.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("#WORK"), ["Standup #work"]);
+        assert_eq!(matching("\"1:1\""), ["Standup #work"]);
+        assert_eq!(matching("-status:done"), ["Standup #work"]);
+        assert_eq!(matching("migrated:true"), ["Laundry #home"]);
+        assert_eq!(
+            matching("(#home OR priority:high) after:2025-09-01").len(),
+            2
+        );
+        assert!(matching("before:2025-09-10").is_empty());
+    }
+}
@@ -1,23 +1,57 @@
 use chrono::NaiveDate;
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use std::io::{self};
 
 use crate::{
     Cli,
     display::{
-        json::JsonRenderer, markdown::MarkdownRenderer, renderer::Renderer,
-        terminal::TerminalRenderer,
+        json::JsonRenderer,
+        markdown::MarkdownRenderer,
+        renderer::Renderer,
+        terminal::{DateFormats, TerminalRenderer},
+    },
+    models::{
+        dayfile::DayFile,
+        item::Item,
+        recurrence::RecurrenceRule,
+        settings::{Settings, is_valid_format},
+    },
+    utils::{
+        task_ref::{TaskPath, TaskRef},
+        theme::Theme,
+        tusk_error::TuskError,
+    },
+    view::{
+        agenda::Agenda,
+        batch::Batch,
+        calendar::Calendar,
+        config::ConfigEntry,
+        due::DueReport,
+        export::ExportReport,
+        history::{History, HistoryEntry},
+        import::ImportReport,
+        migration::Migration,
+        plan::Plan,
+        pomodoro::PomodoroRun,
+        rollover::Rollover,
+        search::SearchResults,
+        stale::StaleReport,
+        stats::StatsReport,
+        store_migration::StoreMigration,
+        time_report::TimeReport,
+        timer::TimerChange,
+        vault::VaultSummary,
     },
-    models::{dayfile::DayFile, item::Item},
-    utils::{theme::Theme, tusk_error::TuskError},
-    view::agenda::Agenda,
 };
 
-#[derive(Debug, Clone, PartialEq, ValueEnum, Copy)]
+#[derive(Debug, Clone, PartialEq, ValueEnum, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RenderOutput {
     Terminal,
     Json,
     #[value(alias = "md")]
+    #[serde(alias = "md")]
     Markdown,
 }
 
@@ -27,15 +61,38 @@ pub struct RenderOpts {
     pub verbose: bool,
     pub vault_name: Option<String>,
     pub color: bool,
+    pub formats: DateFormats,
+    pub checkbox_open: Option<String>,
+    pub checkbox_done: Option<String>,
 }
 
-impl From<&Cli> for RenderOpts {
-    fn from(cli: &Cli) -> Self {
+impl RenderOpts {
+    /// Flags on the command line win over the config, which wins over the built-in defaults.
+    pub fn new(cli: &Cli, settings: &Settings, vault: Option<String>) -> Self {
+        let defaults = DateFormats::default();
+        // A hand-edited config could hold a format chrono can't use.
+        let format = |set: &Option<String>, default: String| {
+            set.clone()
+                .filter(|f| is_valid_format(f))
+                .unwrap_or(default)
+        };
+
         Self {
-            output: cli.output,
+            output: cli
+                .output
+                .or(settings.output)
+                .unwrap_or(RenderOutput::Terminal),
             verbose: cli.verbose,
-            vault_name: cli.vault.clone(),
-            color: !cli.no_colour,
+            vault_name: vault,
+            color: !cli.no_colour && settings.colour.unwrap_or(true),
+            formats: DateFormats {
+                date: format(&settings.date_format, defaults.date),
+                date_time: format(&settings.date_time_format, defaults.date_time),
+                due: format(&settings.due_format, defaults.due),
+                due_time: format(&settings.due_time_format, defaults.due_time),
+            },
+            checkbox_open: settings.checkbox_open.clone(),
+            checkbox_done: settings.checkbox_done.clone(),
         }
     }
 }
@@ -43,12 +100,16 @@ impl From<&Cli> for RenderOpts {
 pub fn make_renderer(opts: &RenderOpts) -> RendererImpl {
     match opts.output {
         RenderOutput::Terminal => RendererImpl::Terminal(TerminalRenderer {
-            theme: Theme::new(opts.color),
+            theme: Theme::new(opts.color)
+                .with_checkboxes(opts.checkbox_open.clone(), opts.checkbox_done.clone()),
             vault: opts.vault_name.clone(),
             verbose: opts.verbose,
+            formats: opts.formats.clone(),
         }),
         RenderOutput::Json => RendererImpl::Json(JsonRenderer {}),
-        RenderOutput::Markdown => RendererImpl::Markdown(MarkdownRenderer {}),
+        RenderOutput::Markdown => RendererImpl::Markdown(MarkdownRenderer {
+            formats: opts.formats.clone(),
+        }),
     }
 }
 
@@ -78,13 +139,13 @@ impl RendererImpl {
     pub fn render_summary(
         &self,
         date: Option<NaiveDate>,
-        index: usize,
+        reference: &TaskRef,
         item: &Item,
     ) -> io::Result<()> {
         match self {
-            RendererImpl::Terminal(r) => r.render_summary(date, index, item),
-            RendererImpl::Json(r) => r.render_summary(date, index, item),
-            RendererImpl::Markdown(r) => r.render_summary(date, index, item),
+            RendererImpl::Terminal(r) => r.render_summary(date, reference, item),
+            RendererImpl::Json(r) => r.render_summary(date, reference, item),
+            RendererImpl::Markdown(r) => r.render_summary(date, reference, item),
         }
     }
 
@@ -94,45 +155,208 @@ impl RendererImpl {
         end: NaiveDate,
         days: u64,
         dayfiles: &[DayFile],
+        time: &TimeReport,
     ) -> io::Result<()> {
         match self {
-            RendererImpl::Terminal(r) => r.render_review(start, end, days, dayfiles),
-            RendererImpl::Json(r) => r.render_review(start, end, days, dayfiles),
-            RendererImpl::Markdown(r) => r.render_review(start, end, days, dayfiles),
+            RendererImpl::Terminal(r) => r.render_review(start, end, days, dayfiles, time),
+            RendererImpl::Json(r) => r.render_review(start, end, days, dayfiles, time),
+            RendererImpl::Markdown(r) => r.render_review(start, end, days, dayfiles, time),
         }
     }
 
-    pub fn render_migrate(
-        &self,
-        to_date: NaiveDate,
-        from_df_original: &DayFile,
-        moved_items: &[Item],
-        dry_run: bool,
-    ) -> io::Result<()> {
+    pub fn render_migrate(&self, migration: &Migration) -> io::Result<()> {
         match self {
-            RendererImpl::Terminal(r) => {
-                r.render_migrate(to_date, from_df_original, moved_items, dry_run)
-            }
-            RendererImpl::Json(r) => {
-                r.render_migrate(to_date, from_df_original, moved_items, dry_run)
-            }
-            RendererImpl::Markdown(r) => {
-                r.render_migrate(to_date, from_df_original, moved_items, dry_run)
-            }
+            RendererImpl::Terminal(r) => r.render_migrate(migration),
+            RendererImpl::Json(r) => r.render_migrate(migration),
+            RendererImpl::Markdown(r) => r.render_migrate(migration),
         }
     }
 
     pub fn render_action(
         &self,
-        index: usize,
+        reference: &TaskPath,
         date: NaiveDate,
         action: ActionKind,
         item: Option<&Item>,
     ) -> io::Result<()> {
         match self {
-            RendererImpl::Terminal(r) => r.render_action(index, date, action, item),
-            RendererImpl::Json(r) => r.render_action(index, date, action, item),
-            RendererImpl::Markdown(r) => r.render_action(index, date, action, item),
+            RendererImpl::Terminal(r) => r.render_action(reference, date, action, item),
+            RendererImpl::Json(r) => r.render_action(reference, date, action, item),
+            RendererImpl::Markdown(r) => r.render_action(reference, date, action, item),
+        }
+    }
+
+    pub fn render_batch(&self, batch: &Batch) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_batch(batch),
+            RendererImpl::Json(r) => r.render_batch(batch),
+            RendererImpl::Markdown(r) => r.render_batch(batch),
+        }
+    }
+
+    pub fn render_due(&self, report: &DueReport) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_due(report),
+            RendererImpl::Json(r) => r.render_due(report),
+            RendererImpl::Markdown(r) => r.render_due(report),
+        }
+    }
+
+    pub fn render_search(&self, results: &SearchResults) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_search(results),
+            RendererImpl::Json(r) => r.render_search(results),
+            RendererImpl::Markdown(r) => r.render_search(results),
+        }
+    }
+
+    pub fn render_rules(&self, rules: &[RecurrenceRule], today: NaiveDate) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_rules(rules, today),
+            RendererImpl::Json(r) => r.render_rules(rules, today),
+            RendererImpl::Markdown(r) => r.render_rules(rules, today),
+        }
+    }
+
+    pub fn render_rule_action(
+        &self,
+        reference: &TaskRef,
+        action: RuleAction,
+        rule: &RecurrenceRule,
+    ) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_rule_action(reference, action, rule),
+            RendererImpl::Json(r) => r.render_rule_action(reference, action, rule),
+            RendererImpl::Markdown(r) => r.render_rule_action(reference, action, rule),
+        }
+    }
+
+    pub fn render_history(&self, history: &History) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_history(history),
+            RendererImpl::Json(r) => r.render_history(history),
+            RendererImpl::Markdown(r) => r.render_history(history),
+        }
+    }
+
+    pub fn render_history_action(
+        &self,
+        action: HistoryAction,
+        entry: &HistoryEntry,
+    ) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_history_action(action, entry),
+            RendererImpl::Json(r) => r.render_history_action(action, entry),
+            RendererImpl::Markdown(r) => r.render_history_action(action, entry),
+        }
+    }
+
+    pub fn render_store_migrate(&self, migration: &StoreMigration) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_store_migrate(migration),
+            RendererImpl::Json(r) => r.render_store_migrate(migration),
+            RendererImpl::Markdown(r) => r.render_store_migrate(migration),
+        }
+    }
+
+    pub fn render_config(&self, action: ConfigAction, entries: &[ConfigEntry]) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_config(action, entries),
+            RendererImpl::Json(r) => r.render_config(action, entries),
+            RendererImpl::Markdown(r) => r.render_config(action, entries),
+        }
+    }
+
+    pub fn render_vaults(&self, vaults: &[VaultSummary]) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_vaults(vaults),
+            RendererImpl::Json(r) => r.render_vaults(vaults),
+            RendererImpl::Markdown(r) => r.render_vaults(vaults),
+        }
+    }
+
+    pub fn render_vault_action(
+        &self,
+        action: VaultAction,
+        vault: &str,
+        from: Option<&str>,
+    ) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_vault_action(action, vault, from),
+            RendererImpl::Json(r) => r.render_vault_action(action, vault, from),
+            RendererImpl::Markdown(r) => r.render_vault_action(action, vault, from),
+        }
+    }
+
+    pub fn render_export(&self, report: &ExportReport) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_export(report),
+            RendererImpl::Json(r) => r.render_export(report),
+            RendererImpl::Markdown(r) => r.render_export(report),
+        }
+    }
+
+    pub fn render_import(&self, report: &ImportReport) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_import(report),
+            RendererImpl::Json(r) => r.render_import(report),
+            RendererImpl::Markdown(r) => r.render_import(report),
+        }
+    }
+
+    pub fn render_rollover(&self, rollover: &Rollover) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_rollover(rollover),
+            RendererImpl::Json(r) => r.render_rollover(rollover),
+            RendererImpl::Markdown(r) => r.render_rollover(rollover),
+        }
+    }
+
+    pub fn render_stale(&self, report: &StaleReport) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_stale(report),
+            RendererImpl::Json(r) => r.render_stale(report),
+            RendererImpl::Markdown(r) => r.render_stale(report),
+        }
+    }
+
+    pub fn render_timer(&self, change: &TimerChange) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_timer(change),
+            RendererImpl::Json(r) => r.render_timer(change),
+            RendererImpl::Markdown(r) => r.render_timer(change),
+        }
+    }
+
+    pub fn render_pomodoro(&self, run: &PomodoroRun) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_pomodoro(run),
+            RendererImpl::Json(r) => r.render_pomodoro(run),
+            RendererImpl::Markdown(r) => r.render_pomodoro(run),
+        }
+    }
+
+    pub fn render_stats(&self, report: &StatsReport) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_stats(report),
+            RendererImpl::Json(r) => r.render_stats(report),
+            RendererImpl::Markdown(r) => r.render_stats(report),
+        }
+    }
+
+    pub fn render_calendar(&self, calendar: &Calendar) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_calendar(calendar),
+            RendererImpl::Json(r) => r.render_calendar(calendar),
+            RendererImpl::Markdown(r) => r.render_calendar(calendar),
+        }
+    }
+
+    pub fn render_plan(&self, plan: &Plan) -> io::Result<()> {
+        match self {
+            RendererImpl::Terminal(r) => r.render_plan(plan),
+            RendererImpl::Json(r) => r.render_plan(plan),
+            RendererImpl::Markdown(r) => r.render_plan(plan),
         }
     }
 
@@ -145,10 +369,14 @@ impl RendererImpl {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActionKind {
     Done,
     Undone,
     Removed,
+    Edited,
+    Promoted,
+    Demoted,
 }
 
 impl ActionKind {
@@ -157,6 +385,132 @@ impl ActionKind {
             ActionKind::Done => "done",
             ActionKind::Undone => "undone",
             ActionKind::Removed => "rm",
+            ActionKind::Edited => "edit",
+            ActionKind::Promoted => "promote",
+            ActionKind::Demoted => "focus demote",
+        }
+    }
+
+    pub fn as_past_tense(&self) -> &'static str {
+        match self {
+            ActionKind::Done => "Marked done",
+            ActionKind::Undone => "Marked undone",
+            ActionKind::Removed => "Removed",
+            ActionKind::Edited => "Updated",
+            ActionKind::Promoted => "Promoted",
+            ActionKind::Demoted => "Demoted",
+        }
+    }
+}
+
+pub enum RuleAction {
+    Added,
+    Removed,
+    Paused,
+    Resumed,
+}
+
+impl RuleAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            RuleAction::Added => "recur add",
+            RuleAction::Removed => "recur rm",
+            RuleAction::Paused => "recur pause",
+            RuleAction::Resumed => "recur resume",
+        }
+    }
+
+    pub fn as_past_tense(&self) -> &'static str {
+        match self {
+            RuleAction::Added => "Added",
+            RuleAction::Removed => "Removed",
+            RuleAction::Paused => "Paused",
+            RuleAction::Resumed => "Resumed",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+impl HistoryAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            HistoryAction::Undo => "undo",
+            HistoryAction::Redo => "redo",
+        }
+    }
+
+    pub fn as_past_tense(&self) -> &'static str {
+        match self {
+            HistoryAction::Undo => "Undid",
+            HistoryAction::Redo => "Redid",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigAction {
+    Get,
+    Set,
+    Unset,
+    Ls,
+}
+
+impl ConfigAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            ConfigAction::Get => "config get",
+            ConfigAction::Set => "config set",
+            ConfigAction::Unset => "config unset",
+            ConfigAction::Ls => "config ls",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum VaultAction {
+    Create,
+    Rename,
+    Copy,
+    Remove,
+    Current,
+    Switch,
+}
+
+impl VaultAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            VaultAction::Create => "vault create",
+            VaultAction::Rename => "vault rename",
+            VaultAction::Copy => "vault copy",
+            VaultAction::Remove => "vault rm",
+            VaultAction::Current | VaultAction::Switch => "vault current",
+        }
+    }
+
+    pub fn as_result(&self) -> &'static str {
+        match self {
+            VaultAction::Create => "created",
+            VaultAction::Rename => "renamed",
+            VaultAction::Copy => "copied",
+            VaultAction::Remove => "removed",
+            VaultAction::Current => "current",
+            VaultAction::Switch => "switched",
+        }
+    }
+
+    pub fn as_past_tense(&self) -> &'static str {
+        match self {
+            VaultAction::Create => "Created",
+            VaultAction::Rename => "Renamed",
+            VaultAction::Copy => "Copied",
+            VaultAction::Remove => "Removed",
+            VaultAction::Current => "Current vault",
+            VaultAction::Switch => "Switched to",
         }
     }
 }
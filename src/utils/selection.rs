@@ -0,0 +1,436 @@
+use crate::{
+    models::item::{Item, ItemPriority},
+    utils::{
+        helpers::validate_index,
+        task_ref::{TaskPath, TaskRef, parse_task_path, parse_task_ref},
+        task_target::TaskTarget,
+        tusk_error::TuskError,
+    },
+};
+
+/// One entry of an item list such as `1,3,5-7`: a reference, a subtask or an
+/// inclusive range of indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pick {
+    Ref(TaskRef),
+    Subtask(TaskPath),
+    Range(usize, usize),
+}
+
+/// Picks given together in one argument, so another positional can follow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickList(pub Vec<Pick>);
+
+/// Which items of a list a command works on. Picks choose items by index,
+/// range or id, all of them if there are none, and the filters narrow that
+/// down by tag, priority and whether they're still open.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    pub picks: Vec<Pick>,
+    pub tags: Vec<String>,
+    pub priority: Option<ItemPriority>,
+    pub open_only: bool,
+}
+
+/// An item, or one of its subtasks, a selection resolved to. Positions are
+/// 0-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Picked {
+    pub target: TaskTarget,
+    pub pos: usize,
+    pub subtask: Option<usize>,
+}
+
+impl Picked {
+    /// The index reference for the pick, e.g. `#3` or `#3.2`.
+    pub fn path(&self) -> TaskPath {
+        TaskPath {
+            task: TaskRef::Index(self.pos + 1),
+            subtask: self.subtask.map(|s| s + 1),
+        }
+    }
+}
+
+impl Selection {
+    /// The reference when the selection is exactly one item or subtask, so
+    /// commands can report it the way they always have.
+    pub fn single(&self) -> Option<TaskPath> {
+        if !self.tags.is_empty() || self.priority.is_some() || self.open_only {
+            return None;
+        }
+
+        match self.picks.as_slice() {
+            [Pick::Ref(r)] => Some(r.clone().into()),
+            [Pick::Subtask(p)] => Some(p.clone()),
+            _ => None,
+        }
+    }
+
+    /// Resolves the selection against `items`, returning the positions it
+    /// covers in list order. Every pick has to point at an item.
+    pub fn resolve(&self, items: &[Item]) -> Result<Vec<usize>, TuskError> {
+        if self.picks.iter().any(|p| matches!(p, Pick::Subtask(_))) {
+            return Err(TuskError::InvalidInput {
+                message: "Subtasks such as 3.2 can't be picked here, use the item's index"
+                    .to_string(),
+            });
+        }
+
+        let picked = self.resolve_in(TaskTarget::Day, items, &[])?;
+        Ok(picked.into_iter().map(|p| p.pos).collect())
+    }
+
+    /// Resolves the selection against the day and focus items at once. Ids
+    /// can point into either list, indices and ranges point into `target`'s.
+    /// Everything is resolved before anything changes, so removing one item
+    /// doesn't shift the ones after it.
+    pub fn resolve_in(
+        &self,
+        target: TaskTarget,
+        day_items: &[Item],
+        focus_items: &[Item],
+    ) -> Result<Vec<Picked>, TuskError> {
+        let list = |t: TaskTarget| match t {
+            TaskTarget::Day => day_items,
+            TaskTarget::Focus => focus_items,
+        };
+
+        let mut picked = Vec::new();
+
+        if self.picks.is_empty() {
+            picked.extend((0..list(target).len()).map(|pos| Picked {
+                target,
+                pos,
+                subtask: None,
+            }));
+        }
+
+        for pick in &self.picks {
+            match pick {
+                Pick::Ref(r) => {
+                    let (target, pos) = r.resolve(target, day_items, focus_items)?;
+                    picked.push(Picked {
+                        target,
+                        pos,
+                        subtask: None,
+                    });
+                }
+                Pick::Subtask(path) => {
+                    let (target, pos) = path.task.resolve(target, day_items, focus_items)?;
+                    let n = path.subtask.unwrap_or_default();
+                    let subtask = validate_index(n, list(target)[pos].subtasks.len())?;
+                    picked.push(Picked {
+                        target,
+                        pos,
+                        subtask: Some(subtask),
+                    });
+                }
+                Pick::Range(start, end) => {
+                    let len = list(target).len();
+                    validate_index(*start, len)?;
+                    validate_index(*end, len)?;
+                    picked.extend((start - 1..*end).map(|pos| Picked {
+                        target,
+                        pos,
+                        subtask: None,
+                    }));
+                }
+            }
+        }
+
+        picked.sort_unstable();
+        picked.dedup();
+        picked.retain(|p| self.matches(&list(p.target)[p.pos]));
+
+        Ok(picked)
+    }
+
+    fn matches(&self, item: &Item) -> bool {
+        let tagged = self
+            .tags
+            .iter()
+            .all(|tag| item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+
+        tagged
+            && self.priority.is_none_or(|p| item.priority == p)
+            && (!self.open_only || item.done_at.is_none())
+    }
+}
+
+pub fn parse_pick(s: &str) -> Result<Pick, String> {
+    let s = s.trim();
+
+    if let Some((start, end)) = s.split_once('-')
+        && let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>())
+    {
+        return if start <= end {
+            Ok(Pick::Range(start, end))
+        } else {
+            Err(format!(
+                "Invalid range '{s}', the start comes after the end"
+            ))
+        };
+    }
+
+    let path = parse_task_path(s)?;
+
+    Ok(match path.subtask {
+        Some(_) => Pick::Subtask(path),
+        None => Pick::Ref(path.task),
+    })
+}
+
+/// Parses a comma separated list of picks given as one argument, e.g. `1,3,5-7`.
+pub fn parse_pick_list(s: &str) -> Result<PickList, String> {
+    s.split(',')
+        .map(parse_pick)
+        .collect::<Result<_, _>>()
+        .map(PickList)
+}
+
+/// Parses `--id` values, where the `@` is optional and a number is an id too.
+pub fn parse_id_pick(s: &str) -> Result<Pick, String> {
+    let id = s.trim().trim_start_matches('@');
+    parse_task_ref(&format!("@{id}")).map(Pick::Ref)
+}
+
+/// Splits positionals such as `4 "New text"` into the picks and the text after
+/// them. The text can only follow a single pick, and is taken as text unless
+/// it's an index, range or `@` id, so `1 3` is still two items.
+pub fn picks_then_text<T>(
+    args: Vec<String>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<(Vec<T>, Option<String>), TuskError> {
+    let mut picks = Vec::new();
+
+    for (n, arg) in args.iter().enumerate() {
+        let parsed = parse(arg);
+
+        if n == 1 && args.len() == 2 && (parsed.is_err() || !looks_like_pick(arg)) {
+            return Ok((picks, Some(arg.clone())));
+        }
+
+        picks.push(parsed.map_err(|message| TuskError::InvalidInput { message })?);
+    }
+
+    Ok((picks, None))
+}
+
+/// Whether `s` starts like an index or an `@` id, after any `DAY:` prefix. A
+/// bare id could just as well be a word of text.
+fn looks_like_pick(s: &str) -> bool {
+    let rest = s.split_once(':').map_or(s, |(_, rest)| rest);
+    rest.trim_start()
+        .starts_with(|c: char| c.is_ascii_digit() || c == '@')
+}
+
+impl From<TaskRef> for Selection {
+    fn from(task: TaskRef) -> Self {
+        Self {
+            picks: vec![Pick::Ref(task)],
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::helpers::extract_tags;
+
+    fn items(specs: &[(&str, ItemPriority, bool)]) -> Vec<Item> {
+        specs
+            .iter()
+            .enumerate()
+            .map(|(n, (text, priority, done))| {
+                let mut item = Item::new(text.to_string(), *priority, extract_tags(text), None);
+                item.id = format!("id{n}");
+                item.done_at = done.then_some(item.created_at);
+                item
+            })
+            .collect()
+    }
+
+    fn picks(s: &str) -> Vec<Pick> {
+        parse_pick_list(s).unwrap().0
+    }
+
+    #[test]
+    fn parses_picks() {
+        assert_eq!(
+            picks("1, 3,5-7"),
+            [
+                Pick::Ref(TaskRef::Index(1)),
+                Pick::Ref(TaskRef::Index(3)),
+                Pick::Range(5, 7),
+            ]
+        );
+        assert_eq!(
+            parse_pick("@x7-Kp").unwrap(),
+            Pick::Ref(TaskRef::Id("x7-Kp".into()))
+        );
+        assert_eq!(
+            parse_pick("3.2").unwrap(),
+            Pick::Subtask(TaskPath {
+                task: TaskRef::Index(3),
+                subtask: Some(2),
+            })
+        );
+        assert_eq!(
+            parse_id_pick("x7Kp").unwrap(),
+            parse_id_pick("@x7Kp").unwrap()
+        );
+        assert_eq!(
+            parse_id_pick("12").unwrap(),
+            Pick::Ref(TaskRef::Id("12".into()))
+        );
+
+        assert!(parse_pick("7-5").is_err());
+        assert!(parse_pick("3.x").is_err());
+        assert!(parse_pick_list("1,,2").is_err());
+    }
+
+    #[test]
+    fn text_can_follow_a_single_pick() {
+        let split = |args: &[&str]| {
+            picks_then_text(args.iter().map(|a| a.to_string()).collect(), parse_pick)
+        };
+
+        assert_eq!(
+            split(&["4", "Count sheep"]).unwrap(),
+            (
+                vec![Pick::Ref(TaskRef::Index(4))],
+                Some("Count sheep".into())
+            )
+        );
+        assert_eq!(
+            split(&["4", "Groceries"]).unwrap().1.as_deref(),
+            Some("Groceries")
+        );
+        assert_eq!(
+            split(&["4", "@x7Kp"]).unwrap().0,
+            [
+                Pick::Ref(TaskRef::Index(4)),
+                Pick::Ref(TaskRef::Id("x7Kp".into()))
+            ]
+        );
+        assert_eq!(
+            split(&["1", "3"]).unwrap(),
+            (
+                vec![Pick::Ref(TaskRef::Index(1)), Pick::Ref(TaskRef::Index(3))],
+                None
+            )
+        );
+        assert!(split(&["Count sheep"]).is_err());
+        assert!(split(&["1", "3", "Count sheep"]).is_err());
+    }
+
+    #[test]
+    fn resolves_picks_in_list_order_once_each() {
+        let list = items(&[
+            ("a", ItemPriority::Low, false),
+            ("b", ItemPriority::Low, false),
+            ("c", ItemPriority::Low, false),
+            ("d", ItemPriority::Low, false),
+        ]);
+
+        let selection = Selection {
+            picks: picks("4,2-3,@id1"),
+            ..Selection::default()
+        };
+        assert_eq!(selection.resolve(&list).unwrap(), [1, 2, 3]);
+
+        // No picks means every item.
+        assert_eq!(Selection::default().resolve(&list).unwrap(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn every_pick_has_to_exist() {
+        let list = items(&[
+            ("a", ItemPriority::Low, false),
+            ("b", ItemPriority::Low, false),
+        ]);
+
+        for bad in ["3", "1-3", "0", "@nope"] {
+            let selection = Selection {
+                picks: picks(bad),
+                ..Selection::default()
+            };
+            assert!(selection.resolve(&list).is_err(), "{bad} should be refused");
+        }
+
+        let subtask = Selection {
+            picks: picks("1.1"),
+            ..Selection::default()
+        };
+        assert!(subtask.resolve(&list).is_err());
+    }
+
+    #[test]
+    fn filters_narrow_the_picks() {
+        let list = items(&[
+            ("a #work", ItemPriority::High, false),
+            ("b #work", ItemPriority::Low, true),
+            ("c #home", ItemPriority::High, false),
+            ("d #Work", ItemPriority::Low, false),
+        ]);
+
+        let work = Selection {
+            tags: vec!["work".into()],
+            ..Selection::default()
+        };
+        assert_eq!(work.resolve(&list).unwrap(), [0, 1, 3]);
+
+        let open_work = Selection {
+            open_only: true,
+            ..work.clone()
+        };
+        assert_eq!(open_work.resolve(&list).unwrap(), [0, 3]);
+
+        let high = Selection {
+            picks: picks("2-4"),
+            priority: Some(ItemPriority::High),
+            ..Selection::default()
+        };
+        assert_eq!(high.resolve(&list).unwrap(), [2]);
+    }
+
+    #[test]
+    fn ids_reach_into_the_focus_list() {
+        let day = items(&[("a", ItemPriority::Low, false)]);
+        let mut focus = items(&[
+            ("f", ItemPriority::Low, false),
+            ("g", ItemPriority::Low, false),
+        ]);
+        focus[1].id = "focus1".into();
+
+        let selection = Selection {
+            picks: picks("1,@focus1"),
+            ..Selection::default()
+        };
+        let picked = selection.resolve_in(TaskTarget::Day, &day, &focus).unwrap();
+
+        assert_eq!(
+            picked.iter().map(|p| (p.target, p.pos)).collect::<Vec<_>>(),
+            [(TaskTarget::Day, 0), (TaskTarget::Focus, 1)]
+        );
+    }
+
+    #[test]
+    fn single_is_only_for_one_plain_pick() {
+        let one = Selection::from(TaskRef::Index(3));
+        assert_eq!(one.single().map(|p| p.task), Some(TaskRef::Index(3)));
+
+        let range = Selection {
+            picks: picks("1-2"),
+            ..Selection::default()
+        };
+        assert_eq!(range.single(), None);
+
+        let filtered = Selection {
+            open_only: true,
+            ..one
+        };
+        assert_eq!(filtered.single(), None);
+    }
+}
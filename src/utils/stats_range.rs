@@ -0,0 +1,91 @@
+use std::fmt;
+
+use chrono::{Days, Months, NaiveDate};
+
+/// How far back `tusk stats` looks, e.g. `30d`, `12w`, `6m` or `1y`, ending today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsRange {
+    pub count: u32,
+    pub unit: RangeUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// How the days of a range are grouped to show completion over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsRange {
+    /// The first day of the range that ends on `today`.
+    pub fn start(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let before = match self.unit {
+            RangeUnit::Day => today.checked_sub_days(Days::new(self.count.into())),
+            RangeUnit::Week => today.checked_sub_days(Days::new(u64::from(self.count) * 7)),
+            RangeUnit::Month => today.checked_sub_months(Months::new(self.count)),
+            RangeUnit::Year => today.checked_sub_months(Months::new(self.count.checked_mul(12)?)),
+        }?;
+
+        before.succ_opt()
+    }
+
+    pub fn bucket(&self) -> Bucket {
+        match self.unit {
+            RangeUnit::Day => Bucket::Day,
+            RangeUnit::Week | RangeUnit::Month => Bucket::Week,
+            RangeUnit::Year => Bucket::Month,
+        }
+    }
+}
+
+impl fmt::Display for StatsRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            RangeUnit::Day => "day",
+            RangeUnit::Week => "week",
+            RangeUnit::Month => "month",
+            RangeUnit::Year => "year",
+        };
+
+        match self.count {
+            1 => write!(f, "{unit}"),
+            n => write!(f, "{n} {unit}s"),
+        }
+    }
+}
+
+pub fn parse_stats_range(s: &str) -> Result<StatsRange, String> {
+    let s = s.trim().to_ascii_lowercase();
+    let invalid = || {
+        format!(
+            "Invalid range '{s}'. Use a number of days, weeks, months or years, e.g. 30d, 12w, 6m or 1y"
+        )
+    };
+
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (count, unit) = s.split_at(split);
+    let count = count.parse::<u32>().map_err(|_| invalid())?;
+
+    let unit = match unit {
+        "d" => RangeUnit::Day,
+        "w" => RangeUnit::Week,
+        "m" => RangeUnit::Month,
+        "y" => RangeUnit::Year,
+        _ => return Err(invalid()),
+    };
+
+    if count == 0 {
+        return Err(invalid());
+    }
+
+    Ok(StatsRange { count, unit })
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::{
+    models::item::Item,
+    utils::{helpers::validate_index, task_target::TaskTarget, tusk_error::TuskError},
+};
+
+/// How a command addresses an item: by its 1-based position in a list, or
+/// by its id (or a unique prefix of it), e.g. `3` or `@x7Kp`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskRef {
+    Index(usize),
+    Id(String),
+}
+
+impl TaskRef {
+    /// Finds the list and position the reference points at.
+    ///
+    /// Indices are resolved against the list for `target`, ids are resolved
+    /// across both the day and focus items so either can be addressed.
+    pub fn resolve(
+        &self,
+        target: TaskTarget,
+        day_items: &[Item],
+        focus_items: &[Item],
+    ) -> Result<(TaskTarget, usize), TuskError> {
+        match self {
+            TaskRef::Index(i) => {
+                let len = match target {
+                    TaskTarget::Day => day_items.len(),
+                    TaskTarget::Focus => focus_items.len(),
+                };
+                Ok((target, validate_index(*i, len)?))
+            }
+            TaskRef::Id(prefix) => {
+                let matches = |items: &[Item], t: TaskTarget| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, i)| i.id.starts_with(prefix.as_str()))
+                        .map(|(pos, i)| (t, pos, i.id == *prefix))
+                        .collect::<Vec<_>>()
+                };
+
+                let mut found = matches(day_items, TaskTarget::Day);
+                found.extend(matches(focus_items, TaskTarget::Focus));
+
+                if let Some((t, pos, _)) = found.iter().find(|(_, _, exact)| *exact) {
+                    return Ok((*t, *pos));
+                }
+
+                match found.as_slice() {
+                    [] => Err(TuskError::ItemNotFound {
+                        reference: self.to_string(),
+                    }),
+                    [(t, pos, _)] => Ok((*t, *pos)),
+                    _ => Err(TuskError::AmbiguousReference {
+                        reference: self.to_string(),
+                        matches: found.len(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Finds the position the reference points at in a single list of ids.
+    pub fn position(&self, ids: &[&str]) -> Result<usize, TuskError> {
+        match self {
+            TaskRef::Index(i) => validate_index(*i, ids.len()),
+            TaskRef::Id(prefix) => {
+                if let Some(pos) = ids.iter().position(|id| id == prefix) {
+                    return Ok(pos);
+                }
+
+                let found = ids
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, id)| id.starts_with(prefix.as_str()))
+                    .map(|(pos, _)| pos)
+                    .collect::<Vec<_>>();
+
+                match found.as_slice() {
+                    [] => Err(TuskError::ItemNotFound {
+                        reference: self.to_string(),
+                    }),
+                    [pos] => Ok(*pos),
+                    _ => Err(TuskError::AmbiguousReference {
+                        reference: self.to_string(),
+                        matches: found.len(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Swaps an id prefix for the full id of the item it resolved to, so
+    /// output always reports a reference that can be reused verbatim.
+    pub fn canonical(&self, item: &Item) -> TaskRef {
+        self.with_id(&item.id)
+    }
+
+    pub fn with_id(&self, id: &str) -> TaskRef {
+        match self {
+            TaskRef::Index(i) => TaskRef::Index(*i),
+            TaskRef::Id(_) => TaskRef::Id(id.to_string()),
+        }
+    }
+}
+
+/// A reference to an item, or with a dotted path such as `3.2` or `@x7Kp.2`,
+/// to one of its subtasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskPath {
+    pub task: TaskRef,
+    /// The 1-based position of the subtask.
+    pub subtask: Option<usize>,
+}
+
+impl TaskPath {
+    pub fn canonical(&self, item: &Item) -> TaskPath {
+        TaskPath {
+            task: self.task.canonical(item),
+            subtask: self.subtask,
+        }
+    }
+}
+
+impl From<TaskRef> for TaskPath {
+    fn from(task: TaskRef) -> Self {
+        TaskPath {
+            task,
+            subtask: None,
+        }
+    }
+}
+
+impl fmt::Display for TaskPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.subtask {
+            Some(n) => write!(f, "{}.{}", self.task, n),
+            None => write!(f, "{}", self.task),
+        }
+    }
+}
+
+impl fmt::Display for TaskRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskRef::Index(i) => write!(f, "#{}", i),
+            TaskRef::Id(id) => write!(f, "@{}", id),
+        }
+    }
+}
+
+pub fn parse_task_ref(s: &str) -> Result<TaskRef, String> {
+    let s = s.trim();
+
+    if let Some(id) = s.strip_prefix('@') {
+        return if is_id_like(id) {
+            Ok(TaskRef::Id(id.to_string()))
+        } else {
+            Err(format!(
+                "Invalid id '{s}'. Use an index or '@' followed by an id, e.g. @x7Kp"
+            ))
+        };
+    }
+
+    if let Ok(i) = s.parse::<usize>() {
+        return Ok(TaskRef::Index(i));
+    }
+
+    if is_id_like(s) {
+        Ok(TaskRef::Id(s.to_string()))
+    } else {
+        Err(format!(
+            "Invalid reference '{s}'. Use an index or '@' followed by an id, e.g. @x7Kp"
+        ))
+    }
+}
+
+pub fn parse_task_path(s: &str) -> Result<TaskPath, String> {
+    let s = s.trim();
+
+    let Some((task, subtask)) = s.rsplit_once('.') else {
+        return parse_task_ref(s).map(TaskPath::from);
+    };
+
+    let subtask = subtask.parse::<usize>().map_err(|_| {
+        format!(
+            "Invalid subtask '{s}'. Use the item's index or id, a dot and the subtask, e.g. 3.2"
+        )
+    })?;
+
+    Ok(TaskPath {
+        task: parse_task_ref(task)?,
+        subtask: Some(subtask),
+    })
+}
+
+fn is_id_like(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
@@ -2,16 +2,32 @@ use std::io::{self, IsTerminal};
 
 use colored::{ColoredString, Colorize};
 
-use crate::models::item::ItemPriority;
+use crate::{
+    models::item::{DueState, ItemPriority},
+    view::calendar::HeatLevel,
+};
 
 pub struct Theme {
     pub color: bool,
+    pub checkbox_open: Option<String>,
+    pub checkbox_done: Option<String>,
 }
 
 impl Theme {
     pub fn new(color: bool) -> Self {
         let color = io::stdout().is_terminal() && color;
-        Self { color }
+        Self {
+            color,
+            checkbox_open: None,
+            checkbox_done: None,
+        }
+    }
+
+    /// Swaps the built-in checkbox glyphs for the configured ones.
+    pub fn with_checkboxes(mut self, open: Option<String>, done: Option<String>) -> Self {
+        self.checkbox_open = open;
+        self.checkbox_done = done;
+        self
     }
 
     pub fn title(&self, s: &str) -> ColoredString {
@@ -84,8 +100,16 @@ impl Theme {
         }
     }
 
-    pub fn checkbox(&self, done: bool) -> &'static str {
-        if self.color && io::stdout().is_terminal() {
+    pub fn checkbox(&self, done: bool) -> &str {
+        let configured = if done {
+            &self.checkbox_done
+        } else {
+            &self.checkbox_open
+        };
+
+        if let Some(glyph) = configured {
+            glyph
+        } else if self.color && io::stdout().is_terminal() {
             if done { "☑" } else { "☐" }
         } else {
             if done { "[x]" } else { "[ ]" }
@@ -109,4 +133,42 @@ impl Theme {
             ItemPriority::Low => g.dimmed(),
         }
     }
+
+    pub fn due(&self, s: impl std::fmt::Display, state: Option<DueState>) -> ColoredString {
+        let s = s.to_string();
+
+        if !self.color {
+            return s.normal();
+        }
+
+        match state {
+            Some(DueState::Overdue) => s.red().bold(),
+            Some(DueState::Today) => s.yellow().bold(),
+            Some(DueState::Soon) => s.yellow(),
+            Some(DueState::Later) | None => s.dimmed(),
+        }
+    }
+
+    /// A calendar cell for how much of a day got done: a coloured square, or
+    /// without colour a glyph that gets denser the more was done.
+    pub fn heat(&self, level: HeatLevel) -> ColoredString {
+        if !self.color {
+            let glyph = match level {
+                HeatLevel::Empty => "·",
+                HeatLevel::Nothing => "░",
+                HeatLevel::Some => "▒",
+                HeatLevel::Most => "▓",
+                HeatLevel::All => "█",
+            };
+            return glyph.normal();
+        }
+
+        match level {
+            HeatLevel::Empty => "·".dimmed(),
+            HeatLevel::Nothing => "■".red(),
+            HeatLevel::Some => "■".yellow(),
+            HeatLevel::Most => "■".green(),
+            HeatLevel::All => "■".green().bold(),
+        }
+    }
 }
@@ -6,6 +6,8 @@ use std::{
 #[derive(Debug)]
 pub enum TuskError {
     IndexOutOfRange { index: usize, max: usize },
+    ItemNotFound { reference: String },
+    AmbiguousReference { reference: String, matches: usize },
     InvalidInput { message: String },
     Io(io::Error),
 }
@@ -14,6 +16,8 @@ impl TuskError {
     pub fn code(&self) -> &'static str {
         match self {
             TuskError::IndexOutOfRange { .. } => "index_out_of_range",
+            TuskError::ItemNotFound { .. } => "item_not_found",
+            TuskError::AmbiguousReference { .. } => "ambiguous_reference",
             TuskError::InvalidInput { .. } => "invalid_input",
             TuskError::Io(_) => "io_error",
         }
@@ -22,6 +26,8 @@ impl TuskError {
     pub fn hint(&self) -> Option<&'static str> {
         match self {
             TuskError::IndexOutOfRange { .. } => Some("Run 'tusk ls' to see valid item indices."),
+            TuskError::ItemNotFound { .. } => Some("Run 'tusk ls --verbose' to see item ids."),
+            TuskError::AmbiguousReference { .. } => Some("Use a longer id prefix."),
             TuskError::InvalidInput { .. } => None,
             TuskError::Io(_) => None,
         }
@@ -34,6 +40,12 @@ impl fmt::Display for TuskError {
             TuskError::IndexOutOfRange { index, max } => {
                 write!(f, "Index {} is out of range (max: {})", index, max)
             }
+            TuskError::ItemNotFound { reference } => {
+                write!(f, "No item matches {}", reference)
+            }
+            TuskError::AmbiguousReference { reference, matches } => {
+                write!(f, "{} matches {} items", reference, matches)
+            }
             TuskError::InvalidInput { message } => {
                 write!(f, "Invalid input: {}", message)
             }
@@ -1 +1,19 @@
-pub mod agenda;
\ No newline at end of file
+pub mod agenda;
+pub mod batch;
+pub mod calendar;
+pub mod config;
+pub mod due;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod migration;
+pub mod plan;
+pub mod pomodoro;
+pub mod rollover;
+pub mod search;
+pub mod stale;
+pub mod stats;
+pub mod store_migration;
+pub mod time_report;
+pub mod timer;
+pub mod vault;
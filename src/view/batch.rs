@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+
+use crate::{
+    models::item::{Item, Subtask},
+    utils::{render::ActionKind, task_ref::TaskPath, task_target::TaskTarget},
+};
+
+pub struct BatchEntry {
+    pub target: TaskTarget,
+    /// The reference as resolved before anything changed.
+    pub reference: TaskPath,
+    /// The item after the change, or as it was when it was removed.
+    pub item: Item,
+    /// The subtask changed, for dotted references.
+    pub subtask: Option<Subtask>,
+}
+
+/// Everything one `done`, `undone`, `rm` or `edit` changed when it was given
+/// a list, a range or filters rather than a single reference.
+pub struct Batch {
+    pub date: NaiveDate,
+    pub action: ActionKind,
+    pub entries: Vec<BatchEntry>,
+}
+
+impl Batch {
+    pub fn new(date: NaiveDate, action: ActionKind) -> Self {
+        Self {
+            date,
+            action,
+            entries: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use chrono::{Datelike, Local, Months, NaiveDate};
+
+use crate::models::{dayfile::DayFile, item::Item};
+
+/// What `tusk cal` covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarSpan {
+    /// The month starting on this date.
+    Month(NaiveDate),
+    Year(i32),
+}
+
+/// How much of a day got done, for colouring its cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatLevel {
+    /// No items.
+    Empty,
+    /// Items, none of them done.
+    Nothing,
+    /// Under half done.
+    Some,
+    /// Half or more done.
+    Most,
+    All,
+}
+
+impl HeatLevel {
+    pub const ALL: [HeatLevel; 5] = [
+        HeatLevel::Empty,
+        HeatLevel::Nothing,
+        HeatLevel::Some,
+        HeatLevel::Most,
+        HeatLevel::All,
+    ];
+}
+
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    pub total: usize,
+    pub done: usize,
+    /// Open items on the day that were migrated there.
+    pub open_migrated: usize,
+    /// Open items, from any day or the focus tasks, due on the day.
+    pub due: usize,
+}
+
+impl CalendarDay {
+    pub fn rate(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.done as f64 / self.total as f64)
+    }
+
+    pub fn level(&self) -> HeatLevel {
+        match self.rate() {
+            None => HeatLevel::Empty,
+            Some(0.0) => HeatLevel::Nothing,
+            Some(r) if r < 0.5 => HeatLevel::Some,
+            Some(r) if r < 1.0 => HeatLevel::Most,
+            Some(_) => HeatLevel::All,
+        }
+    }
+}
+
+/// Every day of a month or year with how much got done on it.
+pub struct Calendar {
+    pub span: CalendarSpan,
+    pub today: NaiveDate,
+    pub days: Vec<CalendarDay>,
+}
+
+impl Calendar {
+    pub fn new(span: CalendarSpan, today: NaiveDate) -> Option<Self> {
+        let (start, end) = match span {
+            CalendarSpan::Month(first) => (first, first.checked_add_months(Months::new(1))?),
+            CalendarSpan::Year(year) => (
+                NaiveDate::from_ymd_opt(year, 1, 1)?,
+                NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?,
+            ),
+        };
+
+        let days = start
+            .iter_days()
+            .take_while(|d| *d < end)
+            .map(|date| CalendarDay {
+                date,
+                total: 0,
+                done: 0,
+                open_migrated: 0,
+                due: 0,
+            })
+            .collect();
+
+        Some(Self { span, today, days })
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.days[0].date
+    }
+
+    /// Inclusive.
+    pub fn end(&self) -> NaiveDate {
+        self.days[self.days.len() - 1].date
+    }
+
+    fn day_mut(&mut self, date: NaiveDate) -> Option<&mut CalendarDay> {
+        let start = self.start();
+        let offset = usize::try_from((date - start).num_days()).ok()?;
+        self.days.get_mut(offset)
+    }
+
+    /// Counts a day's items, if the day is in the calendar.
+    pub fn add_day(&mut self, df: &DayFile) {
+        let Some(day) = self.day_mut(df.date) else {
+            return;
+        };
+
+        for item in &df.items {
+            day.total += 1;
+
+            if item.done_at.is_some() {
+                day.done += 1;
+            } else if item.migrations() > 0 {
+                day.open_migrated += 1;
+            }
+        }
+    }
+
+    /// Marks the days open items are due on.
+    pub fn add_due(&mut self, items: &[Item]) {
+        for item in items.iter().filter(|i| i.done_at.is_none()) {
+            if let Some(due) = item.due
+                && let Some(day) = self.day_mut(due.with_timezone(&Local).date_naive())
+            {
+                day.due += 1;
+            }
+        }
+    }
+
+    /// The days as weeks from Monday to Sunday, `None` for days either side
+    /// of the calendar.
+    pub fn weeks(&self) -> Vec<[Option<&CalendarDay>; 7]> {
+        let lead = self.start().weekday().num_days_from_monday() as usize;
+        let mut weeks = Vec::new();
+        let mut week = [None; 7];
+
+        for (i, day) in self.days.iter().enumerate() {
+            let slot = (lead + i) % 7;
+            week[slot] = Some(day);
+
+            if slot == 6 {
+                weeks.push(week);
+                week = [None; 7];
+            }
+        }
+
+        if week.iter().any(Option::is_some) {
+            weeks.push(week);
+        }
+
+        weeks
+    }
+
+    pub fn active_days(&self) -> usize {
+        self.days.iter().filter(|d| d.total > 0).count()
+    }
+
+    pub fn totals(&self) -> (usize, usize) {
+        self.days
+            .iter()
+            .fold((0, 0), |(done, total), d| (done + d.done, total + d.total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::{models::item::ItemPriority, utils::dates::ymd};
+
+    fn item(done: bool) -> Item {
+        let mut item = Item::new("item".to_string(), ItemPriority::Low, Vec::new(), None);
+        item.done_at = done.then(Utc::now);
+        item
+    }
+
+    fn day(date: &str, items: Vec<Item>) -> DayFile {
+        let mut df = DayFile::new(ymd(date));
+        df.items = items;
+        df
+    }
+
+    #[test]
+    fn month_is_laid_out_from_monday() {
+        let cal = Calendar::new(CalendarSpan::Month(ymd("2025-10-01")), ymd("2025-10-17")).unwrap();
+
+        assert_eq!(
+            (cal.start(), cal.end()),
+            (ymd("2025-10-01"), ymd("2025-10-31"))
+        );
+
+        // The 1st is a Wednesday.
+        let weeks = cal.weeks();
+        assert_eq!(weeks.len(), 5);
+        assert!(weeks[0][..2].iter().all(Option::is_none));
+        assert_eq!(weeks[0][2].map(|d| d.date), Some(ymd("2025-10-01")));
+        assert_eq!(weeks[4][4].map(|d| d.date), Some(ymd("2025-10-31")));
+        assert!(weeks[4][5..].iter().all(Option::is_none));
+
+        let year = Calendar::new(CalendarSpan::Year(2024), ymd("2024-06-01")).unwrap();
+        assert_eq!(year.days.len(), 366);
+    }
+
+    #[test]
+    fn days_are_counted_and_coloured() {
+        let mut cal =
+            Calendar::new(CalendarSpan::Month(ymd("2025-10-01")), ymd("2025-10-17")).unwrap();
+
+        let mut migrated = item(false);
+        migrated.record_migration(ymd("2025-10-01"));
+
+        cal.add_day(&day("2025-10-02", vec![item(true), item(false), migrated]));
+        cal.add_day(&day("2025-10-03", vec![item(true), item(true)]));
+        cal.add_day(&day("2025-10-05", vec![item(true), item(false)]));
+        cal.add_day(&day("2025-11-01", vec![item(true)]));
+
+        let noon = ymd("2025-10-20").and_hms_opt(12, 0, 0).unwrap();
+        let due = noon.and_local_timezone(Local).unwrap().with_timezone(&Utc);
+        let mut open = item(false);
+        open.due = Some(due);
+        let mut done = item(true);
+        done.due = Some(due);
+        let mut later = item(false);
+        later.due = Some(due + Duration::days(30));
+        cal.add_due(&[open, done, later]);
+
+        let on = |date: &str| cal.days.iter().find(|d| d.date == ymd(date)).unwrap();
+        let levels =
+            ["2025-10-01", "2025-10-02", "2025-10-03", "2025-10-05"].map(|d| on(d).level());
+
+        assert_eq!(
+            levels,
+            [
+                HeatLevel::Empty,
+                HeatLevel::Some,
+                HeatLevel::All,
+                HeatLevel::Most
+            ]
+        );
+        assert_eq!(on("2025-10-02").open_migrated, 1);
+        assert_eq!(on("2025-10-20").due, 1);
+        assert_eq!(cal.active_days(), 3);
+        assert_eq!(cal.totals(), (4, 7));
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Serialize;
+
+use crate::models::settings::Settings;
+
+/// Where a config value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    Default,
+    Global,
+    Vault,
+}
+
+impl ConfigSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigSource::Default => "default",
+            ConfigSource::Global => "global",
+            ConfigSource::Vault => "vault",
+        }
+    }
+}
+
+/// One config key with the value in use and where it was set.
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: Option<String>,
+    pub source: ConfigSource,
+}
+
+impl ConfigEntry {
+    /// Looks `key` up in the vault's settings, then the global ones, then the built-in default.
+    pub fn resolve(key: &str, global: &Settings, vault: &Settings) -> Result<Self, String> {
+        let (value, source) = if let Some(v) = vault.get(key)? {
+            (Some(v), ConfigSource::Vault)
+        } else if let Some(v) = global.get(key)? {
+            (Some(v), ConfigSource::Global)
+        } else {
+            (
+                Settings::builtin(key).map(|v| v.to_string()),
+                ConfigSource::Default,
+            )
+        };
+
+        Ok(Self {
+            key: key.to_string(),
+            value,
+            source,
+        })
+    }
+
+    /// Every key, with the aliases from both files.
+    pub fn all(global: &Settings, vault: &Settings) -> Vec<Self> {
+        vault
+            .or(global)
+            .keys()
+            .iter()
+            .filter_map(|key| Self::resolve(key, global, vault).ok())
+            .collect()
+    }
+}
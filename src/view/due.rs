@@ -0,0 +1,53 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::models::item::{DueState, Item};
+
+pub struct DueEntry {
+    /// The day file the item lives in, `None` for focus items.
+    pub date: Option<NaiveDate>,
+    pub index: usize,
+    pub state: DueState,
+    pub item: Item,
+}
+
+pub struct DueReport {
+    pub now: DateTime<Utc>,
+    pub soon_days: i64,
+    pub entries: Vec<DueEntry>,
+}
+
+impl DueReport {
+    pub fn new(now: DateTime<Utc>, soon_days: i64) -> Self {
+        Self {
+            now,
+            soon_days,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds any item from `items` that is overdue, due today or due soon.
+    pub fn collect(&mut self, date: Option<NaiveDate>, items: &[Item]) {
+        for (idx, item) in items.iter().enumerate() {
+            if let Some(state) = item.due_state(self.now, self.soon_days)
+                && state != DueState::Later
+            {
+                self.entries.push(DueEntry {
+                    date,
+                    index: idx + 1,
+                    state,
+                    item: item.clone(),
+                });
+            }
+        }
+
+        self.entries.sort_by_key(|e| e.item.due);
+    }
+
+    pub fn with_state(&self, state: DueState) -> impl Iterator<Item = &DueEntry> {
+        self.entries.iter().filter(move |e| e.state == state)
+    }
+
+    pub fn count(&self, state: DueState) -> usize {
+        self.with_state(state).count()
+    }
+}
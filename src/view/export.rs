@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use crate::{
+    export::ExportFormat,
+    models::{dayfile::DayFile, focus_file::FocusFile},
+};
+
+/// The days, and optionally the focus tasks, that `tusk export` writes out.
+pub struct Export {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<DayFile>,
+    pub focus: Option<FocusFile>,
+}
+
+impl Export {
+    pub fn items(&self) -> usize {
+        let days: usize = self.days.iter().map(|df| df.items.len()).sum();
+        days + self.focus.as_ref().map_or(0, |ff| ff.items.len())
+    }
+
+    /// Splits the export into one per day, plus one for the focus tasks,
+    /// for writing into a directory.
+    pub fn split(self) -> Vec<(String, Export)> {
+        let mut parts: Vec<(String, Export)> = self
+            .days
+            .into_iter()
+            .map(|df| {
+                let name = df.date.to_string();
+                let part = Export {
+                    from: df.date,
+                    to: df.date,
+                    days: vec![df],
+                    focus: None,
+                };
+
+                (name, part)
+            })
+            .collect();
+
+        if let Some(ff) = self.focus {
+            parts.push((
+                "focus".to_string(),
+                Export {
+                    from: self.from,
+                    to: self.to,
+                    days: Vec::new(),
+                    focus: Some(ff),
+                },
+            ));
+        }
+
+        parts
+    }
+}
+
+/// What `tusk export` wrote and where.
+pub struct ExportReport {
+    pub format: ExportFormat,
+    pub path: PathBuf,
+    pub files: usize,
+    pub days: usize,
+    pub items: usize,
+}
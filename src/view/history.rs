@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::journal::{Journal, JournalFile, Operation, OperationKind};
+
+pub struct HistoryEntry {
+    pub id: String,
+    pub command: String,
+    pub at: DateTime<Utc>,
+    pub kind: OperationKind,
+    pub files: Vec<JournalFile>,
+    /// Whether the operation has been undone and not redone since.
+    pub undone: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(op: &Operation, undone: bool) -> Self {
+        Self {
+            id: op.id.clone(),
+            command: op.command.clone(),
+            at: op.at,
+            kind: op.kind.clone(),
+            files: op.files().into_iter().cloned().collect(),
+            undone,
+        }
+    }
+
+    /// The files the operation touched, e.g. `2025-10-20, focus`.
+    pub fn files_label(&self) -> String {
+        self.files
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub struct History {
+    /// Most recent first.
+    pub entries: Vec<HistoryEntry>,
+    pub total: usize,
+}
+
+impl History {
+    pub fn new(journal: &Journal, limit: usize) -> Self {
+        Self {
+            entries: journal
+                .operations
+                .iter()
+                .rev()
+                .take(limit)
+                .map(|op| HistoryEntry::new(op, journal.is_undone(&op.id)))
+                .collect(),
+            total: journal.operations.len(),
+        }
+    }
+}
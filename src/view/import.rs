@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::import::{ImportFormat, Target};
+
+pub enum ImportOutcome {
+    Imported,
+    Skipped(String),
+    /// Invalid in the source file, so it couldn't be imported.
+    Rejected(String),
+}
+
+/// One entry of the source file and what became of it.
+pub struct ImportEntry {
+    pub entry: usize,
+    pub text: String,
+    pub target: Option<Target>,
+    pub outcome: ImportOutcome,
+}
+
+/// What `tusk import` took in, or would take in with `--dry-run`.
+pub struct ImportReport {
+    pub format: ImportFormat,
+    pub path: PathBuf,
+    pub dry_run: bool,
+    pub entries: Vec<ImportEntry>,
+}
+
+impl ImportReport {
+    pub fn imported(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.outcome, ImportOutcome::Imported))
+            .count()
+    }
+
+    pub fn skipped(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.outcome, ImportOutcome::Skipped(_)))
+    }
+
+    pub fn rejected(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.outcome, ImportOutcome::Rejected(_)))
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::models::{dayfile::DayFile, item::Item};
+
+/// What happens to an item picked for migration, asked per item with
+/// `--interactive`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationChoice {
+    Move,
+    Keep,
+    Drop,
+    Focus,
+}
+
+pub struct Migration {
+    /// The from day as it was before migrating.
+    pub from: DayFile,
+    pub to: NaiveDate,
+    pub dry_run: bool,
+    /// Whether items were copied to `to`, leaving the originals in place.
+    pub copy: bool,
+    /// The items as they arrive on `to`.
+    pub moved: Vec<Item>,
+    /// The items sent to the focus tasks instead.
+    pub focused: Vec<Item>,
+    pub dropped: Vec<Item>,
+    /// Open items that stay where they are, not picked or kept when asked.
+    pub kept: usize,
+    /// Picked items that were already done and stay where they are, with
+    /// their 0-based positions.
+    pub already_done: Vec<(usize, Item)>,
+}
+
+impl Migration {
+    pub fn new(from: DayFile, to: NaiveDate, dry_run: bool, copy: bool) -> Self {
+        Self {
+            from,
+            to,
+            dry_run,
+            copy,
+            moved: Vec::new(),
+            focused: Vec::new(),
+            dropped: Vec::new(),
+            kept: 0,
+            already_done: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.focused.is_empty() && self.dropped.is_empty()
+    }
+}
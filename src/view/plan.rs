@@ -0,0 +1,151 @@
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+use crate::{
+    models::{dayfile::DayFile, focus_file::FocusFile},
+    utils::{day_ref::DayRef, helpers::SummaryStats},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanSpan {
+    Week,
+    Month,
+}
+
+impl PlanSpan {
+    /// The command that shows this span.
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            PlanSpan::Week => "week",
+            PlanSpan::Month => "month",
+        }
+    }
+}
+
+/// One day of a plan, with the prefix its items are referenced by.
+pub struct PlanDay {
+    pub day: DayRef,
+    pub dayfile: DayFile,
+}
+
+impl PlanDay {
+    pub fn date(&self) -> NaiveDate {
+        self.dayfile.date
+    }
+
+    pub fn stats(&self) -> SummaryStats {
+        SummaryStats::from(&self.dayfile)
+    }
+
+    /// The reference for the item at a 1-based index, e.g. `mon:3`.
+    pub fn reference(&self, index: usize) -> String {
+        format!("{}:{index}", self.day)
+    }
+}
+
+/// Every day of a week or month, with the focus items above them.
+pub struct Plan {
+    pub span: PlanSpan,
+    pub today: NaiveDate,
+    pub focus: FocusFile,
+    pub days: Vec<PlanDay>,
+}
+
+impl Plan {
+    /// Empty days for the week (Monday to Sunday) or month around `date`.
+    pub fn new(span: PlanSpan, date: NaiveDate, today: NaiveDate, focus: FocusFile) -> Self {
+        let (start, end) = match span {
+            PlanSpan::Week => {
+                let week = date.week(Weekday::Mon);
+                (week.first_day(), week.last_day())
+            }
+            PlanSpan::Month => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = start
+                    .checked_add_months(Months::new(1))
+                    .and_then(|d| d.pred_opt())
+                    .unwrap_or(start);
+                (start, end)
+            }
+        };
+
+        let days = start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .map(|d| PlanDay {
+                day: match span {
+                    PlanSpan::Week => DayRef::Weekday(d.weekday()),
+                    PlanSpan::Month => DayRef::MonthDay(d.day()),
+                },
+                dayfile: DayFile::new(d),
+            })
+            .collect();
+
+        Self {
+            span,
+            today,
+            focus,
+            days,
+        }
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.days[0].date()
+    }
+
+    /// Inclusive.
+    pub fn end(&self) -> NaiveDate {
+        self.days[self.days.len() - 1].date()
+    }
+
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.days.iter().map(PlanDay::date)
+    }
+
+    /// Puts a loaded day in its place, if it's in the plan.
+    pub fn add_day(&mut self, df: DayFile) {
+        let start = self.start();
+
+        if let Ok(offset) = usize::try_from((df.date - start).num_days())
+            && let Some(day) = self.days.get_mut(offset)
+        {
+            day.dayfile = df;
+        }
+    }
+
+    /// The counts across every day, focus items aside.
+    pub fn stats(&self) -> SummaryStats {
+        self.days
+            .iter()
+            .map(PlanDay::stats)
+            .fold(SummaryStats::default(), |acc, s| acc + s)
+    }
+
+    /// The days as weeks from Monday to Sunday, `None` for days either side
+    /// of the plan.
+    pub fn weeks(&self) -> Vec<[Option<&PlanDay>; 7]> {
+        let lead = self.start().weekday().num_days_from_monday() as usize;
+        let mut weeks = Vec::new();
+        let mut week = [None; 7];
+
+        for (i, day) in self.days.iter().enumerate() {
+            let slot = (lead + i) % 7;
+            week[slot] = Some(day);
+
+            if slot == 6 {
+                weeks.push(std::mem::take(&mut week));
+            }
+        }
+
+        if week.iter().any(Option::is_some) {
+            weeks.push(week);
+        }
+
+        weeks
+    }
+
+    /// A date inside the plan to pass as `--date`, so day references find the
+    /// same days, or `None` when the plan already holds today.
+    pub fn anchor(&self) -> Option<NaiveDate> {
+        (!(self.start()..=self.end()).contains(&self.today)).then(|| self.start())
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::Duration;
+
+use crate::{models::item::Item, utils::task_ref::TaskRef};
+
+/// The lengths `tusk focus pomodoro` runs with.
+#[derive(Debug, Clone, Copy)]
+pub struct PomodoroPlan {
+    pub work: Duration,
+    pub rest: Duration,
+    pub rounds: u32,
+}
+
+/// How a run of `tusk focus pomodoro` went.
+pub struct PomodoroRun {
+    pub reference: TaskRef,
+    pub plan: PomodoroPlan,
+    /// The focus item as it is after the run, with a session per round.
+    pub item: Item,
+    pub completed: u32,
+    pub marked_done: bool,
+}
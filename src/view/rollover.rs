@@ -0,0 +1,10 @@
+use chrono::NaiveDate;
+
+use crate::models::item::Item;
+
+/// The open items moved forward to today on the first run of the day.
+pub struct Rollover {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub items: Vec<Item>,
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+
+use crate::{models::item::Item, utils::query::Query};
+
+pub struct SearchHit {
+    /// The day file the item lives in, `None` for focus items.
+    pub date: Option<NaiveDate>,
+    pub index: usize,
+    pub item: Item,
+}
+
+pub struct SearchResults {
+    pub query: String,
+    pub days_searched: usize,
+    pub hits: Vec<SearchHit>,
+}
+
+impl SearchResults {
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            days_searched: 0,
+            hits: Vec::new(),
+        }
+    }
+
+    /// Adds every item in `items` matching `query`. Focus items are matched
+    /// against the date they were created for `before:`/`after:` bounds.
+    pub fn collect(&mut self, query: &Query, date: Option<NaiveDate>, items: &[Item]) {
+        for (idx, item) in items.iter().enumerate() {
+            let on = date.unwrap_or_else(|| item.created_at.date_naive());
+
+            if query.matches(item, on) {
+                self.hits.push(SearchHit {
+                    date,
+                    index: idx + 1,
+                    item: item.clone(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::NaiveDate;
+
+use crate::models::item::{Item, ItemPriority};
+
+/// What `tusk stale` does with the items it finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleAction {
+    List,
+    Drop,
+    Focus,
+    Reprioritise(ItemPriority),
+}
+
+pub struct StaleEntry {
+    pub date: NaiveDate,
+    pub index: usize,
+    pub item: Item,
+}
+
+pub struct StaleReport {
+    pub today: NaiveDate,
+    pub min_migrations: usize,
+    pub action: StaleAction,
+    pub entries: Vec<StaleEntry>,
+}
+
+impl StaleReport {
+    pub fn new(today: NaiveDate, min_migrations: usize, action: StaleAction) -> Self {
+        Self {
+            today,
+            min_migrations,
+            action,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Whether an item is open and has been migrated at least `min_migrations` times.
+    pub fn is_stale(&self, item: &Item) -> bool {
+        item.done_at.is_none() && item.migrations() >= self.min_migrations
+    }
+
+    /// Adds the stale items of a day, returning how many there were.
+    pub fn collect(&mut self, date: NaiveDate, items: &[Item]) -> usize {
+        let before = self.entries.len();
+
+        for (idx, item) in items.iter().enumerate() {
+            if self.is_stale(item) {
+                self.entries.push(StaleEntry {
+                    date,
+                    index: idx + 1,
+                    item: item.clone(),
+                });
+            }
+        }
+
+        // Most migrated first, then oldest.
+        self.entries
+            .sort_by_key(|e| (std::cmp::Reverse(e.item.migrations()), e.item.created_at));
+
+        self.entries.len() - before
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, Duration, NaiveDate};
+
+use crate::{
+    models::{
+        dayfile::DayFile,
+        item::{Item, ItemPriority},
+    },
+    utils::stats_range::{Bucket, StatsRange},
+};
+
+/// How many items there were and how many got done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub total: usize,
+    pub done: usize,
+}
+
+impl Counts {
+    fn add(&mut self, item: &Item) {
+        self.total += 1;
+        self.done += usize::from(item.done_at.is_some());
+    }
+
+    /// The share done, `None` without any items.
+    pub fn rate(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.done as f64 / self.total as f64)
+    }
+}
+
+/// The completion of one stretch of the range, a day, week or month.
+pub struct Period {
+    pub start: NaiveDate,
+    /// Inclusive.
+    pub end: NaiveDate,
+    pub counts: Counts,
+}
+
+/// Numbers for `tusk stats` over the day lists in a range ending today.
+pub struct StatsReport {
+    pub range: StatsRange,
+    pub start: NaiveDate,
+    /// Today, inclusive.
+    pub end: NaiveDate,
+    pub counts: Counts,
+    /// Days in the range with any items.
+    pub active_days: usize,
+    /// Items that were migrated at least once.
+    pub migrated: usize,
+    /// The mean time from an item being added to it being done.
+    pub time_to_complete: Option<Duration>,
+    /// Days in a row with every item done, up to today. Days without items
+    /// don't count either way, and today only adds once it's all done.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub periods: Vec<Period>,
+    pub by_priority: BTreeMap<ItemPriority, Counts>,
+    pub by_tag: BTreeMap<String, Counts>,
+    /// Items per weekday of the day they're on, Monday first.
+    pub weekdays: [Counts; 7],
+}
+
+impl StatsReport {
+    pub fn new(range: StatsRange, start: NaiveDate, end: NaiveDate, dayfiles: &[DayFile]) -> Self {
+        let mut report = Self {
+            range,
+            start,
+            end,
+            counts: Counts::default(),
+            active_days: 0,
+            migrated: 0,
+            time_to_complete: None,
+            current_streak: 0,
+            longest_streak: 0,
+            periods: periods(range.bucket(), start, end),
+            by_priority: BTreeMap::new(),
+            by_tag: BTreeMap::new(),
+            weekdays: [Counts::default(); 7],
+        };
+
+        let mut days = BTreeMap::new();
+        let mut completion_times = Vec::new();
+
+        for df in dayfiles
+            .iter()
+            .filter(|df| (start..=end).contains(&df.date))
+        {
+            let mut day = Counts::default();
+
+            for item in &df.items {
+                day.add(item);
+                report.counts.add(item);
+                report.weekdays[df.date.weekday().num_days_from_monday() as usize].add(item);
+                report
+                    .by_priority
+                    .entry(item.priority)
+                    .or_default()
+                    .add(item);
+
+                for tag in &item.tags {
+                    report
+                        .by_tag
+                        .entry(tag.to_lowercase())
+                        .or_default()
+                        .add(item);
+                }
+
+                if item.migrations() > 0 {
+                    report.migrated += 1;
+                }
+
+                if let Some(done_at) = item.done_at {
+                    completion_times.push((done_at - item.created_at).max(Duration::zero()));
+                }
+            }
+
+            if let Some(period) = report
+                .periods
+                .iter_mut()
+                .find(|p| (p.start..=p.end).contains(&df.date))
+            {
+                period.counts.total += day.total;
+                period.counts.done += day.done;
+            }
+
+            if day.total > 0 {
+                days.insert(df.date, day);
+            }
+        }
+
+        report.active_days = days.len();
+
+        if !completion_times.is_empty() {
+            let sum: Duration = completion_times.iter().copied().sum();
+            report.time_to_complete = Some(sum / completion_times.len() as i32);
+        }
+
+        let mut run = 0;
+        for day in days.values() {
+            run = if day.done == day.total { run + 1 } else { 0 };
+            report.longest_streak = report.longest_streak.max(run);
+        }
+
+        report.current_streak = days
+            .iter()
+            .rev()
+            .skip_while(|(date, day)| **date == end && day.done < day.total)
+            .take_while(|(_, day)| day.done == day.total)
+            .count();
+
+        report
+    }
+
+    /// The mean number of items a day across the whole range.
+    pub fn items_per_day(&self) -> f64 {
+        let days = (self.end - self.start).num_days() + 1;
+        self.counts.total as f64 / days.max(1) as f64
+    }
+
+    /// The share of items that were migrated at least once.
+    pub fn migration_rate(&self) -> Option<f64> {
+        (self.counts.total > 0).then(|| self.migrated as f64 / self.counts.total as f64)
+    }
+
+    /// Tags with the most items first.
+    pub fn tags(&self) -> Vec<(&str, Counts)> {
+        let mut tags = self
+            .by_tag
+            .iter()
+            .map(|(t, c)| (t.as_str(), *c))
+            .collect::<Vec<_>>();
+
+        tags.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        tags
+    }
+}
+
+/// A share as a whole percentage, or a dash when there's nothing to share.
+pub fn format_rate(rate: Option<f64>) -> String {
+    match rate {
+        Some(r) => format!("{:.0}%", r * 100.0),
+        None => "–".to_string(),
+    }
+}
+
+fn periods(bucket: Bucket, start: NaiveDate, end: NaiveDate) -> Vec<Period> {
+    let mut periods = Vec::new();
+    let mut from = start;
+
+    while from <= end {
+        let next = match bucket {
+            Bucket::Day => from.checked_add_days(Days::new(1)),
+            Bucket::Week => from.checked_add_days(Days::new(7)),
+            Bucket::Month => from
+                .with_day(1)
+                .and_then(|d| d.checked_add_months(chrono::Months::new(1))),
+        };
+
+        let to = next
+            .and_then(|n| n.pred_opt())
+            .map_or(end, |last| last.min(end));
+
+        periods.push(Period {
+            start: from,
+            end: to,
+            counts: Counts::default(),
+        });
+
+        match to.succ_opt() {
+            Some(n) => from = n,
+            None => break,
+        }
+    }
+
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{dates::ymd, stats_range::RangeUnit};
+
+    /// An item on `date`, done after `hours` if given.
+    fn item(date: NaiveDate, tag: &str, hours: Option<i64>) -> Item {
+        let mut item = Item::new(
+            tag.to_string(),
+            ItemPriority::Low,
+            vec![tag.to_string()],
+            None,
+        );
+        item.created_at = date.and_hms_opt(9, 0, 0).unwrap().and_utc();
+        item.done_at = hours.map(|h| item.created_at + Duration::hours(h));
+        item
+    }
+
+    fn day(date: &str, items: &[(&str, Option<i64>)]) -> DayFile {
+        let mut df = DayFile::new(ymd(date));
+        df.items = items
+            .iter()
+            .map(|(tag, hours)| item(df.date, tag, *hours))
+            .collect();
+        df
+    }
+
+    #[test]
+    fn report_counts_the_days_in_range() {
+        let mut migrated = day("2025-09-10", &[("home", None)]);
+        migrated.items[0].record_migration(ymd("2025-09-09"));
+
+        let dayfiles = [
+            day("2025-09-01", &[("old", Some(1))]),
+            day("2025-09-08", &[("Work", Some(2)), ("home", Some(4))]),
+            day("2025-09-09", &[("work", Some(3))]),
+            migrated,
+            day("2025-09-12", &[("work", Some(3))]),
+            day("2025-09-14", &[("work", None)]),
+        ];
+
+        let range = StatsRange {
+            count: 7,
+            unit: RangeUnit::Day,
+        };
+        let (start, end) = (ymd("2025-09-08"), ymd("2025-09-14"));
+        let report = StatsReport::new(range, start, end, &dayfiles);
+
+        assert_eq!(report.counts, Counts { total: 6, done: 4 });
+        assert_eq!(report.active_days, 5);
+        assert_eq!(report.migrated, 1);
+        assert_eq!(report.time_to_complete, Some(Duration::hours(3)));
+
+        // An unfinished today doesn't break the streak, the 10th does.
+        assert_eq!(report.current_streak, 1);
+        assert_eq!(report.longest_streak, 2);
+
+        assert_eq!(report.periods.len(), 7);
+        assert_eq!(report.periods[0].counts, Counts { total: 2, done: 2 });
+        assert_eq!(report.weekdays[0], Counts { total: 2, done: 2 });
+        assert_eq!(
+            report.tags(),
+            [
+                ("work", Counts { total: 4, done: 3 }),
+                ("home", Counts { total: 2, done: 1 })
+            ]
+        );
+    }
+}
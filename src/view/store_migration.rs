@@ -0,0 +1,22 @@
+use crate::models::vault_config::StoreBackend;
+
+/// What `tusk store migrate` copied from one backend to another.
+pub struct StoreMigration {
+    pub from: StoreBackend,
+    pub to: StoreBackend,
+    pub days: usize,
+    pub items: usize,
+    pub focus_items: usize,
+}
+
+impl StoreMigration {
+    pub fn new(from: StoreBackend, to: StoreBackend) -> Self {
+        Self {
+            from,
+            to,
+            days: 0,
+            items: 0,
+            focus_items: 0,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::item::{Item, ItemPriority};
+
+/// Time logged on items over a range of days, counted towards the day each
+/// session started.
+pub struct TimeReport {
+    pub start: NaiveDate,
+    /// Exclusive, like the review it belongs to.
+    pub end: NaiveDate,
+    pub total: Duration,
+    pub by_day: BTreeMap<NaiveDate, Duration>,
+    pub by_tag: BTreeMap<String, Duration>,
+    pub by_priority: BTreeMap<ItemPriority, Duration>,
+}
+
+impl TimeReport {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start,
+            end,
+            total: Duration::zero(),
+            by_day: BTreeMap::new(),
+            by_tag: BTreeMap::new(),
+            by_priority: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the item's sessions that fall in the range.
+    pub fn add(&mut self, item: &Item) {
+        for session in item
+            .sessions
+            .iter()
+            .filter(|s| (self.start..self.end).contains(&s.day()))
+        {
+            let spent = session.duration();
+
+            self.total += spent;
+            *self.by_day.entry(session.day()).or_default() += spent;
+            *self.by_priority.entry(item.priority).or_default() += spent;
+
+            for tag in &item.tags {
+                *self.by_tag.entry(tag.to_lowercase()).or_default() += spent;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total.is_zero()
+    }
+
+    /// Tags with the most time first.
+    pub fn tags(&self) -> Vec<(&str, Duration)> {
+        let mut tags = self
+            .by_tag
+            .iter()
+            .map(|(t, d)| (t.as_str(), *d))
+            .collect::<Vec<_>>();
+
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        tags
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::{models::item::Item, utils::task_target::TaskTarget};
+
+/// A timer on an item, just started or just stopped.
+pub struct TimerEntry {
+    pub target: TaskTarget,
+    pub date: NaiveDate,
+    /// The item as it is after the change, with the new session when stopped.
+    pub item: Item,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+}
+
+impl TimerEntry {
+    /// How long the timer ran, `None` while it's still running.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.stopped_at.map(|end| end - self.started_at)
+    }
+}
+
+/// What `tusk start` and `tusk stop` did. Starting a timer stops the one
+/// already running, so a start can carry both.
+pub struct TimerChange {
+    pub stopped: Option<TimerEntry>,
+    pub started: Option<TimerEntry>,
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::item::Item;
+
+/// One line of `tusk vault ls`.
+pub struct VaultSummary {
+    pub name: String,
+    pub current: bool,
+    pub days: usize,
+    pub open: usize,
+    pub done: usize,
+    pub focus: usize,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+impl VaultSummary {
+    pub fn new(name: &str, current: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            current,
+            days: 0,
+            open: 0,
+            done: 0,
+            focus: 0,
+            last_activity: None,
+        }
+    }
+
+    /// Counts a day's items, open and done.
+    pub fn add_day(&mut self, items: &[Item]) {
+        self.days += 1;
+
+        for item in items {
+            if item.done_at.is_some() {
+                self.done += 1;
+            } else {
+                self.open += 1;
+            }
+
+            self.touch(item);
+        }
+    }
+
+    pub fn add_focus(&mut self, items: &[Item]) {
+        self.focus += items.len();
+        items.iter().for_each(|item| self.touch(item));
+    }
+
+    pub fn touched_at(&mut self, at: DateTime<Utc>) {
+        self.last_activity = self.last_activity.max(Some(at));
+    }
+
+    pub fn tasks(&self) -> usize {
+        self.open + self.done + self.focus
+    }
+
+    fn touch(&mut self, item: &Item) {
+        self.touched_at(item.created_at);
+
+        if let Some(done_at) = item.done_at {
+            self.touched_at(done_at);
+        }
+    }
+}
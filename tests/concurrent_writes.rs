@@ -0,0 +1,59 @@
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+
+const TUSK: &str = env!("CARGO_BIN_EXE_tusk");
+const DATE: &str = "2020-01-01";
+
+#[test]
+fn parallel_adds_never_lose_items() {
+    let data_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let runs = 32;
+
+    let children = (0..runs)
+        .map(|n| {
+            Command::new(TUSK)
+                .arg("--data-dir")
+                .arg(data_dir.path())
+                .args(["--no-colour", "add", "--date", DATE, &format!("item {n}")])
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("failed to spawn tusk")
+        })
+        .collect::<Vec<_>>();
+
+    for child in children {
+        let output = child.wait_with_output().expect("tusk didn't finish");
+        assert!(
+            output.status.success(),
+            "tusk add failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let output = Command::new(TUSK)
+        .arg("--data-dir")
+        .arg(data_dir.path())
+        .args(["--output", "json", "ls", "--date", DATE])
+        .output()
+        .expect("failed to run tusk ls");
+
+    assert!(output.status.success());
+
+    let response: Value = serde_json::from_slice(&output.stdout).expect("ls output isn't JSON");
+    let items = response["data"]["items"]
+        .as_array()
+        .expect("ls output has no items");
+
+    let mut texts = items
+        .iter()
+        .map(|i| i["text"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    texts.sort();
+
+    let mut expected = (0..runs).map(|n| format!("item {n}")).collect::<Vec<_>>();
+    expected.sort();
+
+    assert_eq!(texts, expected);
+}